@@ -0,0 +1,244 @@
+use super::Collector;
+use fastfield::FastFieldReader;
+use schema::{Field, GeoPoint};
+use SegmentReader;
+use SegmentLocalId;
+use DocAddress;
+use Result;
+use std::collections::BinaryHeap;
+use std::cmp::Ordering;
+use DocId;
+use Score;
+
+// The heap keeps the farthest of the retained documents on top.
+#[derive(Clone, Copy)]
+struct GlobalDistancedDoc {
+    distance: f64,
+    doc_address: DocAddress,
+}
+
+impl PartialOrd for GlobalDistancedDoc {
+    fn partial_cmp(&self, other: &GlobalDistancedDoc) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GlobalDistancedDoc {
+    #[inline]
+    fn cmp(&self, other: &GlobalDistancedDoc) -> Ordering {
+        self.distance
+            .partial_cmp(&other.distance)
+            .unwrap_or_else(|| self.doc_address.cmp(&other.doc_address))
+    }
+}
+
+impl PartialEq for GlobalDistancedDoc {
+    fn eq(&self, other: &GlobalDistancedDoc) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for GlobalDistancedDoc {}
+
+/// The `GeoDistanceCollector` keeps track of the K documents
+/// the closest to a given origin.
+///
+/// The location of the documents is read from a geo point fast field.
+/// Documents without any value for the field are not skipped: they are
+/// considered to be located at the south-west corner of the map (`-90, -180`).
+/// To leave them out, the field can also be indexed, and the query restricted
+/// to the documents having a location with an
+/// [`ExistsQuery`](../query/struct.ExistsQuery.html).
+///
+/// The implementation is based on a `BinaryHeap`.
+/// The theorical complexity is `O(n log K)`.
+pub struct GeoDistanceCollector {
+    field: Field,
+    origin: GeoPoint,
+    limit: usize,
+    heap: BinaryHeap<GlobalDistancedDoc>,
+    segment_id: u32,
+    ff_reader: Option<FastFieldReader<GeoPoint>>,
+}
+
+impl GeoDistanceCollector {
+    /// Creates a geo distance collector, with a number of documents equal to "limit".
+    ///
+    /// # Panics
+    /// The method panics if limit is 0
+    pub fn with_limit(field: Field, origin: GeoPoint, limit: usize) -> GeoDistanceCollector {
+        if limit < 1 {
+            panic!("Limit must be strictly greater than 0.");
+        }
+        GeoDistanceCollector {
+            field,
+            origin,
+            limit,
+            heap: BinaryHeap::with_capacity(limit),
+            segment_id: 0,
+            ff_reader: None,
+        }
+    }
+
+    /// Returns the K closest documents, sorted by increasing distance.
+    ///
+    /// Calling this method triggers the sort.
+    /// The result of the sort is not cached.
+    pub fn docs(&self) -> Vec<DocAddress> {
+        self.distance_docs()
+            .into_iter()
+            .map(|distance_doc| distance_doc.1)
+            .collect()
+    }
+
+    /// Returns the K closest documents with their distance (in meters)
+    /// to the origin, sorted by increasing distance.
+    ///
+    /// Calling this method triggers the sort.
+    /// The result of the sort is not cached.
+    pub fn distance_docs(&self) -> Vec<(f64, DocAddress)> {
+        let mut distanced_docs: Vec<GlobalDistancedDoc> = self.heap.iter().cloned().collect();
+        distanced_docs.sort();
+        distanced_docs
+            .into_iter()
+            .map(
+                |GlobalDistancedDoc {
+                     distance,
+                     doc_address,
+                 }| (distance, doc_address),
+            )
+            .collect()
+    }
+
+    /// Return true iff at least K documents have gone through
+    /// the collector.
+    #[inline]
+    pub fn at_capacity(&self) -> bool {
+        self.heap.len() >= self.limit
+    }
+}
+
+impl Collector for GeoDistanceCollector {
+    fn set_segment(&mut self, segment_id: SegmentLocalId, reader: &SegmentReader) -> Result<()> {
+        self.segment_id = segment_id;
+        self.ff_reader = Some(reader.fast_field_reader(self.field)?);
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, _score: Score) {
+        let geo_point = self.ff_reader
+            .as_ref()
+            .expect(
+                "collect() was called before set_segment. \
+                 This should never happen.",
+            )
+            .get(doc);
+        let distance = self.origin.distance(&geo_point);
+        if self.at_capacity() {
+            // It's ok to unwrap as long as a limit of 0 is forbidden.
+            let limit_doc: GlobalDistancedDoc = *self.heap
+                .peek()
+                .expect("Geo distance collector with size 0 is forbidden");
+            if distance < limit_doc.distance {
+                let mut mut_head = self.heap
+                    .peek_mut()
+                    .expect("Geo distance collector with size 0 is forbidden");
+                mut_head.distance = distance;
+                mut_head.doc_address = DocAddress(self.segment_id, doc);
+            }
+        } else {
+            let wrapped_doc = GlobalDistancedDoc {
+                distance,
+                doc_address: DocAddress(self.segment_id, doc),
+            };
+            self.heap.push(wrapped_doc);
+        }
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use Index;
+    use schema::{GeoPointOptions, SchemaBuilder, STRING};
+    use query::{AllQuery, ExistsQuery, Query};
+
+    #[test]
+    fn test_geo_distance_collector() {
+        let mut schema_builder = SchemaBuilder::default();
+        let location_field = schema_builder
+            .add_geo_point_field("location", GeoPointOptions::default().set_fast());
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            // London, Paris, New York, Versailles
+            index_writer.add_document(doc!(location_field => GeoPoint::new(51.5074, -0.1278)));
+            index_writer.add_document(doc!(location_field => GeoPoint::new(48.8566, 2.3522)));
+            index_writer.add_document(doc!(location_field => GeoPoint::new(40.7128, -74.0060)));
+            index_writer.add_document(doc!(location_field => GeoPoint::new(48.8049, 2.1204)));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let eiffel_tower = GeoPoint::new(48.8584, 2.2945);
+        let mut collector = GeoDistanceCollector::with_limit(location_field, eiffel_tower, 3);
+        AllQuery.search(&*searcher, &mut collector).unwrap();
+        let docs: Vec<DocId> = collector
+            .docs()
+            .into_iter()
+            .map(|doc_address| doc_address.doc())
+            .collect();
+        assert_eq!(docs, vec![1, 3, 0]);
+        let distances: Vec<f64> = collector
+            .distance_docs()
+            .into_iter()
+            .map(|(distance, _)| distance)
+            .collect();
+        assert!(distances[0] < 5_000f64);
+        assert!(distances[2] > 300_000f64 && distances[2] < 400_000f64);
+    }
+
+    #[test]
+    fn test_geo_distance_collector_missing_location() {
+        let mut schema_builder = SchemaBuilder::default();
+        let location_field = schema_builder.add_geo_point_field(
+            "location",
+            GeoPointOptions::default().set_indexed().set_fast(),
+        );
+        let text_field = schema_builder.add_text_field("text", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(location_field => GeoPoint::new(51.5074, -0.1278)));
+            index_writer.add_document(doc!(text_field => "nowhere"));
+            index_writer.add_document(doc!(location_field => GeoPoint::new(48.8566, 2.3522)));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let eiffel_tower = GeoPoint::new(48.8584, 2.2945);
+        let search = |query: &Query| -> Vec<DocId> {
+            let mut collector = GeoDistanceCollector::with_limit(location_field, eiffel_tower, 3);
+            query.search(&*searcher, &mut collector).unwrap();
+            collector
+                .docs()
+                .into_iter()
+                .map(|doc_address| doc_address.doc())
+                .collect()
+        };
+        // The document without any location is ranked as if it was at (-90, -180).
+        assert_eq!(search(&AllQuery), vec![2, 0, 1]);
+        assert_eq!(search(&ExistsQuery::new(location_field)), vec![2, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_geo_distance_collector_0() {
+        GeoDistanceCollector::with_limit(Field(0), GeoPoint::default(), 0);
+    }
+}
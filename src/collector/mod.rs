@@ -20,6 +20,9 @@ pub use self::top_collector::TopCollector;
 mod facet_collector;
 pub use self::facet_collector::FacetCollector;
 
+mod geo_distance_collector;
+pub use self::geo_distance_collector::GeoDistanceCollector;
+
 mod chained_collector;
 pub use self::chained_collector::chain;
 
@@ -92,7 +95,8 @@ pub mod tests {
     use test::Bencher;
     use DocId;
     use Score;
-    use core::SegmentReader;
+    use core::{Searcher, SegmentReader};
+    use query::Query;
     use SegmentLocalId;
     use fastfield::FastFieldReader;
     use schema::Field;
@@ -117,6 +121,11 @@ pub mod tests {
         pub fn scores(self) -> Vec<Score> {
             self.scores
         }
+
+        /// Return the documents along with their scores.
+        pub fn docs_and_scores(self) -> (Vec<DocId>, Vec<Score>) {
+            (self.docs, self.scores)
+        }
     }
 
     impl Default for TestCollector {
@@ -147,6 +156,13 @@ pub mod tests {
         }
     }
 
+    /// Runs the query, and returns the documents it matched along with their scores.
+    pub fn search_docs_and_scores(searcher: &Searcher, query: &Query) -> (Vec<DocId>, Vec<Score>) {
+        let mut test_collector = TestCollector::default();
+        query.search(searcher, &mut test_collector).unwrap();
+        test_collector.docs_and_scores()
+    }
+
     /// Collects in order all of the fast fields for all of the
     /// doc in the `DocSet`
     ///
@@ -14,6 +14,7 @@ pub(crate) use self::bitset::TinySet;
 pub use byteorder::LittleEndian as Endianness;
 
 use std::io;
+use std::net::{IpAddr, Ipv6Addr};
 
 /// Computes the number of bits that will be used for bitpacking.
 ///
@@ -101,10 +102,73 @@ pub fn u64_to_i64(val: u64) -> i64 {
     (val ^ HIGHEST_BIT) as i64
 }
 
+/// Maps a `f64` to `u64`
+///
+/// For simplicity, tantivy internally handles `f64` as `u64`.
+/// The mapping is defined by this function.
+///
+/// Maps `f64` to `u64` so that the natural order of
+/// the floats is preserved:
+/// `-inf .. +inf` is mapped to an increasing range of `u64`.
+///
+/// Positive floats simply get their sign bit flipped, while
+/// negative floats get all of their bits flipped.
+/// `NaN` values end up at both extremities of the range,
+/// depending on their sign bit.
+///
+/// # See also
+/// The [reverse mapping is `u64_to_f64`](./fn.u64_to_f64.html).
+#[inline(always)]
+pub fn f64_to_u64(val: f64) -> u64 {
+    let bits = val.to_bits();
+    if bits & HIGHEST_BIT == 0 {
+        bits ^ HIGHEST_BIT
+    } else {
+        !bits
+    }
+}
+
+/// Reverse the mapping given by [`f64_to_u64`](./fn.f64_to_u64.html).
+#[inline(always)]
+pub fn u64_to_f64(val: u64) -> f64 {
+    f64::from_bits(if val & HIGHEST_BIT != 0 {
+        val ^ HIGHEST_BIT
+    } else {
+        !val
+    })
+}
+
+/// Normalizes an IP address to its 128-bit representation.
+///
+/// IPv4 addresses are mapped to IPv6 (`::ffff:a.b.c.d`), so that
+/// both families can live in the same field and sort consistently.
+///
+/// # See also
+/// The [reverse mapping is `ipv6_to_ip`](./fn.ipv6_to_ip.html).
+pub fn ip_to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ipv4) => ipv4.to_ipv6_mapped(),
+        IpAddr::V6(ipv6) => ipv6,
+    }
+}
+
+/// Reverse the mapping given by [`ip_to_ipv6`](./fn.ip_to_ipv6.html).
+///
+/// IPv4-mapped addresses are returned as IPv4 addresses.
+pub fn ipv6_to_ip(ipv6: Ipv6Addr) -> IpAddr {
+    match ipv6.to_ipv4() {
+        Some(ipv4) if ipv6.segments()[5] == 0xffff => IpAddr::V4(ipv4),
+        _ => IpAddr::V6(ipv6),
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test {
 
-    use super::{compute_num_bits, i64_to_u64, u64_to_i64};
+    use super::{compute_num_bits, f64_to_u64, i64_to_u64, u64_to_f64, u64_to_i64};
+    use super::{ip_to_ipv6, ipv6_to_ip};
+    use std::f64;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
     pub use super::serialize::test::fixed_size_test;
 
     fn test_i64_converter_helper(val: i64) {
@@ -123,6 +187,51 @@ pub(crate) mod test {
         }
     }
 
+    fn test_f64_converter_helper(val: f64) {
+        assert_eq!(u64_to_f64(f64_to_u64(val)), val);
+    }
+
+    #[test]
+    fn test_f64_converter() {
+        test_f64_converter_helper(0f64);
+        test_f64_converter_helper(-0.5f64);
+        test_f64_converter_helper(f64::MIN);
+        test_f64_converter_helper(f64::MAX);
+        test_f64_converter_helper(f64::INFINITY);
+        test_f64_converter_helper(f64::NEG_INFINITY);
+        let vals = [
+            f64::NEG_INFINITY,
+            f64::MIN,
+            -1_000.5f64,
+            -1f64,
+            -f64::MIN_POSITIVE,
+            0f64,
+            f64::MIN_POSITIVE,
+            0.25f64,
+            1f64,
+            1_000.5f64,
+            f64::MAX,
+            f64::INFINITY,
+        ];
+        for window in vals.windows(2) {
+            assert!(f64_to_u64(window[0]) < f64_to_u64(window[1]));
+        }
+    }
+
+    #[test]
+    fn test_ip_converter() {
+        let ipv4 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(
+            ip_to_ipv6(ipv4),
+            Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x0a00, 0x0001)
+        );
+        assert_eq!(ipv6_to_ip(ip_to_ipv6(ipv4)), ipv4);
+        let ipv6 = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        assert_eq!(ipv6_to_ip(ip_to_ipv6(ipv6)), ipv6);
+        let localhost = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+        assert_eq!(ipv6_to_ip(ip_to_ipv6(localhost)), localhost);
+    }
+
     #[test]
     fn test_compute_num_bits() {
         assert_eq!(compute_num_bits(1), 1u8);
@@ -2,7 +2,7 @@ use Result;
 use error::{ErrorKind, ResultExt};
 use serde_json;
 use schema::Schema;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::borrow::BorrowMut;
 use std::fmt;
 use core::SegmentId;
@@ -41,7 +41,7 @@ fn load_metas(directory: &Directory) -> Result<IndexMeta> {
 /// Search Index
 pub struct Index {
     directory: ManagedDirectory,
-    schema: Schema,
+    schema: Arc<RwLock<Schema>>,
     searcher_pool: Arc<Pool<Searcher>>,
     tokenizers: TokenizerManager,
 }
@@ -97,7 +97,7 @@ impl Index {
         let schema = metas.schema.clone();
         let index = Index {
             directory,
-            schema,
+            schema: Arc::new(RwLock::new(schema)),
             searcher_pool: Arc::new(Pool::new()),
             tokenizers: TokenizerManager::default(),
         };
@@ -164,8 +164,34 @@ impl Index {
     /// Accessor to the index schema
     ///
     /// The schema is actually cloned.
+    ///
+    /// It is the schema of the last commit, as read by the last call
+    /// to `load_searchers`. (See `IndexWriter::update_schema`.)
     pub fn schema(&self) -> Schema {
-        self.schema.clone()
+        self.schema
+            .read()
+            .expect("Schema lock poisoned. This should never happen.")
+            .clone()
+    }
+
+    /// Replaces the schema of this index, and of all of its clones
+    /// sharing the same schema.
+    pub(crate) fn set_schema(&self, schema: Schema) {
+        *self.schema
+            .write()
+            .expect("Schema lock poisoned. This should never happen.") = schema;
+    }
+
+    /// Returns a clone of this index, with a schema that is not shared
+    /// with the other clones.
+    ///
+    /// This is used by the `IndexWriter` to index documents with
+    /// a schema that is not committed yet.
+    pub(crate) fn clone_with_schema(&self, schema: Schema) -> Index {
+        Index {
+            schema: Arc::new(RwLock::new(schema)),
+            ..self.clone()
+        }
     }
 
     /// Returns the list of segments that are searchable
@@ -217,11 +243,16 @@ impl Index {
     ///
     /// This needs to be called when a new segment has been
     /// published or after a merge.
+    ///
+    /// The schema of the index is also reloaded, as it may have been
+    /// extended by the last commit.
     pub fn load_searchers(&self) -> Result<()> {
-        let searchable_segments = self.searchable_segments()?;
-        let segment_readers: Vec<SegmentReader> = searchable_segments
-            .iter()
-            .map(SegmentReader::open)
+        let metas = self.load_metas()?;
+        self.set_schema(metas.schema);
+        let segment_readers: Vec<SegmentReader> = metas
+            .segments
+            .into_iter()
+            .map(|segment_meta| SegmentReader::open(&self.segment(segment_meta)))
             .collect::<Result<_>>()?;
         let searchers = (0..NUM_SEARCHERS)
             .map(|_| Searcher::from(segment_readers.clone()))
@@ -255,9 +286,103 @@ impl Clone for Index {
     fn clone(&self) -> Index {
         Index {
             directory: self.directory.clone(),
-            schema: self.schema.clone(),
+            schema: Arc::clone(&self.schema),
             searcher_pool: Arc::clone(&self.searcher_pool),
             tokenizers: self.tokenizers.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use core::Index;
+    use schema::{Cardinality, IntOptions, SchemaBuilder, Term, STRING, TEXT};
+    use futures::Future;
+
+    #[test]
+    fn test_update_schema() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+        index_writer.add_document(doc!(text_field => "a"));
+        let mut schema_builder = SchemaBuilder::from(index.schema());
+        let score_options = IntOptions::default()
+            .set_indexed()
+            .set_fast(Cardinality::SingleValue);
+        let score_field = schema_builder.add_i64_field("score", score_options);
+        let tag_field = schema_builder.add_text_field("tag", TEXT);
+        index_writer.update_schema(schema_builder.build()).unwrap();
+        // the new schema is only written by the next commit.
+        assert_eq!(index.load_metas().unwrap().schema.fields().len(), 1);
+        index_writer.add_document(doc!(
+            text_field => "a",
+            score_field => -3i64,
+            tag_field => "b"
+        ));
+        index_writer.commit().unwrap();
+        assert_eq!(index.load_metas().unwrap().schema.fields().len(), 3);
+        assert_eq!(index.schema().fields().len(), 1);
+        index.load_searchers().unwrap();
+        assert_eq!(index.schema().fields().len(), 3);
+        {
+            let searcher = index.searcher();
+            assert_eq!(searcher.segment_readers().len(), 2);
+            let mut scores = vec![];
+            let mut fieldnorms = vec![];
+            for segment_reader in searcher.segment_readers() {
+                let score_reader = segment_reader.fast_field_reader::<i64>(score_field).unwrap();
+                scores.push(score_reader.get(0));
+                fieldnorms.push(segment_reader.get_fieldnorms_reader(tag_field).fieldnorm(0));
+            }
+            scores.sort();
+            fieldnorms.sort();
+            assert_eq!(scores, vec![-3i64, 0i64]);
+            assert_eq!(fieldnorms, vec![0u32, 1u32]);
+            assert_eq!(searcher.doc_freq(&Term::from_field_text(tag_field, "b")), 1);
+        }
+        let segment_ids = index.searchable_segment_ids().unwrap();
+        index_writer
+            .merge(&segment_ids)
+            .wait()
+            .expect("Merging failed");
+        index_writer.wait_merging_threads().unwrap();
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let score_reader = searcher
+            .segment_reader(0)
+            .fast_field_reader::<i64>(score_field)
+            .unwrap();
+        let mut scores = vec![score_reader.get(0), score_reader.get(1)];
+        scores.sort();
+        assert_eq!(scores, vec![-3i64, 0i64]);
+        assert_eq!(searcher.doc_freq(&Term::from_field_text(tag_field, "b")), 1);
+        assert_eq!(searcher.doc_freq(&Term::from_field_text(text_field, "a")), 2);
+        assert_eq!(index.load_metas().unwrap().schema.fields().len(), 3);
+    }
+
+    #[test]
+    fn test_update_schema_errors() {
+        let mut schema_builder = SchemaBuilder::default();
+        schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+        // removing a field
+        assert!(index_writer.update_schema(SchemaBuilder::default().build()).is_err());
+        // modifying a field
+        let mut schema_builder = SchemaBuilder::default();
+        schema_builder.add_text_field("text", STRING);
+        assert!(index_writer.update_schema(schema_builder.build()).is_err());
+        // rolling back the update
+        let mut schema_builder = SchemaBuilder::from(index.schema());
+        schema_builder.add_text_field("title", TEXT);
+        index_writer.update_schema(schema_builder.build()).unwrap();
+        index_writer.rollback().unwrap();
+        index_writer.commit().unwrap();
+        index.load_searchers().unwrap();
+        assert_eq!(index.load_metas().unwrap().schema.fields().len(), 1);
+        assert_eq!(index.schema().fields().len(), 1);
+    }
+}
@@ -23,9 +23,12 @@ use fastfield::FacetReader;
 use fastfield::FastFieldReader;
 use schema::Schema;
 use termdict::TermDictionary;
-use fastfield::{FastValue, MultiValueIntFastFieldReader};
+use fastfield::{BytesFastFieldReader, FastValue, MultiValueIntFastFieldReader,
+                StrFastFieldReader};
 use schema::Cardinality;
 use fieldnorm::FieldNormReader;
+use directory::ReadOnlySource;
+use std::cmp::max;
 
 /// Entry point to access all of the datastructures of the `Segment`
 ///
@@ -43,6 +46,7 @@ use fieldnorm::FieldNormReader;
 #[derive(Clone)]
 pub struct SegmentReader {
     inv_idx_reader_cache: Arc<RwLock<HashMap<Field, Arc<InvertedIndexReader>>>>,
+    missing_fast_field_cache: Arc<RwLock<HashMap<Field, FastFieldReader<u64>>>>,
 
     segment_id: SegmentId,
     segment_meta: SegmentMeta,
@@ -89,6 +93,11 @@ impl SegmentReader {
         self.delete_bitset().is_some()
     }
 
+    /// Returns the schema of the index.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
     /// Accessor to a segment's fast field reader given a field.
     ///
     /// Returns the u64 fast value reader if the field
@@ -97,6 +106,10 @@ impl SegmentReader {
     /// Return a FastFieldNotAvailableError if the field is not
     /// declared as a fast field in the schema.
     ///
+    /// If the field was added to the schema after the segment was written,
+    /// the reader returns the default value of the field type
+    /// (`0`, `0.0` or `false`) for all documents.
+    ///
     /// # Panics
     /// May panic if the index is corrupted.
     pub fn fast_field_reader<Item: FastValue>(
@@ -106,15 +119,63 @@ impl SegmentReader {
         let field_entry = self.schema.get_field_entry(field);
         if Item::fast_field_cardinality(field_entry.field_type()) == Some(Cardinality::SingleValue)
             {
-                self.fast_fields_composite
+                Ok(self.fast_fields_composite
                     .open_read(field)
-                    .ok_or_else(|| FastFieldNotAvailableError::new(field_entry))
                     .map(FastFieldReader::open)
+                    .unwrap_or_else(|| self.missing_fast_field_reader(field)))
             } else {
             Err(FastFieldNotAvailableError::new(field_entry))
         }
     }
 
+    /// Returns the reader of a fast field that is absent from the segment,
+    /// because the field was added to the schema after the segment was written.
+    /// (See `IndexWriter::update_schema`.)
+    ///
+    /// All of the values are the default value of the field type.
+    /// The reader is built on the first call, and cached afterwards.
+    fn missing_fast_field_reader<Item: FastValue>(&self, field: Field) -> FastFieldReader<Item> {
+        if let Some(fast_field_reader) = self.missing_fast_field_cache
+            .read()
+            .expect("Lock poisoned. This should never happen")
+            .get(&field)
+        {
+            return fast_field_reader.clone().cast();
+        }
+        let field_entry = self.schema.get_field_entry(field);
+        let default_val = fastfield::value_if_missing(field_entry.field_type());
+        let num_vals = max(self.max_doc() as usize, 1);
+        let fast_field_reader: FastFieldReader<u64> =
+            FastFieldReader::from(vec![default_val; num_vals]);
+        self.missing_fast_field_cache
+            .write()
+            .expect("Fast field reader cache lock poisoned. This should never happen.")
+            .insert(field, fast_field_reader.clone());
+        fast_field_reader.cast()
+    }
+
+    /// Accessor to the raw fast field reader stored at the given index
+    /// of a field.
+    ///
+    /// Unlike `fast_field_reader`, this method does not check the type
+    /// of the field. `u64`, `i64` and `f64` values are all exposed
+    /// through their internal `u64` representation when `Item` is `u64`,
+    /// which is what the `IndexMerger` needs.
+    pub(crate) fn fast_field_reader_with_idx<Item: FastValue>(
+        &self,
+        field: Field,
+        idx: usize,
+    ) -> fastfield::Result<FastFieldReader<Item>> {
+        let field_entry = self.schema.get_field_entry(field);
+        if let Some(fast_field_source) = self.fast_fields_composite.open_read_with_idx(field, idx) {
+            Ok(FastFieldReader::open(fast_field_source))
+        } else if idx == 0 && field_entry.is_int_fast() {
+            Ok(self.missing_fast_field_reader(field))
+        } else {
+            Err(FastFieldNotAvailableError::new(field_entry))
+        }
+    }
+
     /// Accessor to the `MultiValueIntFastFieldReader` associated to a given `Field`.
     /// May panick if the field is not a multivalued fastfield of the type `Item`.
     pub fn multi_fast_field_reader<Item: FastValue>(
@@ -124,20 +185,76 @@ impl SegmentReader {
         let field_entry = self.schema.get_field_entry(field);
         if Item::fast_field_cardinality(field_entry.field_type()) == Some(Cardinality::MultiValues)
             {
-                let idx_reader = self.fast_fields_composite
-                    .open_read_with_idx(field, 0)
-                    .ok_or_else(|| FastFieldNotAvailableError::new(field_entry))
-                    .map(FastFieldReader::open)?;
-                let vals_reader = self.fast_fields_composite
-                    .open_read_with_idx(field, 1)
-                    .ok_or_else(|| FastFieldNotAvailableError::new(field_entry))
-                    .map(FastFieldReader::open)?;
-                Ok(MultiValueIntFastFieldReader::open(idx_reader, vals_reader))
+                Ok(self.multi_fast_field_reader_unchecked(field))
             } else {
             Err(FastFieldNotAvailableError::new(field_entry))
         }
     }
 
+    /// Accessor to the `MultiValueIntFastFieldReader` of a field,
+    /// without checking the type of the field.
+    ///
+    /// Like `fast_field_reader_with_idx`, this is used by the `IndexMerger`
+    /// to read the values through their internal `u64` representation.
+    pub(crate) fn multi_fast_field_reader_unchecked<Item: FastValue>(
+        &self,
+        field: Field,
+    ) -> MultiValueIntFastFieldReader<Item> {
+        // If the field is absent from the segment, all documents
+        // are reported as having no values.
+        let num_idx_vals = self.max_doc() as usize + 1;
+        let idx_reader = self.fast_fields_composite
+            .open_read_with_idx(field, 0)
+            .map(FastFieldReader::open)
+            .unwrap_or_else(|| FastFieldReader::from(vec![0u64; num_idx_vals]));
+        let vals_reader = self.fast_fields_composite
+            .open_read_with_idx(field, 1)
+            .map(FastFieldReader::open)
+            .unwrap_or_else(|| FastFieldReader::from(vec![Item::default()]));
+        MultiValueIntFastFieldReader::open(idx_reader, vals_reader)
+    }
+
+    /// Accessor to the `BytesFastFieldReader` associated to a given `Field`.
+    ///
+    /// Return a FastFieldNotAvailableError if the field is not
+    /// declared as a bytes fast field in the schema.
+    pub fn bytes_fast_field_reader(&self, field: Field) -> fastfield::Result<BytesFastFieldReader> {
+        let field_entry = self.schema.get_field_entry(field);
+        match *field_entry.field_type() {
+            FieldType::Bytes(ref bytes_options) if bytes_options.is_fast() => {}
+            _ => return Err(FastFieldNotAvailableError::new(field_entry)),
+        }
+        // If the field is absent from the segment, all documents
+        // are reported as having an empty value.
+        let idx_reader = self.fast_fields_composite
+            .open_read_with_idx(field, 0)
+            .map(FastFieldReader::open)
+            .unwrap_or_else(|| FastFieldReader::from(vec![0u64; self.max_doc() as usize + 1]));
+        let values = self.fast_fields_composite
+            .open_read_with_idx(field, 1)
+            .unwrap_or_else(ReadOnlySource::empty);
+        Ok(BytesFastFieldReader::open(idx_reader, values))
+    }
+
+    /// Accessor to the `StrFastFieldReader` associated to a given `Field`.
+    ///
+    /// Return a FastFieldNotAvailableError if the field is not
+    /// declared as an indexed fast text field in the schema.
+    pub fn str_fast_field_reader(&self, field: Field) -> fastfield::Result<StrFastFieldReader> {
+        let field_entry = self.schema.get_field_entry(field);
+        match *field_entry.field_type() {
+            FieldType::Str(_) if fastfield::has_term_ord_fast_field(field_entry.field_type()) => {}
+            _ => return Err(FastFieldNotAvailableError::new(field_entry)),
+        }
+        let term_ords_reader = self.multi_fast_field_reader_unchecked(field);
+        // No document of the segment has any term for this field.
+        let termdict = self.termdict_composite
+            .open_read(field)
+            .map(TermDictionaryImpl::from_source)
+            .unwrap_or_else(|| TermDictionaryImpl::empty(field_entry.field_type().clone()));
+        Ok(StrFastFieldReader::new(term_ords_reader, termdict))
+    }
+
     /// Accessor to the `FacetReader` associated to a given `Field`.
     pub fn facet_reader(&self, field: Field) -> Result<FacetReader> {
         let field_entry = self.schema.get_field_entry(field);
@@ -149,15 +266,11 @@ impl SegmentReader {
             )).into());
         }
         let term_ords_reader = self.multi_fast_field_reader(field)?;
-        let termdict_source = self.termdict_composite.open_read(field).ok_or_else(|| {
-            ErrorKind::InvalidArgument(format!(
-                "The field \"{}\" is a hierarchical \
-                 but this segment does not seem to have the field term \
-                 dictionary.",
-                field_entry.name()
-            ))
-        })?;
-        let termdict = TermDictionaryImpl::from_source(termdict_source);
+        // No document of the segment has any facet for this field.
+        let termdict = self.termdict_composite
+            .open_read(field)
+            .map(TermDictionaryImpl::from_source)
+            .unwrap_or_else(|| TermDictionaryImpl::empty(field_entry.field_type().clone()));
         let facet_reader = FacetReader::new(term_ords_reader, termdict);
         Ok(facet_reader)
     }
@@ -170,14 +283,20 @@ impl SegmentReader {
     ///
     /// They are simply stored as a fast field, serialized in
     /// the `.fieldnorm` file of the segment.
+    ///
+    /// If the field was added to the schema after the segment was written,
+    /// all of the documents have a fieldnorm of 0.
     pub fn get_fieldnorms_reader(&self, field: Field) -> FieldNormReader {
         if let Some(fieldnorm_source) = self.fieldnorms_composite
             .open_read(field) {
             FieldNormReader::open(fieldnorm_source)
         } else {
-            let field_name = self.schema.get_field_name(field);
-            let err_msg=  format!("Field norm not found for field {:?}. Was it market as indexed during indexing.", field_name);
-            panic!(err_msg);
+            let field_entry = self.schema.get_field_entry(field);
+            if !field_entry.is_indexed() {
+                let err_msg=  format!("Field norm not found for field {:?}. Was it market as indexed during indexing.", field_entry.name());
+                panic!(err_msg);
+            }
+            FieldNormReader::open(ReadOnlySource::from(vec![0u8; self.max_doc() as usize]))
         }
     }
 
@@ -222,6 +341,7 @@ impl SegmentReader {
         let schema = segment.schema();
         Ok(SegmentReader {
             inv_idx_reader_cache: Arc::new(RwLock::new(HashMap::new())),
+            missing_fast_field_cache: Arc::new(RwLock::new(HashMap::new())),
             segment_meta: segment.meta().clone(),
             termdict_composite,
             postings_composite,
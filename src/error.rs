@@ -48,6 +48,11 @@ error_chain!(
             description("an error occurred in a thread")
             display("an error occurred in a thread: '{}'", err)
         }
+        /// A multi-term query (e.g. a prefix or a wildcard) matched more terms than allowed.
+        TooManyTerms(max_expansions: usize) {
+            description("a query expanded into too many terms")
+            display("a query expanded into more than {} terms", max_expansions)
+        }
         /// An Error appeared related to the lack of a field.
         SchemaError(field: String) {
             description("a schema field is missing")
@@ -0,0 +1,72 @@
+mod writer;
+mod reader;
+
+pub use self::writer::BytesFastFieldWriter;
+pub use self::reader::BytesFastFieldReader;
+
+#[cfg(test)]
+mod tests {
+
+    use schema::{BytesOptions, SchemaBuilder, Term};
+    use query::TermQuery;
+    use schema::IndexRecordOption;
+    use collector::tests::TestCollector;
+    use DocAddress;
+    use Index;
+
+    #[test]
+    fn test_bytes() {
+        let mut schema_builder = SchemaBuilder::default();
+        let field = schema_builder.add_bytes_field("bytesfield", BytesOptions::default().set_fast());
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_with_num_threads(1, 3_000_000).unwrap();
+        index_writer.add_document(doc!(field=>vec![0u8, 1, 2, 3]));
+        index_writer.add_document(doc!(field=>vec![]));
+        index_writer.add_document(doc!(field=>vec![255u8]));
+        index_writer.add_document(doc!());
+        index_writer.add_document(doc!(field=>vec![1u8, 3, 5, 7, 9]));
+        index_writer.add_document(doc!(field=>vec![0u8; 1000]));
+        assert!(index_writer.commit().is_ok());
+
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let reader = searcher.segment_reader(0);
+        let bytes_reader = reader.bytes_fast_field_reader(field).unwrap();
+
+        assert_eq!(bytes_reader.get_val(0), &[0u8, 1, 2, 3]);
+        assert!(bytes_reader.get_val(1).is_empty());
+        assert_eq!(bytes_reader.get_val(2), &[255u8]);
+        assert!(bytes_reader.get_val(3).is_empty());
+        assert_eq!(bytes_reader.get_val(4), &[1u8, 3, 5, 7, 9]);
+        let long = vec![0u8; 1000];
+        assert_eq!(bytes_reader.get_val(5), long.as_slice());
+    }
+
+    #[test]
+    fn test_bytes_stored_and_indexed() {
+        let mut schema_builder = SchemaBuilder::default();
+        let bytes_options = BytesOptions::default().set_stored().set_indexed();
+        let field = schema_builder.add_bytes_field("bytesfield", bytes_options);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 3_000_000).unwrap();
+            // not valid utf-8
+            index_writer.add_document(doc!(field=>vec![0u8, 159, 146, 150]));
+            index_writer.add_document(doc!(field=>vec![1u8, 2]));
+            assert!(index_writer.commit().is_ok());
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let doc = searcher.doc(&DocAddress(0, 0)).unwrap();
+        assert_eq!(doc.get_first(field).unwrap().bytes_value(), &[0u8, 159, 146, 150]);
+        let term_query = TermQuery::new(
+            Term::from_field_bytes(field, &[1u8, 2]),
+            IndexRecordOption::Basic,
+        );
+        let mut collector = TestCollector::default();
+        searcher.search(&term_query, &mut collector).unwrap();
+        assert_eq!(collector.docs(), vec![1]);
+    }
+}
@@ -0,0 +1,79 @@
+use std::io;
+
+use fastfield::serializer::FastFieldSerializer;
+use schema::{Document, Field, Value};
+
+/// Writer for byte array (as in, any number of bytes per document) fast fields
+///
+/// This `BytesFastFieldWriter` is only useful for advanced user.
+/// The normal way to get your associated bytes in your index
+/// is to
+/// - declare your field with fast set to `true` in the schema
+/// - add your document simply by calling `.add_document(...)`.
+///
+/// The `BytesFastFieldWriter` can be acquired from the
+/// fast field writer itself.
+///
+/// Only the first bytes value of a document is kept in the fast field.
+pub struct BytesFastFieldWriter {
+    field: Field,
+    vals: Vec<u8>,
+    doc_index: Vec<u64>,
+}
+
+impl BytesFastFieldWriter {
+    /// Creates a new `BytesFastFieldWriter`
+    pub fn new(field: Field) -> Self {
+        BytesFastFieldWriter {
+            field,
+            vals: Vec::new(),
+            doc_index: Vec::new(),
+        }
+    }
+
+    /// Access the field associated to the `BytesFastFieldWriter`
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Finalize the current document.
+    pub(crate) fn next_doc(&mut self) {
+        self.doc_index.push(self.vals.len() as u64);
+    }
+
+    /// Shift to the next document and add
+    /// the first bytes value associated to the field, if any.
+    pub fn add_document(&mut self, doc: &Document) {
+        self.next_doc();
+        if let Some(value) = doc.get_first(self.field) {
+            match *value {
+                Value::Bytes(ref bytes) => self.vals.extend_from_slice(bytes),
+                _ => panic!("Bytes field contained non-Bytes Value!"),
+            }
+        }
+    }
+
+    /// Serializes the fast field values by pushing them to the `FastFieldSerializer`.
+    ///
+    /// The offset of each document is written as a `u64` fast field (`idx = 0`),
+    /// followed by the concatenation of all of the values (`idx = 1`).
+    pub fn serialize(&self, serializer: &mut FastFieldSerializer) -> io::Result<()> {
+        {
+            // writing the offset index
+            let mut doc_index_serializer =
+                serializer.new_u64_fast_field_with_idx(self.field, 0, self.vals.len() as u64, 0)?;
+            for &offset in &self.doc_index {
+                doc_index_serializer.add_val(offset)?;
+            }
+            doc_index_serializer.add_val(self.vals.len() as u64)?;
+            doc_index_serializer.close_field()?;
+        }
+        {
+            // writing the values themselves
+            let mut value_serializer = serializer.new_bytes_fast_field_with_idx(self.field, 1);
+            value_serializer.write_all(&self.vals)?;
+            value_serializer.flush()?;
+        }
+        Ok(())
+    }
+}
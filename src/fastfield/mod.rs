@@ -13,8 +13,18 @@ the `DocSet` : for instance for scoring, grouping, filtering, or faceting.
 
 
 Fields have to be declared as `FAST` in the  schema.
-Currently only 64-bits integers (signed or unsigned) are
-supported.
+Currently only 64-bits integers (signed or unsigned),
+64-bits floats, booleans and dates are supported.
+Date fast fields are accessed as `i64` timestamps (in seconds).
+Geo point fast fields store the Morton code of the points, and
+are accessed as `GeoPoint`.
+
+Bytes fields can also be declared as fast. They are stored
+as a variable-length column, and accessed through a `BytesFastFieldReader`.
+
+Indexed text fields declared as fast store, for each document, the
+ordinals of their terms in the term dictionary of the segment, just like
+facets do. They are accessed through a `StrFastFieldReader`.
 
 They are stored in a bit-packed fashion so that their
 memory usage is directly linear with the amplitude of the
@@ -27,10 +37,13 @@ use common;
 use schema::Cardinality;
 use schema::FieldType;
 use schema::Value;
+use schema::GeoPoint;
+pub use self::bytes::{BytesFastFieldReader, BytesFastFieldWriter};
 pub use self::delete::DeleteBitSet;
 pub use self::delete::write_delete_bitset;
 pub use self::error::{FastFieldNotAvailableError, Result};
 pub use self::facet_reader::FacetReader;
+pub use self::str_reader::StrFastFieldReader;
 pub use self::multivalued::MultiValueIntFastFieldReader;
 pub use self::reader::FastFieldReader;
 pub use self::serializer::FastFieldSerializer;
@@ -42,9 +55,11 @@ mod serializer;
 mod error;
 mod delete;
 mod facet_reader;
+mod str_reader;
 mod multivalued;
+mod bytes;
 
-/// Trait for types that are allowed for fast fields: (u64 or i64).
+/// Trait for types that are allowed for fast fields: (u64, i64, f64, bool or `GeoPoint`).
 pub trait FastValue: Default + Clone + Copy {
     /// Converts a value from u64
     ///
@@ -100,7 +115,9 @@ impl FastValue for i64 {
 
     fn fast_field_cardinality(field_type: &FieldType) -> Option<Cardinality> {
         match *field_type {
-            FieldType::I64(ref integer_options) => integer_options.get_fastfield_cardinality(),
+            FieldType::I64(ref integer_options) | FieldType::Date(ref integer_options) => {
+                integer_options.get_fastfield_cardinality()
+            }
             _ => None,
         }
     }
@@ -110,11 +127,117 @@ impl FastValue for i64 {
     }
 }
 
+impl FastValue for f64 {
+    fn from_u64(val: u64) -> Self {
+        common::u64_to_f64(val)
+    }
+
+    fn to_u64(&self) -> u64 {
+        common::f64_to_u64(*self)
+    }
+
+    fn fast_field_cardinality(field_type: &FieldType) -> Option<Cardinality> {
+        match *field_type {
+            FieldType::F64(ref integer_options) => integer_options.get_fastfield_cardinality(),
+            _ => None,
+        }
+    }
+
+    fn as_u64(&self) -> u64 {
+        self.to_bits()
+    }
+}
+
+impl FastValue for bool {
+    fn from_u64(val: u64) -> Self {
+        val != 0
+    }
+
+    fn to_u64(&self) -> u64 {
+        *self as u64
+    }
+
+    fn fast_field_cardinality(field_type: &FieldType) -> Option<Cardinality> {
+        match *field_type {
+            FieldType::Bool(ref integer_options) => integer_options.get_fastfield_cardinality(),
+            _ => None,
+        }
+    }
+
+    fn as_u64(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl FastValue for GeoPoint {
+    fn from_u64(val: u64) -> Self {
+        GeoPoint::from_morton(val)
+    }
+
+    fn to_u64(&self) -> u64 {
+        self.to_morton()
+    }
+
+    fn fast_field_cardinality(field_type: &FieldType) -> Option<Cardinality> {
+        match *field_type {
+            FieldType::GeoPoint(ref geo_point_options) if geo_point_options.is_fast() => {
+                Some(Cardinality::SingleValue)
+            }
+            _ => None,
+        }
+    }
+
+    fn as_u64(&self) -> u64 {
+        self.to_morton()
+    }
+}
+
+/// Returns the `u64` value recorded in the fast field of a field type,
+/// for the documents that do not have any value.
+pub(crate) fn value_if_missing(field_type: &FieldType) -> u64 {
+    match *field_type {
+        FieldType::I64(_) | FieldType::Date(_) => common::i64_to_u64(0i64),
+        FieldType::F64(_) => common::f64_to_u64(0f64),
+        _ => 0u64,
+    }
+}
+
+/// Returns the cardinality of the `u64` column of an int fast field
+/// (u64, i64, f64, bool, date or geo point), or `None` if the field
+/// type is not an int fast field.
+pub(crate) fn int_fast_field_cardinality(field_type: &FieldType) -> Option<Cardinality> {
+    match *field_type {
+        FieldType::U64(ref options)
+        | FieldType::I64(ref options)
+        | FieldType::F64(ref options)
+        | FieldType::Bool(ref options)
+        | FieldType::Date(ref options) => options.get_fastfield_cardinality(),
+        FieldType::GeoPoint(ref options) if options.is_fast() => Some(Cardinality::SingleValue),
+        _ => None,
+    }
+}
+
+/// Returns true iff the fast field of a field type is a column
+/// of term ordinals, as for facets and string fast fields.
+pub(crate) fn has_term_ord_fast_field(field_type: &FieldType) -> bool {
+    match *field_type {
+        FieldType::HierarchicalFacet => true,
+        FieldType::Str(ref text_options) => {
+            text_options.is_fast() && text_options.get_indexing_options().is_some()
+        }
+        _ => false,
+    }
+}
+
 fn value_to_u64(value: &Value) -> u64 {
     match *value {
         Value::U64(ref val) => *val,
         Value::I64(ref val) => common::i64_to_u64(*val),
-        _ => panic!("Expected a u64/i64 field, got {:?} ", value),
+        Value::F64(ref val) => common::f64_to_u64(*val),
+        Value::Bool(val) => val as u64,
+        Value::Date(ref date) => common::i64_to_u64(date.timestamp()),
+        Value::GeoPoint(ref geo_point) => geo_point.to_morton(),
+        _ => panic!("Expected a u64/i64/f64/bool/date/geo point field, got {:?} ", value),
     }
 }
 
@@ -136,6 +259,8 @@ mod tests {
     use super::*;
     use test;
     use test::Bencher;
+    use Index;
+    use chrono::{TimeZone, Utc};
 
     lazy_static! {
         static ref SCHEMA: Schema = {
@@ -340,6 +465,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_date_fastfield() {
+        let mut schema_builder = SchemaBuilder::new();
+        let date_field = schema_builder.add_date_field("date", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 3_000_000).unwrap();
+            index_writer.add_document(doc!(date_field => Utc.timestamp(1_500_000_000i64, 0)));
+            index_writer.add_document(doc!());
+            index_writer.add_document(doc!(date_field => Utc.timestamp(-10i64, 0)));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let fast_field_reader = searcher
+            .segment_reader(0)
+            .fast_field_reader::<i64>(date_field)
+            .unwrap();
+        assert_eq!(fast_field_reader.get(0), 1_500_000_000i64);
+        assert_eq!(fast_field_reader.get(1), 0i64);
+        assert_eq!(fast_field_reader.get(2), -10i64);
+    }
+
+    #[test]
+    fn test_bool_fastfield() {
+        let path = Path::new("test");
+        let mut directory: RAMDirectory = RAMDirectory::create();
+        let mut schema_builder = SchemaBuilder::new();
+        let bool_field = schema_builder.add_bool_field("flag", FAST);
+        let schema = schema_builder.build();
+        {
+            let write: WritePtr = directory.open_write(Path::new("test")).unwrap();
+            let mut serializer = FastFieldSerializer::from_write(write).unwrap();
+            let mut fast_field_writers = FastFieldsWriter::from_schema(&schema);
+            fast_field_writers.add_document(&doc!(bool_field=>true));
+            fast_field_writers.add_document(&doc!());
+            fast_field_writers.add_document(&doc!(bool_field=>false));
+            fast_field_writers.add_document(&doc!(bool_field=>true));
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
+            serializer.close().unwrap();
+        }
+        let source = directory.open_read(&path).unwrap();
+        {
+            let fast_fields_composite = CompositeFile::open(&source).unwrap();
+            let data = fast_fields_composite.open_read(bool_field).unwrap();
+            // min, amplitude, one byte of bitpacked values and the padding.
+            assert_eq!(data.len(), 8 + 8 + 1 + 7);
+            let fast_field_reader = FastFieldReader::<bool>::open(data);
+            assert_eq!(fast_field_reader.min_value(), false);
+            assert_eq!(fast_field_reader.max_value(), true);
+            assert_eq!(fast_field_reader.get(0), true);
+            assert_eq!(fast_field_reader.get(1), false);
+            assert_eq!(fast_field_reader.get(2), false);
+            assert_eq!(fast_field_reader.get(3), true);
+            let mut buffer = vec![false; 4];
+            fast_field_reader.get_range(0, &mut buffer[..]);
+            assert_eq!(buffer, vec![true, false, false, true]);
+        }
+    }
+
     #[test]
     fn test_signed_intfastfield_default_val() {
         let path = Path::new("test");
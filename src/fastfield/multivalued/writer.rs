@@ -11,17 +11,21 @@ pub struct MultiValueIntFastFieldWriter {
     field: Field,
     vals: Vec<u64>,
     doc_index: Vec<u64>,
-    is_facet: bool,
+    is_term_ord: bool,
 }
 
 impl MultiValueIntFastFieldWriter {
     /// Creates a new `IntFastFieldWriter`
-    pub fn new(field: Field, is_facet: bool) -> Self {
+    ///
+    /// If `is_term_ord` is true, the values are not extracted from the
+    /// documents. Instead, the `SegmentWriter` records the `UnorderedTermId`
+    /// of the terms of the field, as for facets and string fast fields.
+    pub fn new(field: Field, is_term_ord: bool) -> Self {
         MultiValueIntFastFieldWriter {
             field,
             vals: Vec::new(),
             doc_index: Vec::new(),
-            is_facet,
+            is_term_ord,
         }
     }
 
@@ -43,7 +47,7 @@ impl MultiValueIntFastFieldWriter {
     }
 
     pub fn add_document(&mut self, doc: &Document) {
-        if !self.is_facet {
+        if !self.is_term_ord {
             for field_value in doc.field_values() {
                 if field_value.field() == self.field {
                     self.add_val(value_to_u64(field_value.value()));
@@ -68,6 +72,9 @@ impl MultiValueIntFastFieldWriter {
         serializer: &mut FastFieldSerializer,
         mapping_opt: Option<&HashMap<UnorderedTermId, usize>>,
     ) -> io::Result<()> {
+        // Only the values of term ordinal fields need to be remapped. Other
+        // indexed fields also come with a mapping, which must be ignored.
+        let mapping_opt = if self.is_term_ord { mapping_opt } else { None };
         {
             // writing the offset index
             let mut doc_index_serializer =
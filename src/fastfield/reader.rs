@@ -72,6 +72,13 @@ impl<Item: FastValue> FastFieldReader<Item> {
     /// May panic if `start + output.len()` is greater than
     /// the segment's `maxdoc`.
     pub fn get_range(&self, start: u32, output: &mut [Item]) {
+        if mem::size_of::<Item>() != mem::size_of::<u64>() {
+            // The output buffer cannot be reused to unpack the values (e.g. `bool`).
+            for (i, out) in output.iter_mut().enumerate() {
+                *out = self.get(start + i as u32);
+            }
+            return;
+        }
         let output_u64: &mut [u64] = unsafe { mem::transmute(output) };
         self.bit_unpacker.get_range(start, output_u64);
         for out in output_u64.iter_mut() {
@@ -96,6 +103,17 @@ impl<Item: FastValue> FastFieldReader<Item> {
     pub fn max_value(&self) -> Item {
         Item::from_u64(self.max_value_u64)
     }
+
+    /// Returns a reader over the same `u64` values, decoded as
+    /// another `FastValue` type.
+    pub(crate) fn cast<TOther: FastValue>(self) -> FastFieldReader<TOther> {
+        FastFieldReader {
+            bit_unpacker: self.bit_unpacker,
+            min_value_u64: self.min_value_u64,
+            max_value_u64: self.max_value_u64,
+            _phantom: PhantomData,
+        }
+    }
 }
 
 impl<Item: FastValue> From<Vec<Item>> for FastFieldReader<Item> {
@@ -0,0 +1,119 @@
+use super::MultiValueIntFastFieldReader;
+use DocId;
+use termdict::TermOrdinal;
+use termdict::{TermDictionary, TermDictionaryImpl};
+
+/// The string fast field reader makes it possible to access the
+/// terms associated to a given document in a specific segment.
+///
+/// Like for the `FacetReader`, the terms are exposed in the form of
+/// term ordinals, which can then be translated into a string
+/// via `.str_from_ord(...)`.
+///
+/// Term ordinals are defined as the position of the terms in the sorted
+/// term dictionary of the segment. Comparing the ordinals of two
+/// documents is therefore equivalent to comparing their terms, which makes
+/// them convenient for sorting and grouping.
+/// These ordinals are segment local and only make sense for a given segment.
+pub struct StrFastFieldReader {
+    term_ords: MultiValueIntFastFieldReader<u64>,
+    term_dict: TermDictionaryImpl,
+}
+
+impl StrFastFieldReader {
+    /// Creates a new `StrFastFieldReader`.
+    ///
+    /// A string fast field reader just wraps :
+    /// - a `MultiValueIntFastFieldReader` that makes it possible to
+    /// access the list of term ords for a given document.
+    /// - a `TermDictionaryImpl` that helps associating a term to
+    /// an ordinal and vice versa.
+    pub fn new(
+        term_ords: MultiValueIntFastFieldReader<u64>,
+        term_dict: TermDictionaryImpl,
+    ) -> StrFastFieldReader {
+        StrFastFieldReader {
+            term_ords,
+            term_dict,
+        }
+    }
+
+    /// Returns the number of distinct terms in the segment.
+    /// This does not take in account the documents that may be marked
+    /// as deleted.
+    ///
+    /// Term ordinals range from `0` to `num_terms() - 1`.
+    pub fn num_terms(&self) -> usize {
+        self.term_dict.num_terms()
+    }
+
+    /// Accessor for the term dictionary of the field.
+    pub fn term_dict(&self) -> &TermDictionaryImpl {
+        &self.term_dict
+    }
+
+    /// Given a term ordinal, writes the term associated to it in `output`.
+    ///
+    /// # Panics
+    /// Panics if the ordinal is not a valid term ordinal.
+    pub fn str_from_ord(&self, term_ord: TermOrdinal, output: &mut String) {
+        // The terms of a text field are the utf-8 bytes of its tokens.
+        let bytes = unsafe { output.as_mut_vec() };
+        if !self.term_dict.ord_to_term(term_ord, bytes) {
+            bytes.clear();
+            panic!("Term ordinal {} no found.", term_ord);
+        }
+    }
+
+    /// Return the list of term ordinals associated to a document,
+    /// in the order in which the tokens were indexed.
+    pub fn term_ords(&self, doc: DocId, output: &mut Vec<u64>) {
+        self.term_ords.get_vals(doc, output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use core::Index;
+    use schema::{SchemaBuilder, STRING, TEXT};
+
+    #[test]
+    fn test_str_fast_field_reader() {
+        let mut schema_builder = SchemaBuilder::default();
+        let brand_field = schema_builder.add_text_field("brand", STRING.set_fast());
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(brand_field => "nike", text_field => "shoes"));
+            index_writer.add_document(doc!(brand_field => "puma", brand_field => "adidas"));
+            index_writer.add_document(doc!(text_field => "no brand"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        assert!(segment_reader.str_fast_field_reader(text_field).is_err());
+        let str_reader = segment_reader.str_fast_field_reader(brand_field).unwrap();
+        assert_eq!(str_reader.num_terms(), 3);
+        let mut term_ords = Vec::new();
+        {
+            str_reader.term_ords(0, &mut term_ords);
+            assert_eq!(&term_ords[..], &[1]);
+        }
+        {
+            str_reader.term_ords(1, &mut term_ords);
+            assert_eq!(&term_ords[..], &[2, 0]);
+        }
+        {
+            str_reader.term_ords(2, &mut term_ords);
+            assert!(term_ords.is_empty());
+        }
+        let mut text = String::new();
+        str_reader.str_from_ord(0, &mut text);
+        assert_eq!(text, "adidas");
+        str_reader.str_from_ord(2, &mut text);
+        assert_eq!(text, "puma");
+    }
+}
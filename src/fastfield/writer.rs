@@ -2,17 +2,18 @@ use schema::{Cardinality, Document, Field, Schema};
 use fastfield::FastFieldSerializer;
 use std::io;
 use schema::FieldType;
-use common;
 use common::VInt;
 use std::collections::HashMap;
 use postings::UnorderedTermId;
 use super::multivalued::MultiValueIntFastFieldWriter;
+use super::bytes::BytesFastFieldWriter;
 use common::BinarySerializable;
 
 /// The fastfieldswriter regroup all of the fast field writers.
 pub struct FastFieldsWriter {
     single_value_writers: Vec<IntFastFieldWriter>,
     multi_values_writers: Vec<MultiValueIntFastFieldWriter>,
+    bytes_value_writers: Vec<BytesFastFieldWriter>,
 }
 
 impl FastFieldsWriter {
@@ -20,16 +21,17 @@ impl FastFieldsWriter {
     pub fn from_schema(schema: &Schema) -> FastFieldsWriter {
         let mut single_value_writers = Vec::new();
         let mut multi_values_writers = Vec::new();
+        let mut bytes_value_writers = Vec::new();
 
         for (field_id, field_entry) in schema.fields().iter().enumerate() {
             let field = Field(field_id as u32);
-            let default_value = if let FieldType::I64(_) = *field_entry.field_type() {
-                common::i64_to_u64(0i64)
-            } else {
-                0u64
-            };
+            let default_value = super::value_if_missing(field_entry.field_type());
             match *field_entry.field_type() {
-                FieldType::I64(ref int_options) | FieldType::U64(ref int_options) => {
+                FieldType::I64(ref int_options)
+                | FieldType::U64(ref int_options)
+                | FieldType::F64(ref int_options)
+                | FieldType::Bool(ref int_options)
+                | FieldType::Date(ref int_options) => {
                     match int_options.get_fastfield_cardinality() {
                         Some(Cardinality::SingleValue) => {
                             let mut fast_field_writer = IntFastFieldWriter::new(field);
@@ -43,9 +45,21 @@ impl FastFieldsWriter {
                         None => {}
                     }
                 }
-                FieldType::HierarchicalFacet => {
-                    let fast_field_writer = MultiValueIntFastFieldWriter::new(field, true);
-                    multi_values_writers.push(fast_field_writer);
+                FieldType::HierarchicalFacet | FieldType::Str(_) => {
+                    if super::has_term_ord_fast_field(field_entry.field_type()) {
+                        let fast_field_writer = MultiValueIntFastFieldWriter::new(field, true);
+                        multi_values_writers.push(fast_field_writer);
+                    }
+                }
+                FieldType::GeoPoint(ref geo_point_options) => {
+                    if geo_point_options.is_fast() {
+                        single_value_writers.push(IntFastFieldWriter::new(field));
+                    }
+                }
+                FieldType::Bytes(ref bytes_options) => {
+                    if bytes_options.is_fast() {
+                        bytes_value_writers.push(BytesFastFieldWriter::new(field));
+                    }
                 }
                 _ => {}
             }
@@ -53,6 +67,7 @@ impl FastFieldsWriter {
         FastFieldsWriter {
             single_value_writers,
             multi_values_writers,
+            bytes_value_writers,
         }
     }
 
@@ -79,6 +94,17 @@ impl FastFieldsWriter {
             .find(|multivalue_writer| multivalue_writer.field() == field)
     }
 
+    /// Returns the bytes fast field writer for the given field.
+    ///
+    /// Returns None if the field does not exist, or is not
+    /// configured as a bytes fastfield in the schema.
+    pub fn get_bytes_writer(&mut self, field: Field) -> Option<&mut BytesFastFieldWriter> {
+        // TODO optimize
+        self.bytes_value_writers
+            .iter_mut()
+            .find(|field_writer| field_writer.field() == field)
+    }
+
     /// Indexes all of the fastfields of a new document.
     pub fn add_document(&mut self, doc: &Document) {
         for field_writer in &mut self.single_value_writers {
@@ -88,6 +114,9 @@ impl FastFieldsWriter {
             field_writer.next_doc();
             field_writer.add_document(doc);
         }
+        for field_writer in &mut self.bytes_value_writers {
+            field_writer.add_document(doc);
+        }
     }
 
     /// Serializes all of the `FastFieldWriter`s by pushing them in
@@ -104,6 +133,9 @@ impl FastFieldsWriter {
             let field = field_writer.field();
             field_writer.serialize(serializer, mapping.get(&field))?;
         }
+        for field_writer in &self.bytes_value_writers {
+            field_writer.serialize(serializer)?;
+        }
         Ok(())
     }
 }
@@ -120,9 +152,11 @@ impl FastFieldsWriter {
 /// bitpacked and the number of bits required for bitpacking
 /// can only been known once we have seen all of the values.
 ///
-/// Both u64, and i64 use the same writer.
-/// i64 are just remapped to the `0..2^64 - 1`
-/// using `common::i64_to_u64`.
+/// u64, i64, f64, bool, date and geo point fields use the same writer.
+/// Bools are encoded as 0 or 1, hence bitpacked over a single bit.
+/// Geo points are encoded as their Morton code.
+/// i64 and f64 are just remapped to the `0..2^64 - 1`
+/// using `common::i64_to_u64` and `common::f64_to_u64`.
 pub struct IntFastFieldWriter {
     field: Field,
     vals: Vec<u8>,
@@ -181,8 +215,8 @@ impl IntFastFieldWriter {
     /// Extract the value associated to the fast field for
     /// this document.
     ///
-    /// i64 are remapped to u64 using the logic
-    /// in `common::i64_to_u64`.
+    /// i64 and f64 are remapped to u64 using the logic
+    /// in `common::i64_to_u64` and `common::f64_to_u64`.
     ///
     /// If the value is missing, then the default value is used
     /// instead.
@@ -23,6 +23,7 @@ use indexer::SegmentWriter;
 use docset::DocSet;
 use schema::IndexRecordOption;
 use schema::Document;
+use schema::Schema;
 use schema::Term;
 use std::mem;
 use std::mem::swap;
@@ -116,10 +117,15 @@ pub fn open_index_writer(
 
     let delete_queue = DeleteQueue::new();
 
-    let current_opstamp = index.load_metas()?.opstamp;
+    let metas = index.load_metas()?;
+    let current_opstamp = metas.opstamp;
 
     let stamper = Stamper::new(current_opstamp);
 
+    // The writer gets its own schema, as `update_schema` only affects
+    // the other clones of the index after the next commit.
+    let index = index.clone_with_schema(metas.schema);
+
     let segment_updater =
         SegmentUpdater::new(index.clone(), stamper.clone(), &delete_queue.cursor())?;
 
@@ -127,7 +133,7 @@ pub fn open_index_writer(
         _directory_lock: Some(directory_lock),
 
         heap_size_in_bytes_per_thread,
-        index: index.clone(),
+        index,
 
         document_receiver,
         document_sender,
@@ -150,6 +156,27 @@ pub fn open_index_writer(
     Ok(index_writer)
 }
 
+/// Checks that `new_schema` only appends fields to `schema`.
+fn check_schema_extension(schema: &Schema, new_schema: &Schema) -> Result<()> {
+    if new_schema.fields().len() < schema.fields().len() {
+        bail!(ErrorKind::InvalidArgument(
+            "Fields cannot be removed from the schema of an index.".to_string()
+        ));
+    }
+    for (field_entry, new_field_entry) in schema.fields().iter().zip(new_schema.fields()) {
+        if field_entry.name() != new_field_entry.name()
+            || field_entry.field_type() != new_field_entry.field_type()
+        {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "The field {:?} cannot be modified. New fields must be \
+                 appended at the end of the schema.",
+                field_entry.name()
+            )));
+        }
+    }
+    Ok(())
+}
+
 pub fn compute_deleted_bitset(
     delete_bitset: &mut BitSet,
     segment_reader: &SegmentReader,
@@ -473,6 +500,45 @@ impl IndexWriter {
         document_receiver
     }
 
+    /// Updates the schema of the index, by appending new fields to it.
+    ///
+    /// The fields of the current schema must be kept as is, and in the same order.
+    /// (See `SchemaBuilder::from(index.schema())`.)
+    ///
+    /// The indexing queue is cut, so that the documents added
+    /// before the update are indexed with the former schema,
+    /// and the documents added after it with the new schema.
+    ///
+    /// The new schema is written in the `meta.json` file along with
+    /// the next commit, and is picked up by `Index::load_searchers`.
+    /// A rollback restores the schema of the last commit.
+    ///
+    /// Existing documents are not reindexed: the segments written
+    /// before the update report the new fields as empty,
+    /// and their fast fields return the default value of the field type.
+    ///
+    /// # Errors
+    /// If the new schema does not extend the current one,
+    /// returns `Error::InvalidArgument`.
+    pub fn update_schema(&mut self, schema: Schema) -> Result<()> {
+        check_schema_extension(&self.index.schema(), &schema)?;
+
+        // The current workers index the pending documents with
+        // the former schema, and terminate.
+        self.recreate_document_channel();
+        let former_workers_join_handle = mem::replace(&mut self.workers_join_handle, vec![]);
+        for worker_handle in former_workers_join_handle {
+            let indexing_worker_result = worker_handle
+                .join()
+                .map_err(|e| Error::from_kind(ErrorKind::ErrorInThread(format!("{:?}", e))))?;
+            indexing_worker_result?;
+        }
+
+        // The segment updater shares the schema of `self.index`.
+        self.index.set_schema(schema);
+        self.start_workers()
+    }
+
     /// Rollback to the last commit
     ///
     /// This cancels all of the update that
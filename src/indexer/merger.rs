@@ -8,20 +8,71 @@ use postings::InvertedIndexSerializer;
 use itertools::Itertools;
 use docset::DocSet;
 use fastfield::DeleteBitSet;
-use schema::{Field, Schema};
+use schema::{Cardinality, Field, FieldType, Schema};
 use termdict::TermMerger;
 use fastfield::FastFieldSerializer;
 use fastfield::FastFieldReader;
+use fastfield::MultiValueIntFastFieldReader;
+use fastfield::has_term_ord_fast_field;
+use fastfield::int_fast_field_cardinality;
 use store::StoreWriter;
 use std::cmp::{max, min};
 use termdict::TermDictionary;
 use termdict::TermStreamer;
+use termdict::TermOrdinal;
+use std::collections::HashMap;
 use fieldnorm::FieldNormsSerializer;
 use fieldnorm::FieldNormsWriter;
 use fieldnorm::FieldNormReader;
 use postings::Postings;
 
 
+/// Maps the term ordinals of each of the merged segments to
+/// the term ordinals of the resulting segment, for a given field.
+///
+/// It is used to remap the columns of term ordinals of the
+/// facets and the string fast fields.
+struct TermOrdinalMapping {
+    per_segment_new_term_ordinals: Vec<Vec<TermOrdinal>>,
+}
+
+impl TermOrdinalMapping {
+    fn new(num_terms_per_segment: Vec<usize>) -> TermOrdinalMapping {
+        TermOrdinalMapping {
+            per_segment_new_term_ordinals: num_terms_per_segment
+                .into_iter()
+                .map(|num_terms| vec![TermOrdinal::default(); num_terms])
+                .collect(),
+        }
+    }
+
+    fn register_from_to(&mut self, segment_ord: usize, from_ord: TermOrdinal, to_ord: TermOrdinal) {
+        self.per_segment_new_term_ordinals[segment_ord][from_ord as usize] = to_ord;
+    }
+
+    fn get_segment(&self, segment_ord: usize) -> &[TermOrdinal] {
+        &self.per_segment_new_term_ordinals[segment_ord][..]
+    }
+}
+
+/// Reads the values of a multi-valued fast field for a document.
+///
+/// If the values are term ordinals, they are remapped to the term ordinals
+/// of the resulting segment.
+fn read_merged_vals(
+    multi_reader: &MultiValueIntFastFieldReader<u64>,
+    doc_id: DocId,
+    new_term_ords_opt: Option<&[TermOrdinal]>,
+    vals: &mut Vec<u64>,
+) {
+    multi_reader.get_vals(doc_id, vals);
+    if let Some(new_term_ords) = new_term_ords_opt {
+        for val in vals.iter_mut() {
+            *val = new_term_ords[*val as usize];
+        }
+    }
+}
+
 fn compute_total_num_tokens(readers: &[SegmentReader], field: Field) -> u64 {
     let mut total_tokens = 0u64;
     let mut count: [usize; 256] = [0; 256];
@@ -144,12 +195,19 @@ impl IndexMerger {
         Ok(())
     }
 
-    fn write_fast_fields(&self, fast_field_serializer: &mut FastFieldSerializer) -> Result<()> {
+    fn write_fast_fields(
+        &self,
+        fast_field_serializer: &mut FastFieldSerializer,
+        term_ord_mappings: &HashMap<Field, TermOrdinalMapping>,
+    ) -> Result<()> {
         let fast_fields: Vec<Field> = self.schema
             .fields()
             .iter()
             .enumerate()
-            .filter(|&(_, field_entry)| field_entry.is_int_fast())
+            .filter(|&(_, field_entry)| {
+                int_fast_field_cardinality(field_entry.field_type())
+                    == Some(Cardinality::SingleValue)
+            })
             .map(|(field_id, _)| Field(field_id as u32))
             .collect();
 
@@ -159,7 +217,7 @@ impl IndexMerger {
             let mut max_val = u64::min_value();
 
             for reader in &self.readers {
-                match reader.fast_field_reader::<u64>(field) {
+                match reader.fast_field_reader_with_idx::<u64>(field, 0) {
                     Ok(u64_reader) => {
                         if let Some((seg_min_val, seg_max_val)) = compute_min_max_val(
                             &u64_reader,
@@ -210,10 +268,161 @@ impl IndexMerger {
 
             fast_single_field_serializer.close_field()?;
         }
+
+        let multi_fast_fields: Vec<Field> = self.schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|&(_, field_entry)| {
+                int_fast_field_cardinality(field_entry.field_type())
+                    == Some(Cardinality::MultiValues)
+                    || has_term_ord_fast_field(field_entry.field_type())
+            })
+            .map(|(field_id, _)| Field(field_id as u32))
+            .collect();
+        for field in multi_fast_fields {
+            let term_ord_mapping_opt = term_ord_mappings.get(&field);
+            self.write_multi_fast_field(field, term_ord_mapping_opt, fast_field_serializer)?;
+        }
+
+        let bytes_fast_fields: Vec<Field> = self.schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|&(_, field_entry)| match *field_entry.field_type() {
+                FieldType::Bytes(ref bytes_options) => bytes_options.is_fast(),
+                _ => false,
+            })
+            .map(|(field_id, _)| Field(field_id as u32))
+            .collect();
+        for field in bytes_fast_fields {
+            self.write_bytes_fast_field(field, fast_field_serializer)?;
+        }
+        Ok(())
+    }
+
+    fn write_multi_fast_field(
+        &self,
+        field: Field,
+        term_ord_mapping_opt: Option<&TermOrdinalMapping>,
+        fast_field_serializer: &mut FastFieldSerializer,
+    ) -> Result<()> {
+        let multi_readers: Vec<(&SegmentReader, MultiValueIntFastFieldReader<u64>)> = self.readers
+            .iter()
+            .map(|reader| (reader, reader.multi_fast_field_reader_unchecked(field)))
+            .collect();
+        let mut vals = Vec::with_capacity(100);
+        let mut total_num_vals = 0u64;
+        let mut min_val = u64::max_value();
+        let mut max_val = u64::min_value();
+        for (segment_ord, &(reader, ref multi_reader)) in multi_readers.iter().enumerate() {
+            let new_term_ords_opt =
+                term_ord_mapping_opt.map(|mapping| mapping.get_segment(segment_ord));
+            for doc_id in 0..reader.max_doc() {
+                if !reader.is_deleted(doc_id) {
+                    read_merged_vals(multi_reader, doc_id, new_term_ords_opt, &mut vals);
+                    for &val in &vals {
+                        min_val = min(min_val, val);
+                        max_val = max(max_val, val);
+                    }
+                    total_num_vals += vals.len() as u64;
+                }
+            }
+        }
+        if total_num_vals == 0 {
+            min_val = 0;
+            max_val = 0;
+        }
+        {
+            // writing the offset index
+            let mut doc_index_serializer =
+                fast_field_serializer.new_u64_fast_field_with_idx(field, 0, total_num_vals, 0)?;
+            let mut offset = 0u64;
+            for &(reader, ref multi_reader) in &multi_readers {
+                for doc_id in 0..reader.max_doc() {
+                    if !reader.is_deleted(doc_id) {
+                        doc_index_serializer.add_val(offset)?;
+                        multi_reader.get_vals(doc_id, &mut vals);
+                        offset += vals.len() as u64;
+                    }
+                }
+            }
+            doc_index_serializer.add_val(offset)?;
+            doc_index_serializer.close_field()?;
+        }
+        {
+            // writing the values themselves
+            let mut value_serializer =
+                fast_field_serializer.new_u64_fast_field_with_idx(field, min_val, max_val, 1)?;
+            for (segment_ord, &(reader, ref multi_reader)) in multi_readers.iter().enumerate() {
+                let new_term_ords_opt =
+                    term_ord_mapping_opt.map(|mapping| mapping.get_segment(segment_ord));
+                for doc_id in 0..reader.max_doc() {
+                    if !reader.is_deleted(doc_id) {
+                        read_merged_vals(multi_reader, doc_id, new_term_ords_opt, &mut vals);
+                        for &val in &vals {
+                            value_serializer.add_val(val)?;
+                        }
+                    }
+                }
+            }
+            value_serializer.close_field()?;
+        }
+        Ok(())
+    }
+
+    fn write_bytes_fast_field(
+        &self,
+        field: Field,
+        fast_field_serializer: &mut FastFieldSerializer,
+    ) -> Result<()> {
+        let mut bytes_readers = Vec::with_capacity(self.readers.len());
+        let mut total_num_bytes = 0u64;
+        for reader in &self.readers {
+            let bytes_reader = reader.bytes_fast_field_reader(field)?;
+            for doc_id in 0..reader.max_doc() {
+                if !reader.is_deleted(doc_id) {
+                    total_num_bytes += bytes_reader.get_val(doc_id).len() as u64;
+                }
+            }
+            bytes_readers.push((reader, bytes_reader));
+        }
+        {
+            // writing the offset index
+            let mut doc_index_serializer =
+                fast_field_serializer.new_u64_fast_field_with_idx(field, 0, total_num_bytes, 0)?;
+            let mut offset = 0u64;
+            for &(reader, ref bytes_reader) in &bytes_readers {
+                for doc_id in 0..reader.max_doc() {
+                    if !reader.is_deleted(doc_id) {
+                        doc_index_serializer.add_val(offset)?;
+                        offset += bytes_reader.get_val(doc_id).len() as u64;
+                    }
+                }
+            }
+            doc_index_serializer.add_val(offset)?;
+            doc_index_serializer.close_field()?;
+        }
+        {
+            // writing the values themselves
+            let mut value_serializer = fast_field_serializer.new_bytes_fast_field_with_idx(field, 1);
+            for &(reader, ref bytes_reader) in &bytes_readers {
+                for doc_id in 0..reader.max_doc() {
+                    if !reader.is_deleted(doc_id) {
+                        value_serializer.write_all(bytes_reader.get_val(doc_id))?;
+                    }
+                }
+            }
+            value_serializer.flush()?;
+        }
         Ok(())
     }
 
-    fn write_postings(&self, serializer: &mut InvertedIndexSerializer) -> Result<()> {
+    fn write_postings(
+        &self,
+        serializer: &mut InvertedIndexSerializer,
+    ) -> Result<HashMap<Field, TermOrdinalMapping>> {
+        let mut term_ord_mappings = HashMap::new();
 
         let mut positions_buffer: Vec<u32> = Vec::with_capacity(1_000);
         let mut delta_computer = DeltaComputer::new();
@@ -239,6 +448,21 @@ impl IndexMerger {
             let mut merged_terms = TermMerger::new(field_term_streams);
             let mut max_doc = 0;
 
+            let field_entry = self.schema.get_field_entry(indexed_field);
+
+            // The fast field of facets and string fast fields hold term ordinals,
+            // which need to be remapped.
+            let mut term_ord_mapping_opt = if has_term_ord_fast_field(field_entry.field_type()) {
+                let num_terms_per_segment = field_readers
+                    .iter()
+                    .map(|field_reader| field_reader.terms().num_terms())
+                    .collect();
+                Some(TermOrdinalMapping::new(num_terms_per_segment))
+            } else {
+                None
+            };
+            let mut new_term_ord: TermOrdinal = 0;
+
             // map from segment doc ids to the resulting merged segment doc id.
             let mut merged_doc_id_map: Vec<Vec<Option<DocId>>> =
                 Vec::with_capacity(self.readers.len());
@@ -273,8 +497,6 @@ impl IndexMerger {
             // ...
             let mut field_serializer = serializer.new_field(indexed_field, total_num_tokens)?;
 
-            let field_entry = self.schema.get_field_entry(indexed_field);
-
             // ... set segment postings option the new field.
             let segment_postings_option =
                 field_entry.field_type().get_index_record_option().expect(
@@ -316,6 +538,17 @@ impl IndexMerger {
                     // the term, so we add it.
                     field_serializer.new_term(term_bytes)?;
 
+                    if let Some(ref mut term_ord_mapping) = term_ord_mapping_opt {
+                        for heap_item in merged_terms.current_kvs() {
+                            term_ord_mapping.register_from_to(
+                                heap_item.segment_ord,
+                                heap_item.streamer.term_ord(),
+                                new_term_ord,
+                            );
+                        }
+                    }
+                    new_term_ord += 1;
+
                     // We can now serialize this postings, by pushing each document to the
                     // postings serializer.
                     for (segment_ord, mut segment_postings) in segment_postings {
@@ -357,8 +590,12 @@ impl IndexMerger {
             }
 
             field_serializer.close()?;
+
+            if let Some(term_ord_mapping) = term_ord_mapping_opt {
+                term_ord_mappings.insert(indexed_field, term_ord_mapping);
+            }
         }
-        Ok(())
+        Ok(term_ord_mappings)
     }
 
     fn write_storable_fields(&self, store_writer: &mut StoreWriter) -> Result<()> {
@@ -381,9 +618,9 @@ impl IndexMerger {
 
 impl SerializableSegment for IndexMerger {
     fn write(&self, mut serializer: SegmentSerializer) -> Result<u32> {
-        self.write_postings(serializer.get_postings_serializer())?;
+        let term_ord_mappings = self.write_postings(serializer.get_postings_serializer())?;
         self.write_fieldnorms(serializer.get_fieldnorms_serializer())?;
-        self.write_fast_fields(serializer.get_fast_field_serializer())?;
+        self.write_fast_fields(serializer.get_fast_field_serializer(), &term_ord_mappings)?;
         self.write_storable_fields(serializer.get_store_writer())?;
         serializer.close()?;
         Ok(self.max_doc)
@@ -394,6 +631,7 @@ impl SerializableSegment for IndexMerger {
 mod tests {
     use schema;
     use schema::Document;
+    use schema::Facet;
     use schema::Term;
     use schema::TextFieldIndexing;
     use query::TermQuery;
@@ -545,6 +783,201 @@ mod tests {
         collector.vals()
     }
 
+    #[test]
+    fn test_index_merger_signed_and_float_fast_fields() {
+        let mut schema_builder = schema::SchemaBuilder::default();
+        let fast_options = schema::IntOptions::default().set_fast(Cardinality::SingleValue);
+        let int_field = schema_builder.add_i64_field("intval", fast_options.clone());
+        let float_field = schema_builder.add_f64_field("floatval", fast_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(int_field => -3i64, float_field => -1.5f64));
+            index_writer.add_document(doc!(int_field => 5i64, float_field => 0.25f64));
+            index_writer.commit().expect("commit failed");
+            index_writer.add_document(doc!(int_field => -7i64, float_field => 1000.5f64));
+            index_writer.commit().expect("commit failed");
+        }
+        {
+            let segment_ids = index
+                .searchable_segment_ids()
+                .expect("Searchable segments failed.");
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let segment_reader = searcher.segment_reader(0);
+        let int_reader = segment_reader.fast_field_reader::<i64>(int_field).unwrap();
+        let float_reader = segment_reader.fast_field_reader::<f64>(float_field).unwrap();
+        let mut vals: Vec<(i64, f64)> = (0..3)
+            .map(|doc| (int_reader.get(doc), float_reader.get(doc)))
+            .collect();
+        vals.sort_by_key(|&(int_val, _)| int_val);
+        assert_eq!(vals, vec![(-7i64, 1000.5f64), (-3i64, -1.5f64), (5i64, 0.25f64)]);
+    }
+
+    #[test]
+    fn test_index_merger_multi_valued_fast_field() {
+        let mut schema_builder = schema::SchemaBuilder::default();
+        let int_field =
+            schema_builder.add_i64_field("intvals", schema::MULTI_FAST | schema::INT_INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(int_field => 1i64, int_field => 3i64));
+            index_writer.add_document(doc!());
+            index_writer.commit().expect("commit failed");
+            index_writer.add_document(doc!(int_field => -4i64));
+            index_writer.add_document(doc!(
+                int_field => -5i64,
+                int_field => -20i64,
+                int_field => 1i64
+            ));
+            index_writer.delete_term(Term::from_field_i64(int_field, 3i64));
+            index_writer.commit().expect("commit failed");
+        }
+        {
+            let segment_ids = index
+                .searchable_segment_ids()
+                .expect("Searchable segments failed.");
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let multi_reader = searcher
+            .segment_reader(0)
+            .multi_fast_field_reader::<i64>(int_field)
+            .unwrap();
+        let mut vals: Vec<Vec<i64>> = (0..3)
+            .map(|doc| {
+                let mut doc_vals = Vec::new();
+                multi_reader.get_vals(doc, &mut doc_vals);
+                doc_vals
+            })
+            .collect();
+        vals.sort();
+        assert_eq!(vals, vec![vec![], vec![-5i64, -20i64, 1i64], vec![-4i64]]);
+    }
+
+    #[test]
+    fn test_index_merger_term_ord_fast_fields() {
+        let mut schema_builder = schema::SchemaBuilder::default();
+        let brand_field = schema_builder.add_text_field("brand", schema::STRING.set_fast());
+        let facet_field = schema_builder.add_facet_field("category");
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            {
+                let mut doc = Document::default();
+                doc.add_text(brand_field, "nike");
+                doc.add_facet(facet_field, "/shoes/running");
+                index_writer.add_document(doc);
+            }
+            index_writer.add_document(doc!(brand_field => "zara"));
+            index_writer.commit().expect("commit failed");
+            {
+                let mut doc = Document::default();
+                doc.add_text(brand_field, "adidas");
+                doc.add_facet(facet_field, "/bags");
+                index_writer.add_document(doc);
+            }
+            index_writer.add_document(doc!(brand_field => "puma"));
+            index_writer.delete_term(Term::from_field_text(brand_field, "zara"));
+            index_writer.commit().expect("commit failed");
+        }
+        {
+            let segment_ids = index
+                .searchable_segment_ids()
+                .expect("Searchable segments failed.");
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let segment_reader = searcher.segment_reader(0);
+        let str_reader = segment_reader.str_fast_field_reader(brand_field).unwrap();
+        let mut facet_reader = segment_reader.facet_reader(facet_field).unwrap();
+        assert_eq!(str_reader.num_terms(), 3);
+        let mut term_ords = Vec::new();
+        let mut brands = Vec::new();
+        let mut facets = Vec::new();
+        for doc in 0..3 {
+            str_reader.term_ords(doc, &mut term_ords);
+            assert_eq!(term_ords.len(), 1);
+            let mut brand = String::new();
+            str_reader.str_from_ord(term_ords[0], &mut brand);
+            brands.push(brand);
+            facet_reader.facet_ords(doc, &mut term_ords);
+            for &facet_ord in &term_ords {
+                let mut facet = Facet::root();
+                facet_reader.facet_from_ord(facet_ord, &mut facet);
+                facets.push(facet.to_string());
+            }
+        }
+        brands.sort();
+        assert_eq!(brands, vec!["adidas", "nike", "puma"]);
+        facets.sort();
+        assert_eq!(facets, vec!["/bags", "/shoes/running"]);
+    }
+
+    #[test]
+    fn test_index_merger_bytes_fast_field() {
+        let mut schema_builder = schema::SchemaBuilder::default();
+        let bytes_options = schema::BytesOptions::default().set_fast().set_indexed();
+        let bytes_field = schema_builder.add_bytes_field("bytes", bytes_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(bytes_field => vec![1u8, 2u8]));
+            index_writer.add_document(doc!(bytes_field => vec![3u8]));
+            index_writer.commit().expect("commit failed");
+            index_writer.add_document(doc!());
+            index_writer.add_document(doc!(bytes_field => vec![4u8, 5u8, 6u8]));
+            index_writer.delete_term(Term::from_field_bytes(bytes_field, &[3u8]));
+            index_writer.commit().expect("commit failed");
+        }
+        {
+            let segment_ids = index
+                .searchable_segment_ids()
+                .expect("Searchable segments failed.");
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let bytes_reader = searcher
+            .segment_reader(0)
+            .bytes_fast_field_reader(bytes_field)
+            .unwrap();
+        let mut vals: Vec<Vec<u8>> = (0..3)
+            .map(|doc| bytes_reader.get_val(doc).to_vec())
+            .collect();
+        vals.sort();
+        assert_eq!(vals, vec![vec![], vec![1u8, 2u8], vec![4u8, 5u8, 6u8]]);
+    }
+
     #[test]
     fn test_index_merger_with_deletes() {
         let mut schema_builder = schema::SchemaBuilder::default();
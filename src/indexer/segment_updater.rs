@@ -241,13 +241,13 @@ impl SegmentUpdater {
         Ok(segment_entries)
     }
 
-    pub fn save_metas(&self, opstamp: u64, commit_message: Option<String>) {
+    pub fn save_metas(&self, schema: Schema, opstamp: u64, commit_message: Option<String>) {
         if self.is_alive() {
             let index = &self.0.index;
             let directory = index.directory();
             save_metas(
                 self.0.segment_manager.committed_segment_metas(),
-                index.schema(),
+                schema,
                 opstamp,
                 commit_message,
                 directory.box_clone().borrow_mut(),
@@ -276,7 +276,10 @@ impl SegmentUpdater {
                     .purge_deletes(opstamp)
                     .expect("Failed purge deletes");
                 segment_updater.0.segment_manager.commit(segment_entries);
-                segment_updater.save_metas(opstamp, payload);
+                // The schema of the writer, possibly updated
+                // since the last commit, is committed.
+                let schema = segment_updater.0.index.schema();
+                segment_updater.save_metas(schema, opstamp, payload);
                 segment_updater.garbage_collect_files_exec();
                 segment_updater.consider_merge_options();
             }
@@ -427,7 +430,11 @@ impl SegmentUpdater {
             segment_updater.consider_merge_options();
             info!("save metas");
             let previous_metas = segment_updater.0.index.load_metas().unwrap();
-            segment_updater.save_metas(previous_metas.opstamp, previous_metas.payload);
+            segment_updater.save_metas(
+                previous_metas.schema,
+                previous_metas.opstamp,
+                previous_metas.payload,
+            );
             segment_updater.garbage_collect_files_exec();
         }).wait()
     }
@@ -18,6 +18,9 @@ use tokenizer::FacetTokenizer;
 use tokenizer::{TokenStream, Tokenizer};
 use schema::Value;
 use fieldnorm::FieldNormsWriter;
+use schema::Field;
+use schema::JSON_TEXT_CODE;
+use serde_json::Value as JsonValue;
 
 /// A `SegmentWriter` is in charge of creating segment index from a
 /// documents.
@@ -65,6 +68,12 @@ impl<'a> SegmentWriter<'a> {
                         segment.index().tokenizers().get(tokenizer_name)
                     },
                 ),
+                FieldType::Json(ref json_object_options) => json_object_options
+                    .get_indexing_options()
+                    .and_then(|text_index_option| {
+                        let tokenizer_name = &text_index_option.tokenizer();
+                        segment.index().tokenizers().get(tokenizer_name)
+                    }),
                 _ => None,
             })
             .collect();
@@ -173,8 +182,25 @@ impl<'a> SegmentWriter<'a> {
                             0
                         } else {
                             let mut token_stream = tokenizer.token_stream_texts(&texts[..]);
-                            self.multifield_postings
-                                .index_text(doc_id, field, &mut token_stream)
+                            match self.fast_field_writers.get_multivalue_writer(field) {
+                                Some(term_ords_writer) => {
+                                    // string fast field
+                                    let mut term_ids = Vec::new();
+                                    let num_tokens =
+                                        self.multifield_postings.index_text_with_term_ids(
+                                            doc_id,
+                                            field,
+                                            &mut token_stream,
+                                            &mut term_ids,
+                                        );
+                                    for term_id in term_ids {
+                                        term_ords_writer.add_val(term_id);
+                                    }
+                                    num_tokens
+                                }
+                                None => self.multifield_postings
+                                    .index_text(doc_id, field, &mut token_stream),
+                            }
                         }
                     } else {
                         0
@@ -204,6 +230,96 @@ impl<'a> SegmentWriter<'a> {
                         }
                     }
                 }
+                FieldType::F64(ref int_option) => {
+                    if int_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_f64(
+                                field_value.field(),
+                                field_value.value().f64_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::Bool(ref int_option) => {
+                    if int_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_bool(
+                                field_value.field(),
+                                field_value.value().bool_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::Date(ref int_option) => {
+                    if int_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_date(
+                                field_value.field(),
+                                field_value.value().date_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::Bytes(ref bytes_option) => {
+                    if bytes_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_bytes(
+                                field_value.field(),
+                                field_value.value().bytes_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::GeoPoint(ref geo_point_option) => {
+                    if geo_point_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_geo_point(
+                                field_value.field(),
+                                &field_value.value().geo_point_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::Ip(ref ip_option) => {
+                    if ip_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_ip(
+                                field_value.field(),
+                                field_value.value().ip_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::Json(ref json_object_options) => {
+                    if json_object_options.is_indexed() {
+                        let mut position = 0u32;
+                        let mut num_tokens = 0u32;
+                        let mut path = String::new();
+                        for field_value in field_values {
+                            for (key, json_value) in field_value.value().json_object_value() {
+                                path.push_str(key);
+                                index_json_value(
+                                    &mut self.multifield_postings,
+                                    &self.tokenizers[field.0 as usize],
+                                    doc_id,
+                                    field,
+                                    &mut path,
+                                    json_value,
+                                    &mut position,
+                                    &mut num_tokens,
+                                );
+                                path.clear();
+                            }
+                        }
+                        self.fieldnorms_writer.record(doc_id, field, num_tokens);
+                    }
+                }
             }
         }
         doc.filter_fields(|field| schema.get_field_entry(field).is_stored());
@@ -234,6 +350,88 @@ impl<'a> SegmentWriter<'a> {
     }
 }
 
+/// Position gap inserted between two text leaves of a JSON object,
+/// to prevent phrase queries from matching across them.
+const JSON_POSITION_GAP: u32 = 2;
+
+/// Indexes a JSON value, found under `path` within the JSON object field `field`.
+///
+/// Objects are walked recursively, and the values of arrays
+/// are indexed under the path of the array.
+/// `num_tokens` is incremented by the number of text tokens indexed.
+#[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+fn index_json_value(
+    multifield_postings: &mut MultiFieldPostingsWriter,
+    tokenizer: &Option<Box<BoxedTokenizer>>,
+    doc_id: DocId,
+    field: Field,
+    path: &mut String,
+    json_value: &JsonValue,
+    position: &mut u32,
+    num_tokens: &mut u32,
+) {
+    match *json_value {
+        JsonValue::Null => {}
+        JsonValue::Bool(val) => {
+            let term = Term::from_field_json_bool(field, path, val);
+            multifield_postings.subscribe(doc_id, &term);
+        }
+        JsonValue::Number(ref number) => {
+            let term = if let Some(val) = number.as_i64() {
+                Term::from_field_json_i64(field, path, val)
+            } else if let Some(val) = number.as_f64() {
+                Term::from_field_json_f64(field, path, val)
+            } else {
+                return;
+            };
+            multifield_postings.subscribe(doc_id, &term);
+        }
+        JsonValue::String(ref text) => {
+            if let Some(ref tokenizer) = *tokenizer {
+                let term_prefix = Term::json_path_prefix(field, path, JSON_TEXT_CODE);
+                let mut token_stream = tokenizer.token_stream(text);
+                *num_tokens += multifield_postings.index_text_with_prefix(
+                    doc_id,
+                    &term_prefix,
+                    position,
+                    &mut token_stream,
+                );
+                *position += JSON_POSITION_GAP;
+            }
+        }
+        JsonValue::Array(ref json_values) => for json_value in json_values {
+            index_json_value(
+                multifield_postings,
+                tokenizer,
+                doc_id,
+                field,
+                path,
+                json_value,
+                position,
+                num_tokens,
+            );
+        },
+        JsonValue::Object(ref json_object) => {
+            let path_len = path.len();
+            for (key, json_value) in json_object {
+                path.push('.');
+                path.push_str(key);
+                index_json_value(
+                    multifield_postings,
+                    tokenizer,
+                    doc_id,
+                    field,
+                    path,
+                    json_value,
+                    position,
+                    num_tokens,
+                );
+                path.truncate(path_len);
+            }
+        }
+    }
+}
+
 // This method is used as a trick to workaround the borrow checker
 fn write(
     multifield_postings: &MultiFieldPostingsWriter,
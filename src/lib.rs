@@ -131,12 +131,15 @@ extern crate error_chain;
 
 #[cfg(feature="mmap")]
 extern crate atomicwrites;
+extern crate base64;
 extern crate bit_set;
 extern crate byteorder;
 extern crate chan;
+extern crate chrono;
 extern crate combine;
 extern crate crossbeam;
 extern crate fst;
+extern crate fst_regex;
 extern crate futures;
 extern crate futures_cpupool;
 extern crate itertools;
@@ -215,7 +218,8 @@ pub use core::{InvertedIndexReader, SegmentReader};
 pub use postings::Postings;
 pub use core::SegmentComponent;
 
-pub use common::{i64_to_u64, u64_to_i64};
+pub use common::{f64_to_u64, i64_to_u64, u64_to_f64, u64_to_i64};
+pub use common::{ip_to_ipv6, ipv6_to_ip};
 
 /// Expose the current version of tantivy, as well
 /// whether it was compiled with the simd compression.
@@ -247,6 +251,12 @@ pub type DocId = u32;
 /// to the search
 pub type Score = f32;
 
+/// A UTC date time, as handled by the `Date` field type.
+///
+/// Dates are indexed, stored and kept in fast fields
+/// with a precision of one second.
+pub type DateTime = chrono::DateTime<chrono::Utc>;
+
 /// A `SegmentLocalId` identifies a segment.
 /// It only makes sense for a given searcher.
 pub type SegmentLocalId = u32;
@@ -284,7 +294,9 @@ mod tests {
     use Index;
     use core::SegmentReader;
     use query::BooleanQuery;
+    use query::QueryParser;
     use schema::*;
+    use DocAddress;
     use docset::DocSet;
     use IndexWriter;
     use Postings;
@@ -690,6 +702,86 @@ mod tests {
         assert!(!postings.advance());
     }
 
+    #[test]
+    fn test_json_object_field() {
+        let mut schema_builder = SchemaBuilder::default();
+        let indexing = TextFieldIndexing::default()
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        let attributes_field = schema_builder.add_json_object_field(
+            "attributes",
+            JsonObjectOptions::default()
+                .set_indexing_options(indexing)
+                .set_stored(),
+        );
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            let docs = [
+                r#"{"attributes": {"color": "red", "dims": {"width": 3},
+                                   "tags": ["big", "shiny"]}}"#,
+                r#"{"attributes": {"color": "dark blue", "dims": {"width": 4.5},
+                                   "available": true}}"#,
+                r#"{"attributes": {"shade": "red", "name": "blue red"}}"#,
+            ];
+            for doc_json in &docs {
+                index_writer.add_document(schema.parse_document(doc_json).unwrap());
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![]);
+        let get_doc_ids = |query_str: &str| {
+            let query = query_parser.parse_query(query_str).unwrap();
+            let mut collector = TestCollector::default();
+            searcher.search(&*query, &mut collector).unwrap();
+            collector.docs()
+        };
+        assert_eq!(get_doc_ids("attributes.color:red"), vec![0]);
+        assert_eq!(get_doc_ids("attributes.color:blue"), vec![1]);
+        assert_eq!(get_doc_ids("attributes.shade:red"), vec![2]);
+        assert_eq!(get_doc_ids("attributes.dims.width:3"), vec![0]);
+        assert_eq!(get_doc_ids("attributes.dims.width:4.5"), vec![1]);
+        assert_eq!(get_doc_ids("attributes.available:true"), vec![1]);
+        assert_eq!(get_doc_ids("attributes.tags:shiny"), vec![0]);
+        assert_eq!(get_doc_ids("attributes.color:rad~1"), vec![0]);
+        assert!(get_doc_ids("attributes.colr:red~1").is_empty());
+        assert!(get_doc_ids("attributes.shad:red~1").is_empty());
+        assert_eq!(get_doc_ids("attributes.color:\"dark blue\""), vec![1]);
+        assert!(get_doc_ids("attributes.name:\"red blue\"").is_empty());
+        assert!(get_doc_ids("attributes.dims:3").is_empty());
+        let doc = searcher.doc(&DocAddress(0, 0)).unwrap();
+        assert_eq!(
+            schema.to_json(&doc),
+            r#"{"attributes":[{"color":"red","dims":{"width":3},"tags":["big","shiny"]}]}"#
+        );
+    }
+
+    #[test]
+    fn test_stored_only_json_object_field() {
+        use termdict::TermDictionary;
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let attributes_field = schema_builder
+            .add_json_object_field("attributes", JsonObjectOptions::default().set_stored());
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            let doc_json = r#"{"text": "a", "attributes": {"color": "red", "width": 3}}"#;
+            index_writer.add_document(schema.parse_document(doc_json).unwrap());
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        assert_eq!(segment_reader.inverted_index(attributes_field).terms().num_terms(), 0);
+        assert_eq!(segment_reader.inverted_index(text_field).terms().num_terms(), 1);
+        let doc = searcher.doc(&DocAddress(0, 0)).unwrap();
+        assert_eq!(schema.to_json(&doc), r#"{"attributes":[{"color":"red","width":3}]}"#);
+    }
+
     #[test]
     fn test_indexedfield_not_in_documents() {
         let mut schema_builder = SchemaBuilder::default();
@@ -22,9 +22,10 @@ fn posting_from_field_entry<'a>(
     heap: &'a Heap,
 ) -> Box<PostingsWriter + 'a> {
     match *field_entry.field_type() {
-        FieldType::Str(ref text_options) => text_options
-            .get_indexing_options()
-            .map(|indexing_options| match indexing_options.index_option() {
+        FieldType::Str(_) | FieldType::Json(_) => field_entry
+            .field_type()
+            .get_index_record_option()
+            .map(|index_record_option| match index_record_option {
                 IndexRecordOption::Basic => {
                     SpecializedPostingsWriter::<NothingRecorder>::new_boxed(heap)
                 }
@@ -36,7 +37,15 @@ fn posting_from_field_entry<'a>(
                 }
             })
             .unwrap_or_else(|| SpecializedPostingsWriter::<NothingRecorder>::new_boxed(heap)),
-        FieldType::U64(_) | FieldType::I64(_) | FieldType::HierarchicalFacet => {
+        FieldType::U64(_)
+        | FieldType::I64(_)
+        | FieldType::F64(_)
+        | FieldType::Bool(_)
+        | FieldType::Date(_)
+        | FieldType::HierarchicalFacet
+        | FieldType::Bytes(_)
+        | FieldType::Ip(_)
+        | FieldType::GeoPoint(_) => {
             SpecializedPostingsWriter::<NothingRecorder>::new_boxed(heap)
         }
     }
@@ -71,6 +80,63 @@ impl<'a> MultiFieldPostingsWriter<'a> {
         postings_writer.index_text(&mut self.term_index, doc, field, token_stream, self.heap)
     }
 
+    /// Tokenizes a text and subscribes all of its tokens, like `index_text`.
+    ///
+    /// The `UnorderedTermId` of each of the tokens is appended to `term_ids`.
+    ///
+    /// Returns the number of tokens.
+    pub fn index_text_with_term_ids(
+        &mut self,
+        doc: DocId,
+        field: Field,
+        token_stream: &mut TokenStream,
+        term_ids: &mut Vec<UnorderedTermId>,
+    ) -> u32 {
+        let postings_writer = self.per_field_postings_writers[field.0 as usize].deref_mut();
+        let term_index = &mut self.term_index;
+        let heap = self.heap;
+        let mut term = unsafe { Term::with_capacity(100) };
+        term.set_field(field);
+        let mut sink = |token: &Token| {
+            term.set_text(token.text.as_str());
+            let term_id =
+                postings_writer.subscribe(term_index, doc, token.position as u32, &term, heap);
+            term_ids.push(term_id);
+        };
+        token_stream.process(&mut sink)
+    }
+
+    /// Tokenizes a text and subscribes all of its tokens, appended
+    /// to the value of `term_prefix`.
+    ///
+    /// The positions of the tokens are shifted by `*position`,
+    /// which is then updated to the position following the last token.
+    ///
+    /// Returns the number of tokens.
+    pub fn index_text_with_prefix(
+        &mut self,
+        doc: DocId,
+        term_prefix: &Term,
+        position: &mut u32,
+        token_stream: &mut TokenStream,
+    ) -> u32 {
+        let field = term_prefix.field();
+        let prefix_len = term_prefix.as_slice().len();
+        let postings_writer = self.per_field_postings_writers[field.0 as usize].deref_mut();
+        let term_index = &mut self.term_index;
+        let heap = self.heap;
+        let position_offset = *position;
+        let mut term = term_prefix.clone();
+        let mut sink = |token: &Token| {
+            term.truncate(prefix_len);
+            term.append_bytes(token.text.as_bytes());
+            let token_position = position_offset + token.position as u32;
+            postings_writer.subscribe(term_index, doc, token_position, &term, heap);
+            *position = token_position + 1;
+        };
+        token_stream.process(&mut sink)
+    }
+
     pub fn subscribe(&mut self, doc: DocId, term: &Term) -> UnorderedTermId {
         let postings_writer = self.per_field_postings_writers[term.field().0 as usize].deref_mut();
         postings_writer.subscribe(&mut self.term_index, doc, 0u32, term, self.heap)
@@ -0,0 +1,60 @@
+use fst::Automaton;
+use schema::{Field, IndexRecordOption};
+use query::{Scorer, Weight};
+use termdict::{TermStreamer, TermStreamerBuilder};
+use core::SegmentReader;
+use common::BitSet;
+use Result;
+use query::BitSetDocSet;
+use query::ConstScorer;
+
+/// A weight matching all of the documents containing a term
+/// accepted by an automaton.
+///
+/// The automaton is intersected with the term dictionary of each
+/// segment, so that only the matching terms are visited.
+/// As for the `RangeWeight`, the matching documents are
+/// collected into a `BitSet` and all get a constant score.
+pub struct AutomatonWeight<A>
+where
+    A: Automaton,
+{
+    field: Field,
+    automaton: A,
+}
+
+impl<A> AutomatonWeight<A>
+where
+    A: Automaton,
+{
+    /// Creates a new `AutomatonWeight`.
+    pub fn new(field: Field, automaton: A) -> AutomatonWeight<A> {
+        AutomatonWeight { field, automaton }
+    }
+}
+
+impl<A> Weight for AutomatonWeight<A>
+where
+    A: Automaton,
+{
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+
+        let inverted_index = reader.inverted_index(self.field);
+        let term_dict = inverted_index.terms();
+        let mut term_stream = term_dict.search(&self.automaton).into_stream();
+        while term_stream.advance() {
+            let term_info = term_stream.value();
+            let mut block_segment_postings = inverted_index
+                .read_block_postings_from_terminfo(term_info, IndexRecordOption::Basic);
+            while block_segment_postings.advance() {
+                for &doc in block_segment_postings.docs() {
+                    doc_bitset.insert(doc);
+                }
+            }
+        }
+        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(box ConstScorer::new(doc_bitset))
+    }
+}
@@ -2,6 +2,7 @@ use fieldnorm::FieldNormReader;
 use Term;
 use Searcher;
 use Score;
+use query::Explanation;
 
 const K1: f32 = 1.2;
 const B: f32 = 0.75;
@@ -11,6 +12,16 @@ fn idf(doc_freq: u64, doc_count: u64) -> f32 {
     (1f32 + x).ln()
 }
 
+fn idf_explain(doc_freq: u64, doc_count: u64) -> Explanation {
+    let mut explanation = Explanation::new(
+        "idf, computed as log(1 + (N - n + 0.5) / (n + 0.5))",
+        idf(doc_freq, doc_count),
+    );
+    explanation.add_const("n, number of documents containing the term", doc_freq as f32);
+    explanation.add_const("N, total number of documents", doc_count as f32);
+    explanation
+}
+
 
 fn cached_tf_component(fieldnorm: u32, average_fieldnorm: f32) -> f32 {
     K1 * (1f32 - B + B * fieldnorm as f32 / average_fieldnorm)
@@ -27,23 +38,43 @@ fn compute_tf_cache(average_fieldnorm: f32) -> [f32; 256] {
 
 #[derive(Clone)]
 pub struct BM25Weight {
+    doc_freqs: Vec<Vec<u64>>,
+    doc_count: u64,
     weight: f32,
     cache: [f32; 256],
+    average_fieldnorm: f32,
 }
 
 impl BM25Weight {
 
     pub fn null() -> BM25Weight {
         BM25Weight {
+            doc_freqs: Vec::new(),
+            doc_count: 0,
             weight: 0f32,
-            cache: [1f32; 256]
+            cache: [1f32; 256],
+            average_fieldnorm: 1f32,
         }
     }
 
     pub fn for_terms(searcher: &Searcher, terms: &[Term]) -> BM25Weight {
-        assert!(!terms.is_empty(), "BM25 requires at least one term");
-        let field = terms[0].field();
-        for term in &terms[1..] {
+        let positions: Vec<&[Term]> = terms.chunks(1).collect();
+        BM25Weight::for_positions(searcher, &positions)
+    }
+
+    /// Creates the weight of a phrase accepting several alternative
+    /// terms at each of its positions.
+    ///
+    /// The idf of a position is the highest idf of its alternative terms,
+    /// so that adding synonyms to a position does not inflate its weight.
+    /// The idf of the phrase is the sum of the idf of its positions.
+    pub fn for_positions(searcher: &Searcher, positions: &[&[Term]]) -> BM25Weight {
+        assert!(
+            !positions.is_empty() && positions.iter().all(|terms| !terms.is_empty()),
+            "BM25 requires at least one term at each position"
+        );
+        let field = positions[0][0].field();
+        for term in positions.iter().flat_map(|terms| terms.iter()) {
             assert_eq!(term.field(), field, "All terms must belong to the same field.");
         }
 
@@ -56,27 +87,109 @@ impl BM25Weight {
         }
         let average_fieldnorm = total_num_tokens as f32 / total_num_docs as f32;
 
-        let idf = terms.iter()
-            .map(|term| {
-                let term_doc_freq = searcher.doc_freq(term);
-                idf(term_doc_freq, total_num_docs)
-            })
-            .sum::<f32>();
-        BM25Weight::new(idf, average_fieldnorm)
+        let doc_freqs: Vec<Vec<u64>> = positions
+            .iter()
+            .map(|terms| terms.iter().map(|term| searcher.doc_freq(term)).collect())
+            .collect();
+        BM25Weight::new(doc_freqs, total_num_docs, average_fieldnorm)
     }
 
-    fn new(idf: f32, average_fieldnorm: f32) -> BM25Weight {
+    fn new(doc_freqs: Vec<Vec<u64>>, doc_count: u64, average_fieldnorm: f32) -> BM25Weight {
+        let total_idf: f32 = doc_freqs
+            .iter()
+            .map(|position_doc_freqs| {
+                position_doc_freqs
+                    .iter()
+                    .map(|&doc_freq| idf(doc_freq, doc_count))
+                    .fold(0f32, f32::max)
+            })
+            .sum();
         BM25Weight {
-            weight: idf * (1f32 + K1),
+            doc_freqs,
+            doc_count,
+            weight: total_idf * (1f32 + K1),
             cache: compute_tf_cache(average_fieldnorm),
+            average_fieldnorm,
+        }
+    }
+
+    /// Explains the idf of the weight.
+    ///
+    /// It is only built on demand, as the weight is cloned
+    /// for every segment.
+    fn idf_explain(&self) -> Explanation {
+        let mut idf_explains: Vec<Explanation> = self.doc_freqs
+            .iter()
+            .map(|position_doc_freqs| {
+                let mut term_idf_explains: Vec<Explanation> = position_doc_freqs
+                    .iter()
+                    .map(|&doc_freq| idf_explain(doc_freq, self.doc_count))
+                    .collect();
+                if term_idf_explains.len() == 1 {
+                    return term_idf_explains.pop().unwrap();
+                }
+                let idf = term_idf_explains
+                    .iter()
+                    .map(|explain| explain.value())
+                    .fold(0f32, f32::max);
+                let mut idf_explain = Explanation::new("idf, max of:", idf);
+                for explain in term_idf_explains {
+                    idf_explain.add_detail(explain);
+                }
+                idf_explain
+            })
+            .collect();
+        match idf_explains.len() {
+            0 => Explanation::new("idf", 0f32),
+            1 => idf_explains.pop().unwrap(),
+            _ => {
+                let idf = idf_explains.iter().map(|explain| explain.value()).sum::<f32>();
+                let mut idf_explain = Explanation::new("idf, sum of:", idf);
+                for explain in idf_explains {
+                    idf_explain.add_detail(explain);
+                }
+                idf_explain
+            }
         }
     }
 
     #[inline(always)]
     pub fn score(&self, fieldnorm_id: u8, term_freq: u32) -> Score {
+        self.score_with_freq(fieldnorm_id, term_freq as f32)
+    }
+
+    /// Same as `score`, for a frequency that may not be an integer,
+    /// such as the sloppy frequency of a phrase.
+    #[inline(always)]
+    pub fn score_with_freq(&self, fieldnorm_id: u8, freq: f32) -> Score {
+        let norm = self.cache[fieldnorm_id as usize];
+        self.weight * freq / (freq + norm)
+    }
+
+    /// Explains the score returned by `score` for
+    /// the same fieldnorm id and term frequency.
+    pub fn explain(&self, fieldnorm_id: u8, term_freq: u32) -> Explanation {
+        let mut explanation =
+            Explanation::new("BM25, product of:", self.score(fieldnorm_id, term_freq));
+        explanation.add_const("(1 + k1)", 1f32 + K1);
+        explanation.add_detail(self.idf_explain());
+
+        let freq = term_freq as f32;
         let norm = self.cache[fieldnorm_id as usize];
-        let term_freq = term_freq as f32;
-        self.weight * term_freq / (term_freq + norm)
+        let mut tf_explain = Explanation::new(
+            "tf, computed as freq / (freq + k1 * (1 - b + b * dl / avgdl))",
+            freq / (freq + norm),
+        );
+        tf_explain.add_const("freq, occurrences of the term within the document", freq);
+        tf_explain.add_const("k1, term saturation parameter", K1);
+        tf_explain.add_const("b, length normalization parameter", B);
+        tf_explain.add_const(
+            "dl, length of the field (fieldnorm)",
+            FieldNormReader::id_to_fieldnorm(fieldnorm_id) as f32,
+        );
+        tf_explain.add_const("avgdl, average length of the field", self.average_fieldnorm);
+        explanation.add_detail(tf_explain);
+        explanation
     }
 }
 
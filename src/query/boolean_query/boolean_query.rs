@@ -7,25 +7,37 @@ use schema::Term;
 use query::TermQuery;
 use schema::IndexRecordOption;
 use query::Occur;
+use super::MinimumShouldMatch;
 
 /// The boolean query combines a set of queries
 ///
 /// The documents matched by the boolean query are
 /// those which
 /// * match all of the sub queries associated with the
-/// `Must` occurence
+/// `Must` or `Filter` occurence
 /// * match none of the sub queries associated with the
 /// `MustNot` occurence.
 /// * match at least one of the subqueries that is not
 /// a `MustNot` occurence.
+///
+/// The score of a document is computed from its `Must` and
+/// `Should` subqueries. `Filter` subqueries only restrict the
+/// documents matched, and are never scored.
+///
+/// A minimum number of `Should` subqueries to match can be
+/// required via `.with_minimum_should_match(...)`.
 #[derive(Debug)]
 pub struct BooleanQuery {
     subqueries: Vec<(Occur, Box<Query>)>,
+    minimum_should_match: MinimumShouldMatch,
 }
 
 impl From<Vec<(Occur, Box<Query>)>> for BooleanQuery {
     fn from(subqueries: Vec<(Occur, Box<Query>)>) -> BooleanQuery {
-        BooleanQuery { subqueries }
+        BooleanQuery {
+            subqueries,
+            minimum_should_match: MinimumShouldMatch::default(),
+        }
     }
 }
 
@@ -34,14 +46,42 @@ impl Query for BooleanQuery {
         let sub_weights = self.subqueries
             .iter()
             .map(|&(ref occur, ref subquery)| {
-                Ok((*occur, subquery.weight(searcher, scoring_enabled)?))
+                let sub_scoring_enabled = scoring_enabled && *occur != Occur::Filter;
+                Ok((*occur, subquery.weight(searcher, sub_scoring_enabled)?))
             })
             .collect::<Result<_>>()?;
-        Ok(box BooleanWeight::new(sub_weights, scoring_enabled))
+        let num_should_clauses = self.subqueries
+            .iter()
+            .filter(|&&(occur, _)| occur == Occur::Should)
+            .count();
+        let minimum_should_match = self.minimum_should_match.resolve(num_should_clauses);
+        Ok(box BooleanWeight::new(
+            sub_weights,
+            scoring_enabled,
+            minimum_should_match,
+        ))
     }
 }
 
 impl BooleanQuery {
+    /// Requires the documents to match at least a given number
+    /// of the `Should` subqueries, e.g. 3 of them, or 60% of them.
+    ///
+    /// If there are fewer `Should` subqueries than required,
+    /// no document matches.
+    pub fn with_minimum_should_match(
+        mut self,
+        minimum_should_match: MinimumShouldMatch,
+    ) -> BooleanQuery {
+        self.minimum_should_match = minimum_should_match;
+        self
+    }
+
+    /// Returns the minimum number of `Should` subqueries to match.
+    pub fn minimum_should_match(&self) -> MinimumShouldMatch {
+        self.minimum_should_match
+    }
+
     /// Helper method to create a boolean query matching a given list of terms.
     /// The resulting query is a disjunction of the terms.
     pub fn new_multiterms_query(terms: Vec<Term>) -> BooleanQuery {
@@ -8,10 +8,16 @@ use downcast::Downcast;
 use std::borrow::Borrow;
 use query::Exclude;
 use query::Occur;
+use query::ConstScorer;
 use query::RequiredOptionalScorer;
 use query::score_combiner::{DoNothingCombiner, ScoreCombiner, SumWithCoordsCombiner};
 use Result;
+use DocId;
+use docset::{DocSet, SkipResult};
+use query::Explanation;
+use query::explanation::does_not_match;
 use query::intersect_scorers;
+use query::min_should_match_scorer::MinShouldMatchScorer;
 use query::term_query::TermScorer;
 
 
@@ -44,16 +50,35 @@ where
 
 }
 
+fn does_match(weight: &Weight, reader: &SegmentReader, doc: DocId) -> Result<bool> {
+    let mut scorer = weight.scorer(reader)?;
+    Ok(scorer.skip_next(doc) == SkipResult::Reached)
+}
+
+/// Wraps the scorer of a `Filter` clause, so that
+/// it does not contribute to the score.
+fn filter_scorer(scorer: Box<Scorer>) -> Box<Scorer> {
+    let mut const_scorer = ConstScorer::new(scorer);
+    const_scorer.set_score(0f32);
+    box const_scorer
+}
+
 pub struct BooleanWeight {
     weights: Vec<(Occur, Box<Weight>)>,
     scoring_enabled: bool,
+    minimum_should_match: usize,
 }
 
 impl BooleanWeight {
-    pub fn new(weights: Vec<(Occur, Box<Weight>)>, scoring_enabled: bool) -> BooleanWeight {
+    pub fn new(
+        weights: Vec<(Occur, Box<Weight>)>,
+        scoring_enabled: bool,
+        minimum_should_match: usize,
+    ) -> BooleanWeight {
         BooleanWeight {
             weights,
             scoring_enabled,
+            minimum_should_match,
         }
     }
 
@@ -70,21 +95,44 @@ impl BooleanWeight {
                 .push(sub_scorer);
         }
 
-        let should_scorer_opt: Option<Box<Scorer>> = per_occur_scorers
+        let should_scorers = per_occur_scorers
             .remove(&Occur::Should)
-            .map(scorer_union::<TScoreCombiner>);
+            .unwrap_or_else(Vec::new);
+        if should_scorers.len() < self.minimum_should_match {
+            return Ok(box EmptyScorer);
+        }
+        let should_scorer_opt: Option<Box<Scorer>> = if should_scorers.is_empty() {
+            None
+        } else if self.minimum_should_match > 1 {
+            Some(box MinShouldMatchScorer::new(
+                should_scorers,
+                self.minimum_should_match,
+            ))
+        } else {
+            Some(scorer_union::<TScoreCombiner>(should_scorers))
+        };
 
         let exclude_scorer_opt: Option<Box<Scorer>> = per_occur_scorers
             .remove(&Occur::MustNot)
             .map(scorer_union::<TScoreCombiner>);
 
-        let must_scorer_opt: Option<Box<Scorer>> =
-            per_occur_scorers.remove(&Occur::Must)
-                .map(intersect_scorers);
+        let mut must_scorers: Vec<Box<Scorer>> = per_occur_scorers
+            .remove(&Occur::Must)
+            .unwrap_or_else(Vec::new);
+        if let Some(filter_scorers) = per_occur_scorers.remove(&Occur::Filter) {
+            must_scorers.extend(filter_scorers.into_iter().map(filter_scorer));
+        }
+        let must_scorer_opt: Option<Box<Scorer>> = if must_scorers.is_empty() {
+            None
+        } else {
+            Some(intersect_scorers(must_scorers))
+        };
 
         let positive_scorer: Box<Scorer> = match (should_scorer_opt, must_scorer_opt) {
             (Some(should_scorer), Some(must_scorer)) => {
-                if self.scoring_enabled {
+                if self.minimum_should_match > 0 {
+                    intersect_scorers(vec![must_scorer, should_scorer])
+                } else if self.scoring_enabled {
                     box RequiredOptionalScorer::<_, _, TScoreCombiner>::new(
                         must_scorer,
                         should_scorer,
@@ -112,12 +160,12 @@ impl Weight for BooleanWeight {
     fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
         if self.weights.is_empty() {
             Ok(box EmptyScorer)
-        } else if self.weights.len() == 1 {
+        } else if self.weights.len() == 1 && self.minimum_should_match == 0 {
             let &(occur, ref weight) = &self.weights[0];
-            if occur == Occur::MustNot {
-                Ok(box EmptyScorer)
-            } else {
-                weight.scorer(reader)
+            match occur {
+                Occur::MustNot => Ok(box EmptyScorer),
+                Occur::Filter => Ok(filter_scorer(weight.scorer(reader)?)),
+                Occur::Must | Occur::Should => weight.scorer(reader),
             }
         } else if self.scoring_enabled {
             self.complex_scorer::<SumWithCoordsCombiner>(reader)
@@ -125,4 +173,29 @@ impl Weight for BooleanWeight {
             self.complex_scorer::<DoNothingCombiner>(reader)
         }
     }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> Result<Explanation> {
+        let mut scorer = self.scorer(reader)?;
+        if scorer.skip_next(doc) != SkipResult::Reached {
+            return Err(does_not_match(doc));
+        }
+        let mut explanation = Explanation::new(
+            "BooleanQuery, sum of the scores of the matching clauses:",
+            scorer.score(),
+        );
+        for &(ref occur, ref subweight) in &self.weights {
+            match *occur {
+                Occur::Must | Occur::Should => {
+                    if does_match(subweight.as_ref(), reader, doc)? {
+                        explanation.add_detail(subweight.explain(reader, doc)?);
+                    }
+                }
+                Occur::Filter => {
+                    explanation.add_const("filter clause, not scored", 0f32);
+                }
+                Occur::MustNot => {}
+            }
+        }
+        Ok(explanation)
+    }
 }
@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// Minimum number of `Should` clauses of a `BooleanQuery`
+/// that a document needs to match.
+///
+/// By default, a `BooleanQuery` without any `Must` clause requires
+/// one of its `Should` clauses to match, while its `Should` clauses
+/// are optional if it has `Must` clauses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimumShouldMatch {
+    /// A fixed number of clauses.
+    Absolute(usize),
+    /// A percentage of the `Should` clauses, rounded down.
+    Percentage(u32),
+}
+
+impl MinimumShouldMatch {
+    /// Returns the minimum number of clauses to match,
+    /// given the number of `Should` clauses.
+    pub fn resolve(&self, num_should_clauses: usize) -> usize {
+        match *self {
+            MinimumShouldMatch::Absolute(num_clauses) => num_clauses,
+            MinimumShouldMatch::Percentage(percentage) => {
+                num_should_clauses * percentage as usize / 100
+            }
+        }
+    }
+}
+
+impl Default for MinimumShouldMatch {
+    fn default() -> MinimumShouldMatch {
+        MinimumShouldMatch::Absolute(0)
+    }
+}
+
+impl fmt::Display for MinimumShouldMatch {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MinimumShouldMatch::Absolute(num_clauses) => write!(formatter, "{}", num_clauses),
+            MinimumShouldMatch::Percentage(percentage) => write!(formatter, "{}%", percentage),
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod boolean_query;
 mod boolean_weight;
+mod minimum_should_match;
 
 pub use self::boolean_query::BooleanQuery;
+pub use self::minimum_should_match::MinimumShouldMatch;
 
 #[cfg(test)]
 mod tests {
@@ -12,7 +14,7 @@ mod tests {
     use query::TermQuery;
     use query::Intersection;
     use query::Scorer;
-    use collector::tests::TestCollector;
+    use collector::tests::{search_docs_and_scores, TestCollector};
     use Index;
     use downcast::Downcast;
     use schema::*;
@@ -20,6 +22,8 @@ mod tests {
     use query::RequiredOptionalScorer;
     use query::score_combiner::SumWithCoordsCombiner;
     use query::term_query::TermScorer;
+    use tests::assert_nearly_equals;
+    use DocAddress;
 
     fn aux_test_helper() -> (Index, Field) {
         let mut schema_builder = SchemaBuilder::default();
@@ -197,4 +201,165 @@ mod tests {
             assert_eq!(score_docs(&boolean_query), vec![0.977973, 0.84699446]);
         }
     }
+
+    #[test]
+    pub fn test_filter() {
+        let (index, text_field) = aux_test_helper();
+
+        let make_term_query = |text: &str| {
+            let term_query = TermQuery::new(
+                Term::from_field_text(text_field, text),
+                IndexRecordOption::WithFreqs,
+            );
+            let query: Box<Query> = box term_query;
+            query
+        };
+
+        let searcher = index.searcher();
+
+        {
+            let boolean_query = BooleanQuery::from(vec![(Occur::Filter, make_term_query("a"))]);
+            let (docs, scores) = search_docs_and_scores(&*searcher, &boolean_query);
+            assert_eq!(docs, vec![0, 1, 3]);
+            assert_eq!(scores, vec![0f32, 0f32, 0f32]);
+        }
+        {
+            let (_, b_scores) = search_docs_and_scores(&*searcher, &*make_term_query("b"));
+            let boolean_query = BooleanQuery::from(vec![
+                (Occur::Must, make_term_query("b")),
+                (Occur::Filter, make_term_query("a")),
+            ]);
+            let (docs, scores) = search_docs_and_scores(&*searcher, &boolean_query);
+            assert_eq!(docs, vec![0, 3]);
+            assert_nearly_equals(b_scores[0], scores[0]);
+            assert_nearly_equals(b_scores[2], scores[1]);
+        }
+        {
+            let boolean_query = BooleanQuery::from(vec![
+                (Occur::Should, make_term_query("c")),
+                (Occur::Filter, make_term_query("d")),
+            ]);
+            let (docs, scores) = search_docs_and_scores(&*searcher, &boolean_query);
+            assert_eq!(docs, vec![3, 4]);
+            assert!(scores[0] > 0f32);
+            assert_eq!(scores[1], 0f32);
+        }
+        {
+            let boolean_query = BooleanQuery::from(vec![
+                (Occur::Filter, make_term_query("a")),
+                (Occur::MustNot, make_term_query("d")),
+            ]);
+            assert_eq!(search_docs_and_scores(&*searcher, &boolean_query).0, vec![0, 1]);
+        }
+    }
+
+    #[test]
+    pub fn test_minimum_should_match() {
+        let (index, text_field) = aux_test_helper();
+
+        let make_term_query = |text: &str| {
+            let term_query = TermQuery::new(
+                Term::from_field_text(text_field, text),
+                IndexRecordOption::Basic,
+            );
+            let query: Box<Query> = box term_query;
+            query
+        };
+
+        let matching_docs = |occur_texts: &[(Occur, &str)], minimum_should_match| {
+            let subqueries = occur_texts
+                .iter()
+                .map(|&(occur, text)| (occur, make_term_query(text)))
+                .collect::<Vec<_>>();
+            let boolean_query =
+                BooleanQuery::from(subqueries).with_minimum_should_match(minimum_should_match);
+            let searcher = index.searcher();
+            let mut test_collector = TestCollector::default();
+            searcher.search(&boolean_query, &mut test_collector).unwrap();
+            test_collector.docs()
+        };
+
+        let should_abd = [
+            (Occur::Should, "a"),
+            (Occur::Should, "b"),
+            (Occur::Should, "d"),
+        ];
+        assert_eq!(
+            matching_docs(&should_abd, MinimumShouldMatch::Absolute(2)),
+            vec![0, 3]
+        );
+        assert_eq!(
+            matching_docs(&should_abd, MinimumShouldMatch::Absolute(3)),
+            vec![3]
+        );
+        assert_eq!(
+            matching_docs(&should_abd, MinimumShouldMatch::Absolute(4)),
+            Vec::<u32>::new()
+        );
+        assert_eq!(
+            matching_docs(&should_abd, MinimumShouldMatch::Percentage(50)),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            matching_docs(&should_abd, MinimumShouldMatch::Percentage(70)),
+            vec![0, 3]
+        );
+        let must_c_should_abd = [
+            (Occur::Must, "c"),
+            (Occur::Should, "a"),
+            (Occur::Should, "b"),
+            (Occur::Should, "d"),
+        ];
+        assert_eq!(
+            matching_docs(&must_c_should_abd, MinimumShouldMatch::Absolute(2)),
+            vec![0, 3]
+        );
+        assert_eq!(
+            matching_docs(&must_c_should_abd[..2], MinimumShouldMatch::Absolute(1)),
+            vec![0, 1, 3]
+        );
+        assert_eq!(
+            matching_docs(&must_c_should_abd[..2], MinimumShouldMatch::Absolute(0)),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    pub fn test_minimum_should_match_resolve() {
+        assert_eq!(MinimumShouldMatch::Absolute(3).resolve(5), 3);
+        assert_eq!(MinimumShouldMatch::Percentage(60).resolve(5), 3);
+        assert_eq!(MinimumShouldMatch::Percentage(50).resolve(5), 2);
+        assert_eq!(MinimumShouldMatch::Percentage(100).resolve(5), 5);
+    }
+
+    #[test]
+    pub fn test_boolean_explain() {
+        let (index, text_field) = aux_test_helper();
+        let searcher = index.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let explain = |query_str: &str, doc: u32| {
+            let query = query_parser.parse_query(query_str).unwrap();
+            query.explain(&*searcher, &DocAddress(0u32, doc))
+        };
+        {
+            let explanation = explain("+a b -d", 0).unwrap();
+            let details = explanation.details();
+            assert_eq!(details.len(), 2);
+            assert_nearly_equals(
+                explanation.value(),
+                details[0].value() + details[1].value(),
+            );
+            let explanation = explain("+a b -d", 1).unwrap();
+            assert_eq!(explanation.details().len(), 1);
+            assert!(explain("+a b -d", 2).is_err());
+            assert!(explain("+a b -d", 3).is_err());
+        }
+        {
+            let explanation = explain("+a #b", 0).unwrap();
+            let details = explanation.details();
+            assert_eq!(details.len(), 2);
+            assert_eq!(details[1].value(), 0f32);
+            assert_nearly_equals(explanation.value(), details[0].value());
+        }
+    }
 }
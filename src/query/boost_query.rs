@@ -0,0 +1,151 @@
+use query::{Explanation, Query, Scorer, Weight};
+use core::{SegmentReader, Searcher};
+use docset::{DocSet, SkipResult};
+use DocId;
+use Result;
+use Score;
+
+/// `BoostQuery` multiplies the scores of the documents
+/// matched by another query by a given factor.
+///
+/// Boosting makes it possible to give more importance to some clauses
+/// of a `BooleanQuery`, e.g. to a match in the title of a document
+/// over a match in its body.
+///
+/// The documents matched are the same as those of the inner query.
+#[derive(Debug)]
+pub struct BoostQuery {
+    query: Box<Query>,
+    boost: Score,
+}
+
+impl BoostQuery {
+    /// Creates a new `BoostQuery`, multiplying the
+    /// scores of `query` by `boost`.
+    pub fn new(query: Box<Query>, boost: Score) -> BoostQuery {
+        BoostQuery { query, boost }
+    }
+
+    /// Returns the inner query.
+    pub fn query(&self) -> &Query {
+        &*self.query
+    }
+
+    /// Returns the boost factor.
+    pub fn boost(&self) -> Score {
+        self.boost
+    }
+}
+
+impl Query for BoostQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        let weight = self.query.weight(searcher, scoring_enabled)?;
+        Ok(box BoostWeight {
+            weight,
+            boost: self.boost,
+        })
+    }
+}
+
+struct BoostWeight {
+    weight: Box<Weight>,
+    boost: Score,
+}
+
+impl Weight for BoostWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let scorer = self.weight.scorer(reader)?;
+        Ok(box BoostScorer {
+            scorer,
+            boost: self.boost,
+        })
+    }
+
+    fn count(&self, reader: &SegmentReader) -> Result<u32> {
+        self.weight.count(reader)
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> Result<Explanation> {
+        let sub_explanation = self.weight.explain(reader, doc)?;
+        let mut explanation = Explanation::new(
+            "BoostQuery, product of:",
+            sub_explanation.value() * self.boost,
+        );
+        explanation.add_const("boost", self.boost);
+        explanation.add_detail(sub_explanation);
+        Ok(explanation)
+    }
+}
+
+struct BoostScorer {
+    scorer: Box<Scorer>,
+    boost: Score,
+}
+
+impl DocSet for BoostScorer {
+    fn advance(&mut self) -> bool {
+        self.scorer.advance()
+    }
+
+    fn skip_next(&mut self, target: DocId) -> SkipResult {
+        self.scorer.skip_next(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.scorer.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.scorer.size_hint()
+    }
+}
+
+impl Scorer for BoostScorer {
+    fn score(&mut self) -> Score {
+        self.scorer.score() * self.boost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{SchemaBuilder, Term, TEXT};
+    use collector::tests::TestCollector;
+    use query::{Query, TermQuery};
+    use schema::IndexRecordOption;
+    use tests::assert_nearly_equals;
+    use super::BoostQuery;
+
+    #[test]
+    fn test_boost_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field => "a b"));
+            index_writer.add_document(doc!(text_field => "b"));
+            index_writer.add_document(doc!(text_field => "a"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let term_query = || {
+            let term = Term::from_field_text(text_field, "a");
+            TermQuery::new(term, IndexRecordOption::WithFreqs)
+        };
+        let mut test_collector = TestCollector::default();
+        term_query().search(&*searcher, &mut test_collector).unwrap();
+        let scores = test_collector.scores();
+
+        let boost_query = BoostQuery::new(box term_query(), 2f32);
+        let mut boosted_collector = TestCollector::default();
+        boost_query.search(&*searcher, &mut boosted_collector).unwrap();
+        let (docs, boosted_scores) = boosted_collector.docs_and_scores();
+        assert_eq!(docs, vec![0, 2]);
+        for (score, boosted_score) in scores.into_iter().zip(boosted_scores) {
+            assert_nearly_equals(score * 2f32, boosted_score);
+        }
+    }
+}
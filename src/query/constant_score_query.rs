@@ -0,0 +1,112 @@
+use query::{ConstScorer, Query, Scorer, Weight};
+use core::{SegmentReader, Searcher};
+use Result;
+use Score;
+
+/// `ConstantScoreQuery` matches the documents of another query,
+/// and gives all of them the same score.
+///
+/// This is useful when a query is only meant to filter documents,
+/// and its scores should not be computed, or not matter.
+/// The score defaults to one, and can be configured
+/// via `.with_score(...)`.
+#[derive(Debug)]
+pub struct ConstantScoreQuery {
+    query: Box<Query>,
+    score: Score,
+}
+
+impl ConstantScoreQuery {
+    /// Creates a new `ConstantScoreQuery`, matching
+    /// the documents of `query` with a score of one.
+    pub fn new(query: Box<Query>) -> ConstantScoreQuery {
+        ConstantScoreQuery { query, score: 1f32 }
+    }
+
+    /// Sets the score given to all of the matched documents.
+    pub fn with_score(mut self, score: Score) -> ConstantScoreQuery {
+        self.score = score;
+        self
+    }
+
+    /// Returns the inner query.
+    pub fn query(&self) -> &Query {
+        &*self.query
+    }
+
+    /// Returns the score given to the matched documents.
+    pub fn score(&self) -> Score {
+        self.score
+    }
+}
+
+impl Query for ConstantScoreQuery {
+    fn weight(&self, searcher: &Searcher, _scoring_enabled: bool) -> Result<Box<Weight>> {
+        // The scores of the inner query are never used.
+        let weight = self.query.weight(searcher, false)?;
+        Ok(box ConstantScoreWeight {
+            weight,
+            score: self.score,
+        })
+    }
+}
+
+struct ConstantScoreWeight {
+    weight: Box<Weight>,
+    score: Score,
+}
+
+impl Weight for ConstantScoreWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let mut const_scorer = ConstScorer::new(self.weight.scorer(reader)?);
+        const_scorer.set_score(self.score);
+        Ok(box const_scorer)
+    }
+
+    fn count(&self, reader: &SegmentReader) -> Result<u32> {
+        self.weight.count(reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{SchemaBuilder, Term, TEXT};
+    use collector::tests::TestCollector;
+    use collector::CountCollector;
+    use query::{Query, TermQuery};
+    use schema::IndexRecordOption;
+    use super::ConstantScoreQuery;
+
+    #[test]
+    fn test_constant_score_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field => "a b"));
+            index_writer.add_document(doc!(text_field => "b"));
+            index_writer.add_document(doc!(text_field => "a a a"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let term_query = || {
+            let term = Term::from_field_text(text_field, "a");
+            box TermQuery::new(term, IndexRecordOption::WithFreqs)
+        };
+        let query = ConstantScoreQuery::new(term_query()).with_score(3f32);
+        let mut test_collector = TestCollector::default();
+        query.search(&*searcher, &mut test_collector).unwrap();
+        let (docs, scores) = test_collector.docs_and_scores();
+        assert_eq!(docs, vec![0, 2]);
+        assert_eq!(scores, vec![3f32, 3f32]);
+        let mut count_collector = CountCollector::default();
+        ConstantScoreQuery::new(term_query())
+            .search(&*searcher, &mut count_collector)
+            .unwrap();
+        assert_eq!(count_collector.count(), 2);
+    }
+}
@@ -0,0 +1,182 @@
+use query::{EmptyScorer, Explanation, Query, Scorer, Union, Weight};
+use query::explanation::does_not_match;
+use query::score_combiner::{DisjunctionMaxCombiner, DoNothingCombiner};
+use core::{SegmentReader, Searcher};
+use docset::{DocSet, SkipResult};
+use DocId;
+use Result;
+use Score;
+
+/// `DisjunctionMaxQuery` matches the documents matching any of its
+/// subqueries, and scores them according to their best match.
+///
+/// The score of a document is the maximum of the scores of the
+/// subqueries it matches, plus the sum of the other scores multiplied by
+/// a tie breaker.
+///
+/// This is typically useful to search a term across several fields.
+/// A `BooleanQuery` sums the scores of the fields, so that a document
+/// repeating the term in its title and its body outranks a document
+/// with a much better match in its title alone.
+/// With a `DisjunctionMaxQuery`, the best field wins, and a small
+/// tie breaker (e.g. `0.1`) still favors the documents matching in
+/// several fields among those with similar best matches.
+#[derive(Debug)]
+pub struct DisjunctionMaxQuery {
+    disjuncts: Vec<Box<Query>>,
+    tie_breaker: Score,
+}
+
+impl DisjunctionMaxQuery {
+    /// Creates a new `DisjunctionMaxQuery`, given its subqueries
+    /// and its tie breaker.
+    ///
+    /// The tie breaker is expected to be between `0` and `1`.
+    pub fn new(disjuncts: Vec<Box<Query>>, tie_breaker: Score) -> DisjunctionMaxQuery {
+        DisjunctionMaxQuery {
+            disjuncts,
+            tie_breaker,
+        }
+    }
+
+    /// Returns the tie breaker.
+    pub fn tie_breaker(&self) -> Score {
+        self.tie_breaker
+    }
+}
+
+impl Query for DisjunctionMaxQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        let weights = self.disjuncts
+            .iter()
+            .map(|disjunct| disjunct.weight(searcher, scoring_enabled))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(box DisjunctionMaxWeight {
+            weights,
+            tie_breaker: self.tie_breaker,
+            scoring_enabled,
+        })
+    }
+}
+
+struct DisjunctionMaxWeight {
+    weights: Vec<Box<Weight>>,
+    tie_breaker: Score,
+    scoring_enabled: bool,
+}
+
+impl Weight for DisjunctionMaxWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let mut scorers = self.weights
+            .iter()
+            .map(|weight| weight.scorer(reader))
+            .collect::<Result<Vec<Box<Scorer>>>>()?;
+        if scorers.is_empty() {
+            Ok(box EmptyScorer)
+        } else if scorers.len() == 1 {
+            Ok(scorers.pop().unwrap())
+        } else if self.scoring_enabled {
+            let score_combiner = DisjunctionMaxCombiner::with_tie_breaker(self.tie_breaker);
+            Ok(box Union::with_score_combiner(scorers, score_combiner))
+        } else {
+            Ok(box Union::<_, DoNothingCombiner>::from(scorers))
+        }
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> Result<Explanation> {
+        let mut sub_explanations = Vec::new();
+        for weight in &self.weights {
+            let mut scorer = weight.scorer(reader)?;
+            if scorer.skip_next(doc) == SkipResult::Reached {
+                sub_explanations.push(weight.explain(reader, doc)?);
+            }
+        }
+        if sub_explanations.is_empty() {
+            return Err(does_not_match(doc));
+        }
+        let scores: Vec<Score> = sub_explanations
+            .iter()
+            .map(|explanation| explanation.value())
+            .collect();
+        let max = scores.iter().cloned().fold(scores[0], f32::max);
+        let sum: Score = scores.iter().sum();
+        let mut explanation = Explanation::new(
+            "DisjunctionMaxQuery, max of the scores of the matching subqueries, \
+             plus tie_breaker * the sum of the others:",
+            max + self.tie_breaker * (sum - max),
+        );
+        explanation.add_const("tie_breaker", self.tie_breaker);
+        for sub_explanation in sub_explanations {
+            explanation.add_detail(sub_explanation);
+        }
+        Ok(explanation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use {DocAddress, Index};
+    use schema::{IndexRecordOption, SchemaBuilder, Term, TEXT};
+    use collector::tests::search_docs_and_scores;
+    use query::{Query, TermQuery};
+    use tests::assert_nearly_equals;
+    use super::DisjunctionMaxQuery;
+
+    #[test]
+    fn test_disjunction_max_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let body_field = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(title_field => "rust", body_field => "rust"));
+            index_writer.add_document(doc!(title_field => "rust", body_field => "java"));
+            index_writer.add_document(doc!(title_field => "java", body_field => "go"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let title_query = || -> Box<Query> {
+            let term = Term::from_field_text(title_field, "rust");
+            box TermQuery::new(term, IndexRecordOption::WithFreqs)
+        };
+        let body_query = || -> Box<Query> {
+            let term = Term::from_field_text(body_field, "rust");
+            box TermQuery::new(term, IndexRecordOption::WithFreqs)
+        };
+        let term_queries = || vec![title_query(), body_query()];
+        let title_scores = search_docs_and_scores(&*searcher, &*title_query()).1;
+        let body_scores = search_docs_and_scores(&*searcher, &*body_query()).1;
+        let (max_score, min_score) = if title_scores[0] > body_scores[0] {
+            (title_scores[0], body_scores[0])
+        } else {
+            (body_scores[0], title_scores[0])
+        };
+
+        let query = DisjunctionMaxQuery::new(term_queries(), 0f32);
+        let (docs, scores) = search_docs_and_scores(&*searcher, &query);
+        assert_eq!(docs, vec![0, 1]);
+        assert_nearly_equals(max_score, scores[0]);
+        assert_nearly_equals(title_scores[1], scores[1]);
+
+        let query = DisjunctionMaxQuery::new(term_queries(), 0.5f32);
+        let (docs, scores) = search_docs_and_scores(&*searcher, &query);
+        assert_eq!(docs, vec![0, 1]);
+        assert_nearly_equals(max_score + 0.5f32 * min_score, scores[0]);
+        assert_nearly_equals(title_scores[1], scores[1]);
+
+        let explanation = query.explain(&*searcher, &DocAddress(0u32, 0u32)).unwrap();
+        assert_nearly_equals(explanation.value(), scores[0]);
+        let details = explanation.details();
+        assert_eq!(details.len(), 3);
+        assert_eq!(details[0].value(), 0.5f32);
+        assert_nearly_equals(details[1].value(), title_scores[0]);
+        assert_nearly_equals(details[2].value(), body_scores[0]);
+        let explanation = query.explain(&*searcher, &DocAddress(0u32, 1u32)).unwrap();
+        assert_nearly_equals(explanation.value(), scores[1]);
+        assert_eq!(explanation.details().len(), 2);
+        assert!(query.explain(&*searcher, &DocAddress(0u32, 2u32)).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use schema::{Cardinality, Field, IndexRecordOption};
+use query::{BitSetDocSet, ConstScorer, Query, Scorer, Weight};
+use termdict::{TermDictionary, TermStreamer};
+use core::{SegmentReader, Searcher};
+use common::BitSet;
+use fastfield::int_fast_field_cardinality;
+use ErrorKind;
+use Result;
+
+/// `ExistsQuery` matches all of the documents that have
+/// at least one value for a given field.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// The field must either be indexed, or be a multivalued int fast field.
+/// A single-valued fast field cannot be used, as the documents without any
+/// value get the default value of the field type and cannot be told apart.
+///
+/// # Implementation
+///
+/// For an indexed field, the query iterates over all of the terms
+/// of the field and appends the documents containing them into a `BitSet`.
+///
+/// Otherwise, the documents having at least one value are read
+/// from the fast field.
+#[derive(Debug)]
+pub struct ExistsQuery {
+    field: Field,
+}
+
+impl ExistsQuery {
+    /// Creates a new `ExistsQuery` for the given field.
+    pub fn new(field: Field) -> ExistsQuery {
+        ExistsQuery { field }
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+}
+
+impl Query for ExistsQuery {
+    fn weight(&self, _searcher: &Searcher, _scoring_enabled: bool) -> Result<Box<Weight>> {
+        Ok(box ExistsWeight { field: self.field })
+    }
+}
+
+struct ExistsWeight {
+    field: Field,
+}
+
+impl ExistsWeight {
+    fn indexed_docs(&self, reader: &SegmentReader) -> BitSet {
+        let mut doc_bitset = BitSet::with_max_value(reader.max_doc());
+        let inverted_index = reader.inverted_index(self.field);
+        let mut term_stream = inverted_index.terms().stream();
+        while term_stream.advance() {
+            let term_info = term_stream.value();
+            let mut block_segment_postings = inverted_index
+                .read_block_postings_from_terminfo(term_info, IndexRecordOption::Basic);
+            while block_segment_postings.advance() {
+                for &doc in block_segment_postings.docs() {
+                    doc_bitset.insert(doc);
+                }
+            }
+        }
+        doc_bitset
+    }
+
+    fn multivalued_docs(&self, reader: &SegmentReader) -> BitSet {
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+        let multi_fast_field_reader = reader.multi_fast_field_reader_unchecked::<u64>(self.field);
+        let mut vals = Vec::new();
+        for doc in 0..max_doc {
+            multi_fast_field_reader.get_vals(doc, &mut vals);
+            if !vals.is_empty() {
+                doc_bitset.insert(doc);
+            }
+        }
+        doc_bitset
+    }
+}
+
+impl Weight for ExistsWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let field_entry = reader.schema().get_field_entry(self.field);
+        let doc_bitset = if field_entry.is_indexed() {
+            self.indexed_docs(reader)
+        } else {
+            match int_fast_field_cardinality(field_entry.field_type()) {
+                Some(Cardinality::MultiValues) => self.multivalued_docs(reader),
+                _ => {
+                    bail!(ErrorKind::InvalidArgument(format!(
+                        "Field {:?} is neither indexed nor a multivalued fast field",
+                        field_entry.name()
+                    )));
+                }
+            }
+        };
+        Ok(box ConstScorer::new(BitSetDocSet::from(doc_bitset)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{Cardinality, IntOptions, SchemaBuilder, STRING, TEXT};
+    use query::{Query, QueryParser, QueryParserError};
+    use super::ExistsQuery;
+
+    #[test]
+    fn test_exists_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let email_field = schema_builder.add_text_field("email", STRING);
+        let tags_field = schema_builder.add_u64_field(
+            "tags",
+            IntOptions::default().set_fast(Cardinality::MultiValues),
+        );
+        let year_field = schema_builder.add_u64_field(
+            "year",
+            IntOptions::default().set_fast(Cardinality::SingleValue),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(title_field => "a", email_field => "a@b.c"));
+            index_writer.add_document(doc!(title_field => "a", tags_field => 3u64));
+            index_writer.add_document(doc!(
+                title_field => "b",
+                email_field => "d@e.f",
+                tags_field => 1u64,
+                tags_field => 2u64
+            ));
+            index_writer.add_document(doc!(title_field => "b", year_field => 2018u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |query: &Query| query.count(&*searcher).unwrap();
+        assert_eq!(count(&ExistsQuery::new(email_field)), 2);
+        assert_eq!(count(&ExistsQuery::new(tags_field)), 2);
+        assert!(ExistsQuery::new(year_field).count(&*searcher).is_err());
+
+        let query_parser = QueryParser::for_index(&index, vec![title_field]);
+        let parsed_count = |query_str: &str| count(&*query_parser.parse_query(query_str).unwrap());
+        assert_eq!(parsed_count("_exists_:email"), 2);
+        assert_eq!(parsed_count("a _exists_:email"), 3);
+        assert_eq!(parsed_count("+a +_exists_:email"), 1);
+        assert_eq!(parsed_count("+b -_exists_:email"), 1);
+        assert_eq!(parsed_count("_exists_:tags"), 2);
+        assert_matches!(
+            query_parser.parse_query("_exists_:year"),
+            Err(QueryParserError::FieldNotIndexed(_))
+        );
+    }
+}
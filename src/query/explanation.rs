@@ -0,0 +1,89 @@
+use DocId;
+use Error;
+use ErrorKind;
+use Score;
+use serde_json;
+
+/// Returns the error reported when explaining
+/// the score of a document that does not match.
+pub(crate) fn does_not_match(doc: DocId) -> Error {
+    ErrorKind::InvalidArgument(format!("Document #({}) does not match", doc)).into()
+}
+
+/// `Explanation` describes how the score of a document was computed.
+///
+/// It is a tree: each node carries a value and a description
+/// of what the value is, and the values of its children are
+/// the inputs that were combined to compute it.
+///
+/// See [`Query::explain`](./trait.Query.html#method.explain).
+#[derive(Clone, Debug, Serialize)]
+pub struct Explanation {
+    value: Score,
+    description: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    details: Vec<Explanation>,
+}
+
+impl Explanation {
+    /// Creates a new explanation without any detail.
+    pub fn new<T: ToString>(description: T, value: Score) -> Explanation {
+        Explanation {
+            value,
+            description: description.to_string(),
+            details: Vec::new(),
+        }
+    }
+
+    /// Returns the value associated to the explanation.
+    pub fn value(&self) -> Score {
+        self.value
+    }
+
+    /// Returns the description of the value.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the explanations of the inputs of the value.
+    pub fn details(&self) -> &[Explanation] {
+        &self.details
+    }
+
+    /// Adds the explanation of an input of the value.
+    pub fn add_detail(&mut self, detail: Explanation) {
+        self.details.push(detail);
+    }
+
+    /// Adds an input of the value which does not need
+    /// further explanation, such as a constant.
+    pub fn add_const<T: ToString>(&mut self, description: T, value: Score) {
+        self.details.push(Explanation::new(description, value));
+    }
+
+    /// Returns the explanation as a pretty printed JSON string.
+    pub fn to_pretty_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Serializing an explanation cannot fail")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde_json;
+    use super::Explanation;
+
+    #[test]
+    fn test_explanation_json() {
+        let mut explanation = Explanation::new("sum of:", 3f32);
+        explanation.add_const("a", 1f32);
+        explanation.add_const("b", 2f32);
+        assert_eq!(explanation.details().len(), 2);
+        assert_eq!(
+            serde_json::to_string(&explanation).unwrap(),
+            "{\"value\":3.0,\"description\":\"sum of:\",\"details\":[\
+             {\"value\":1.0,\"description\":\"a\"},\
+             {\"value\":2.0,\"description\":\"b\"}]}"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use schema::{Cardinality, Field};
+use query::{AllWeight, BitSetDocSet, ConstScorer, EmptyScorer, Query, Scorer, Weight};
+use query::range_query::map_bound;
+use core::{SegmentReader, Searcher};
+use common::BitSet;
+use fastfield::int_fast_field_cardinality;
+use byteorder::{BigEndian, ByteOrder};
+use std::cmp;
+use std::collections::Bound;
+use std::collections::range::RangeArgument;
+use DateTime;
+use ErrorKind;
+use Result;
+use common;
+
+/// Number of fast field values unpacked at once while scanning a segment.
+const SCAN_BLOCK_SIZE: usize = 1_024;
+
+/// `FastFieldRangeQuery` matches all of the documents whose value
+/// for a single-valued int fast field is within a defined range.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// Unlike [`RangeQuery`](./struct.RangeQuery.html), it does not require
+/// the field to be indexed, and its cost does not depend on the number of
+/// distinct values within the range. `RangeQuery` relies on it automatically
+/// for the fields that are fast but not indexed.
+///
+/// The documents without any value for the field are read as having
+/// the default value of the field type (`0`, or `0.0` for `f64` fields),
+/// so a range containing this value matches them as well.
+///
+/// # Implementation
+///
+/// The minimum and maximum values recorded in the header of the fast field
+/// make it possible to skip the segments where either none or all of the
+/// documents match. Otherwise, the values are unpacked block by block, and
+/// the matching documents are appended into a `BitSet`.
+#[derive(Debug)]
+pub struct FastFieldRangeQuery {
+    field: Field,
+    left_bound: Bound<u64>,
+    right_bound: Bound<u64>,
+}
+
+impl FastFieldRangeQuery {
+    /// Create a new `FastFieldRangeQuery` over a `u64` field.
+    pub fn new_u64<TRangeArgument: RangeArgument<u64>>(
+        field: Field,
+        range: TRangeArgument,
+    ) -> FastFieldRangeQuery {
+        let to_u64 = |val: &u64| *val;
+        FastFieldRangeQuery {
+            field,
+            left_bound: map_bound(range.start(), &to_u64),
+            right_bound: map_bound(range.end(), &to_u64),
+        }
+    }
+
+    /// Create a new `FastFieldRangeQuery` over a `i64` field.
+    pub fn new_i64<TRangeArgument: RangeArgument<i64>>(
+        field: Field,
+        range: TRangeArgument,
+    ) -> FastFieldRangeQuery {
+        let to_u64 = |val: &i64| common::i64_to_u64(*val);
+        FastFieldRangeQuery {
+            field,
+            left_bound: map_bound(range.start(), &to_u64),
+            right_bound: map_bound(range.end(), &to_u64),
+        }
+    }
+
+    /// Create a new `FastFieldRangeQuery` over a `f64` field.
+    pub fn new_f64<TRangeArgument: RangeArgument<f64>>(
+        field: Field,
+        range: TRangeArgument,
+    ) -> FastFieldRangeQuery {
+        let to_u64 = |val: &f64| common::f64_to_u64(*val);
+        FastFieldRangeQuery {
+            field,
+            left_bound: map_bound(range.start(), &to_u64),
+            right_bound: map_bound(range.end(), &to_u64),
+        }
+    }
+
+    /// Create a new `FastFieldRangeQuery` over a `Date` field.
+    pub fn new_date<TRangeArgument: RangeArgument<DateTime>>(
+        field: Field,
+        range: TRangeArgument,
+    ) -> FastFieldRangeQuery {
+        let to_u64 = |val: &DateTime| common::i64_to_u64(val.timestamp());
+        FastFieldRangeQuery {
+            field,
+            left_bound: map_bound(range.start(), &to_u64),
+            right_bound: map_bound(range.end(), &to_u64),
+        }
+    }
+}
+
+impl Query for FastFieldRangeQuery {
+    fn weight(&self, _searcher: &Searcher, _scoring_enabled: bool) -> Result<Box<Weight>> {
+        Ok(box FastFieldRangeWeight {
+            field: self.field,
+            left_bound: self.left_bound.clone(),
+            right_bound: self.right_bound.clone(),
+        })
+    }
+}
+
+/// Reads the `u64` representation of a term value of an int field.
+///
+/// Fails if the term value is not 8 bytes long, as is the case
+/// for a range over text.
+fn term_bound_to_u64(bound: &Bound<Vec<u8>>) -> Result<Bound<u64>> {
+    let to_u64 = |term_val: &[u8]| -> Result<u64> {
+        if term_val.len() != 8 {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Expected a term value of 8 bytes, got {} bytes",
+                term_val.len()
+            )));
+        }
+        Ok(BigEndian::read_u64(term_val))
+    };
+    Ok(match *bound {
+        Bound::Included(ref term_val) => Bound::Included(to_u64(&term_val[..])?),
+        Bound::Excluded(ref term_val) => Bound::Excluded(to_u64(&term_val[..])?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
+
+pub(crate) struct FastFieldRangeWeight {
+    field: Field,
+    left_bound: Bound<u64>,
+    right_bound: Bound<u64>,
+}
+
+impl FastFieldRangeWeight {
+    /// Creates a weight out of the bounds of a `RangeQuery`,
+    /// expressed as the value bytes of the terms of the field.
+    pub(crate) fn from_term_bounds(
+        field: Field,
+        left_bound: &Bound<Vec<u8>>,
+        right_bound: &Bound<Vec<u8>>,
+    ) -> Result<FastFieldRangeWeight> {
+        Ok(FastFieldRangeWeight {
+            field,
+            left_bound: term_bound_to_u64(left_bound)?,
+            right_bound: term_bound_to_u64(right_bound)?,
+        })
+    }
+
+    fn is_above_left_bound(&self, val: u64) -> bool {
+        match self.left_bound {
+            Bound::Included(left) => val >= left,
+            Bound::Excluded(left) => val > left,
+            Bound::Unbounded => true,
+        }
+    }
+
+    fn is_below_right_bound(&self, val: u64) -> bool {
+        match self.right_bound {
+            Bound::Included(right) => val <= right,
+            Bound::Excluded(right) => val < right,
+            Bound::Unbounded => true,
+        }
+    }
+
+    fn contains(&self, val: u64) -> bool {
+        self.is_above_left_bound(val) && self.is_below_right_bound(val)
+    }
+}
+
+impl Weight for FastFieldRangeWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let field_entry = reader.schema().get_field_entry(self.field);
+        if int_fast_field_cardinality(field_entry.field_type()) != Some(Cardinality::SingleValue) {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Field {:?} is not a single-valued fast field",
+                field_entry.name()
+            )));
+        }
+        // Values are read through their internal `u64` representation,
+        // which preserves the order of the values and matches the bounds.
+        let fast_field_reader = reader.fast_field_reader_with_idx::<u64>(self.field, 0)?;
+        let min_value = fast_field_reader.min_value();
+        let max_value = fast_field_reader.max_value();
+        if !self.is_above_left_bound(max_value) || !self.is_below_right_bound(min_value) {
+            return Ok(box EmptyScorer);
+        }
+        if self.contains(min_value) && self.contains(max_value) {
+            return AllWeight.scorer(reader);
+        }
+
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+        let mut vals = vec![0u64; SCAN_BLOCK_SIZE];
+        let mut start = 0u32;
+        while start < max_doc {
+            let block_len = cmp::min(SCAN_BLOCK_SIZE as u32, max_doc - start);
+            let block = &mut vals[..block_len as usize];
+            fast_field_reader.get_range(start, block);
+            for (i, &val) in block.iter().enumerate() {
+                if self.contains(val) {
+                    doc_bitset.insert(start + i as u32);
+                }
+            }
+            start += block_len;
+        }
+        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(box ConstScorer::new(doc_bitset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{Cardinality, IntOptions, SchemaBuilder, FAST, INT_INDEXED, STRING};
+    use query::{Query, QueryParser, RangeQuery};
+    use super::FastFieldRangeQuery;
+
+    #[test]
+    fn test_fast_field_range_query() {
+        let mut schema_builder = SchemaBuilder::new();
+        let indexed_field = schema_builder.add_i64_field("indexed", INT_INDEXED);
+        let fast_field = schema_builder.add_i64_field("fast", FAST);
+        let price_field = schema_builder.add_f64_field(
+            "price",
+            IntOptions::default().set_fast(Cardinality::SingleValue),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for val in -1_500i64..1_500i64 {
+                index_writer.add_document(doc!(
+                    indexed_field => val,
+                    fast_field => val,
+                    price_field => val as f64 / 4f64
+                ));
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |query: &Query| query.count(&*searcher).unwrap();
+
+        assert_eq!(count(&FastFieldRangeQuery::new_i64(fast_field, -10..20)), 30);
+        assert_eq!(count(&FastFieldRangeQuery::new_i64(fast_field, 1_000..)), 500);
+        assert_eq!(count(&FastFieldRangeQuery::new_i64(fast_field, ..)), 3_000);
+        assert_eq!(count(&FastFieldRangeQuery::new_i64(fast_field, -5_000..5_000)), 3_000);
+        assert_eq!(count(&FastFieldRangeQuery::new_i64(fast_field, 2_000..)), 0);
+        assert_eq!(count(&FastFieldRangeQuery::new_f64(price_field, -1f64..1f64)), 8);
+
+        // `RangeQuery` picks the fast field when the field is not indexed.
+        for &(start, end) in &[(-1_500, 0), (-3, 3), (1_024, 1_499), (0, 0)] {
+            assert_eq!(
+                count(&RangeQuery::new_i64(fast_field, start..end)),
+                count(&RangeQuery::new_i64(indexed_field, start..end))
+            );
+        }
+        assert_eq!(count(&RangeQuery::new_f64(price_field, 0f64..)), 1_500);
+
+        let query_parser = QueryParser::for_index(&index, vec![]);
+        let query = query_parser.parse_query("fast:[-10 TO 20}").unwrap();
+        assert_eq!(count(&*query), 30);
+    }
+
+    #[test]
+    fn test_fast_field_range_query_missing_values() {
+        let mut schema_builder = SchemaBuilder::new();
+        let fast_field = schema_builder.add_i64_field("fast", FAST);
+        let text_field = schema_builder.add_text_field("text", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(fast_field => -3i64));
+            index_writer.add_document(doc!(fast_field => 5i64));
+            index_writer.add_document(doc!(text_field => "no value"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |query: &Query| query.count(&*searcher).unwrap();
+        assert_eq!(count(&FastFieldRangeQuery::new_i64(fast_field, -1..1)), 1);
+        assert_eq!(count(&FastFieldRangeQuery::new_i64(fast_field, 1..10)), 1);
+        assert_eq!(count(&FastFieldRangeQuery::new_i64(fast_field, -5..10)), 3);
+        assert_eq!(count(&RangeQuery::new_i64(fast_field, 0..1)), 1);
+        let query_parser = QueryParser::for_index(&index, vec![]);
+        let query = query_parser.parse_query("fast:[-1 TO 1]").unwrap();
+        assert_eq!(count(&*query), 1);
+    }
+
+    #[test]
+    fn test_fast_field_range_query_invalid_term_bounds() {
+        let mut schema_builder = SchemaBuilder::new();
+        let fast_field = schema_builder.add_u64_field("fast", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(fast_field => 1u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        assert!(RangeQuery::new_str(fast_field, "a".."c")
+            .count(&*searcher)
+            .is_err());
+    }
+
+    #[test]
+    fn test_fast_field_range_query_multivalued() {
+        let mut schema_builder = SchemaBuilder::new();
+        let field = schema_builder.add_u64_field(
+            "multi",
+            IntOptions::default().set_fast(Cardinality::MultiValues),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(field => 1u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        assert!(FastFieldRangeQuery::new_u64(field, 0..2)
+            .count(&*searcher)
+            .is_err());
+    }
+}
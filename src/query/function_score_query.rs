@@ -0,0 +1,669 @@
+use std::fmt;
+use std::sync::Arc;
+use query::{Explanation, Query, Scorer, Weight};
+use query::explanation::does_not_match;
+use core::{SegmentReader, Searcher};
+use docset::{DocSet, SkipResult};
+use fastfield::{int_fast_field_cardinality, FastFieldReader};
+use schema::{Cardinality, Field, FieldType};
+use common;
+use DocId;
+use ErrorKind;
+use Result;
+use Score;
+
+/// Defines how the value of the score functions of a `FunctionScoreQuery`
+/// is combined with the score of its inner query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreMode {
+    /// The score is multiplied by the value of the functions.
+    Multiply,
+    /// The value of the functions is added to the score.
+    Sum,
+    /// The score is replaced by the value of the functions.
+    Replace,
+}
+
+impl Default for ScoreMode {
+    fn default() -> ScoreMode {
+        ScoreMode::Multiply
+    }
+}
+
+/// Modifier applied to the value of a field by a field value factor.
+///
+/// The `Ln1p`, `Log1p` and `Sqrt` modifiers clamp negative values to `0`,
+/// and a modifier yields `0` whenever its result would not be a finite
+/// number, e.g. for the `Reciprocal` of `0`.
+///
+/// See [`ScoreFunction`](./struct.ScoreFunction.html#method.field_value_factor).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldValueModifier {
+    /// The value is used as is.
+    None,
+    /// `ln(1 + value)`
+    Ln1p,
+    /// `log10(1 + value)`
+    Log1p,
+    /// `sqrt(value)`
+    Sqrt,
+    /// `value * value`
+    Square,
+    /// `1 / value`
+    Reciprocal,
+}
+
+impl FieldValueModifier {
+    fn apply(self, value: f64) -> f64 {
+        let modified_value = match self {
+            FieldValueModifier::None => value,
+            FieldValueModifier::Ln1p => value.max(0f64).ln_1p(),
+            FieldValueModifier::Log1p => (1f64 + value.max(0f64)).log10(),
+            FieldValueModifier::Sqrt => value.max(0f64).sqrt(),
+            FieldValueModifier::Square => value * value,
+            FieldValueModifier::Reciprocal => 1f64 / value,
+        };
+        if modified_value.is_finite() {
+            modified_value
+        } else {
+            0f64
+        }
+    }
+}
+
+/// Shape of the decay of a score, as the value of a field
+/// moves away from an origin.
+///
+/// See [`ScoreFunction::decay`](./struct.ScoreFunction.html#method.decay).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayFunction {
+    /// Gaussian decay, slow around the origin.
+    Gauss,
+    /// Exponential decay.
+    Exp,
+    /// Linear decay, reaching `0` at a distance of `scale / (1 - decay)`.
+    Linear,
+}
+
+impl DecayFunction {
+    /// Computes the decay at a given distance from the origin.
+    /// The decay is equal to `decay` at a distance of `scale`.
+    fn compute(self, distance: f64, scale: f64, decay: f64) -> f64 {
+        match self {
+            DecayFunction::Gauss => (decay.ln() * distance * distance / (scale * scale)).exp(),
+            DecayFunction::Exp => (decay.ln() * distance / scale).exp(),
+            DecayFunction::Linear => {
+                let zero_distance = scale / (1f64 - decay);
+                ((zero_distance - distance) / zero_distance).max(0f64)
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+enum ScoreFunctionKind {
+    FieldValueFactor {
+        factor: f64,
+        modifier: FieldValueModifier,
+    },
+    Decay {
+        decay_function: DecayFunction,
+        origin: f64,
+        scale: f64,
+        offset: f64,
+        decay: f64,
+    },
+    Custom(Arc<Fn(f64) -> Score + Send + Sync>),
+}
+
+/// A function of the value of a fast field, used by a
+/// [`FunctionScoreQuery`](./struct.FunctionScoreQuery.html) to adjust scores.
+///
+/// The field must be a single-valued `u64`, `i64`, `f64`, `bool`
+/// or date fast field. Dates are expressed as their timestamp in seconds,
+/// and booleans as `0` or `1`.
+///
+/// The documents without any value for the field are scored
+/// as if their value was the default value `0`.
+#[derive(Clone)]
+pub struct ScoreFunction {
+    field: Field,
+    kind: ScoreFunctionKind,
+}
+
+impl ScoreFunction {
+    /// Computes `factor * modifier(value)`.
+    ///
+    /// e.g. `ScoreFunction::field_value_factor(popularity, 1.0, FieldValueModifier::Ln1p)`
+    /// combined with the `Multiply` score mode ranks documents by
+    /// `score * ln(1 + popularity)`.
+    pub fn field_value_factor(
+        field: Field,
+        factor: f64,
+        modifier: FieldValueModifier,
+    ) -> ScoreFunction {
+        ScoreFunction {
+            field,
+            kind: ScoreFunctionKind::FieldValueFactor { factor, modifier },
+        }
+    }
+
+    /// Computes a decay, as the value moves away from `origin`.
+    ///
+    /// The function is equal to `1` within `offset` of the origin, and
+    /// to `decay` at a distance of `offset + scale` of the origin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not positive, or if `decay` is not within `]0, 1[`.
+    pub fn decay(
+        field: Field,
+        decay_function: DecayFunction,
+        origin: f64,
+        scale: f64,
+        offset: f64,
+        decay: f64,
+    ) -> ScoreFunction {
+        assert!(scale > 0f64, "The scale of a decay must be positive.");
+        assert!(
+            decay > 0f64 && decay < 1f64,
+            "The decay must be strictly between 0 and 1."
+        );
+        ScoreFunction {
+            field,
+            kind: ScoreFunctionKind::Decay {
+                decay_function,
+                origin,
+                scale,
+                offset,
+                decay,
+            },
+        }
+    }
+
+    /// Applies a user-provided closure to the value of the field.
+    pub fn custom<F>(field: Field, function: F) -> ScoreFunction
+    where
+        F: Fn(f64) -> Score + Send + Sync + 'static,
+    {
+        ScoreFunction {
+            field,
+            kind: ScoreFunctionKind::Custom(Arc::new(function)),
+        }
+    }
+
+    /// Returns the field the function reads its values from.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    fn compute(&self, value: f64) -> f64 {
+        match self.kind {
+            ScoreFunctionKind::FieldValueFactor { factor, modifier } => {
+                factor * modifier.apply(value)
+            }
+            ScoreFunctionKind::Decay {
+                decay_function,
+                origin,
+                scale,
+                offset,
+                decay,
+            } => {
+                let distance = ((value - origin).abs() - offset).max(0f64);
+                decay_function.compute(distance, scale, decay)
+            }
+            ScoreFunctionKind::Custom(ref function) => function(value) as f64,
+        }
+    }
+}
+
+impl fmt::Debug for ScoreFunction {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ScoreFunctionKind::FieldValueFactor { factor, modifier } => write!(
+                formatter,
+                "FieldValueFactor({:?}, factor={}, modifier={:?})",
+                self.field, factor, modifier
+            ),
+            ScoreFunctionKind::Decay {
+                decay_function,
+                origin,
+                scale,
+                offset,
+                decay,
+            } => write!(
+                formatter,
+                "{:?}({:?}, origin={}, scale={}, offset={}, decay={})",
+                decay_function, self.field, origin, scale, offset, decay
+            ),
+            ScoreFunctionKind::Custom(_) => write!(formatter, "Custom({:?})", self.field),
+        }
+    }
+}
+
+/// `FunctionScoreQuery` adjusts the scores of the documents matched
+/// by another query with functions of the values of fast fields.
+///
+/// It makes it possible to combine relevance with other signals,
+/// e.g. to boost popular products, or to favor recent documents.
+///
+/// The values of the functions are multiplied together, and then
+/// combined with the score of the inner query according to the
+/// [`ScoreMode`](./enum.ScoreMode.html) (`Multiply` by default).
+/// If their product is not a finite number, for instance because a
+/// custom function returned `NaN`, it is replaced by `0`.
+///
+/// The documents matched are the same as those of the inner query.
+#[derive(Debug)]
+pub struct FunctionScoreQuery {
+    query: Box<Query>,
+    functions: Vec<ScoreFunction>,
+    score_mode: ScoreMode,
+}
+
+impl FunctionScoreQuery {
+    /// Creates a new `FunctionScoreQuery` over `query`, without any function.
+    pub fn new(query: Box<Query>) -> FunctionScoreQuery {
+        FunctionScoreQuery {
+            query,
+            functions: Vec::new(),
+            score_mode: ScoreMode::default(),
+        }
+    }
+
+    /// Adds a score function.
+    pub fn with_function(mut self, function: ScoreFunction) -> FunctionScoreQuery {
+        self.functions.push(function);
+        self
+    }
+
+    /// Sets how the value of the functions is combined with the score.
+    pub fn with_score_mode(mut self, score_mode: ScoreMode) -> FunctionScoreQuery {
+        self.score_mode = score_mode;
+        self
+    }
+
+    /// Returns the inner query.
+    pub fn query(&self) -> &Query {
+        &*self.query
+    }
+
+    /// Returns the score functions.
+    pub fn functions(&self) -> &[ScoreFunction] {
+        &self.functions
+    }
+
+    /// Returns the score mode.
+    pub fn score_mode(&self) -> ScoreMode {
+        self.score_mode
+    }
+}
+
+impl Query for FunctionScoreQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        let weight = self.query.weight(searcher, scoring_enabled)?;
+        Ok(box FunctionScoreWeight {
+            weight,
+            functions: self.functions.clone(),
+            score_mode: self.score_mode,
+        })
+    }
+}
+
+fn u64_as_f64(val: u64) -> f64 {
+    val as f64
+}
+
+fn i64_as_f64(val: u64) -> f64 {
+    common::u64_to_i64(val) as f64
+}
+
+/// Returns the conversion of the internal `u64` values of a
+/// numeric single-valued fast field to `f64`.
+fn fast_value_to_f64(field_type: &FieldType) -> Option<fn(u64) -> f64> {
+    if int_fast_field_cardinality(field_type) != Some(Cardinality::SingleValue) {
+        return None;
+    }
+    let to_f64: fn(u64) -> f64 = match *field_type {
+        FieldType::U64(_) | FieldType::Bool(_) => u64_as_f64,
+        FieldType::I64(_) | FieldType::Date(_) => i64_as_f64,
+        FieldType::F64(_) => common::u64_to_f64,
+        _ => return None,
+    };
+    Some(to_f64)
+}
+
+struct SegmentScoreFunction {
+    function: ScoreFunction,
+    fast_field_reader: FastFieldReader<u64>,
+    to_f64: fn(u64) -> f64,
+}
+
+impl SegmentScoreFunction {
+    fn compute(&self, doc: DocId) -> f64 {
+        let value = (self.to_f64)(self.fast_field_reader.get(doc));
+        self.function.compute(value)
+    }
+}
+
+struct FunctionScoreWeight {
+    weight: Box<Weight>,
+    functions: Vec<ScoreFunction>,
+    score_mode: ScoreMode,
+}
+
+impl FunctionScoreWeight {
+    fn specialized_scorer(&self, reader: &SegmentReader) -> Result<FunctionScoreScorer> {
+        let mut functions = Vec::with_capacity(self.functions.len());
+        for function in &self.functions {
+            let field_entry = reader.schema().get_field_entry(function.field);
+            let to_f64 = fast_value_to_f64(field_entry.field_type()).ok_or_else(|| {
+                ErrorKind::InvalidArgument(format!(
+                    "Field {:?} is not a numeric single-valued fast field",
+                    field_entry.name()
+                ))
+            })?;
+            let fast_field_reader = reader.fast_field_reader_with_idx::<u64>(function.field, 0)?;
+            functions.push(SegmentScoreFunction {
+                function: function.clone(),
+                fast_field_reader,
+                to_f64,
+            });
+        }
+        Ok(FunctionScoreScorer {
+            scorer: self.weight.scorer(reader)?,
+            functions,
+            score_mode: self.score_mode,
+        })
+    }
+}
+
+impl Weight for FunctionScoreWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        Ok(box self.specialized_scorer(reader)?)
+    }
+
+    fn count(&self, reader: &SegmentReader) -> Result<u32> {
+        self.weight.count(reader)
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> Result<Explanation> {
+        let mut scorer = self.specialized_scorer(reader)?;
+        if scorer.skip_next(doc) != SkipResult::Reached {
+            return Err(does_not_match(doc));
+        }
+        let mut functions_explanation =
+            Explanation::new("product of the score functions:", scorer.functions_value());
+        for segment_function in &scorer.functions {
+            functions_explanation.add_const(
+                format!("{:?}", segment_function.function),
+                segment_function.compute(doc) as Score,
+            );
+        }
+        let mut explanation = Explanation::new(
+            format!("FunctionScoreQuery, score mode {:?}:", self.score_mode),
+            scorer.score(),
+        );
+        if self.score_mode != ScoreMode::Replace {
+            explanation.add_detail(self.weight.explain(reader, doc)?);
+        }
+        explanation.add_detail(functions_explanation);
+        Ok(explanation)
+    }
+}
+
+struct FunctionScoreScorer {
+    scorer: Box<Scorer>,
+    functions: Vec<SegmentScoreFunction>,
+    score_mode: ScoreMode,
+}
+
+impl FunctionScoreScorer {
+    fn functions_value(&self) -> Score {
+        let doc = self.scorer.doc();
+        let functions_value = self.functions
+            .iter()
+            .map(|function| function.compute(doc))
+            .product::<f64>() as Score;
+        if functions_value.is_finite() {
+            functions_value
+        } else {
+            0f32
+        }
+    }
+}
+
+impl DocSet for FunctionScoreScorer {
+    fn advance(&mut self) -> bool {
+        self.scorer.advance()
+    }
+
+    fn skip_next(&mut self, target: DocId) -> SkipResult {
+        self.scorer.skip_next(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.scorer.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.scorer.size_hint()
+    }
+}
+
+impl Scorer for FunctionScoreScorer {
+    fn score(&mut self) -> Score {
+        let functions_value = self.functions_value();
+        match self.score_mode {
+            ScoreMode::Multiply => self.scorer.score() * functions_value,
+            ScoreMode::Sum => self.scorer.score() + functions_value,
+            ScoreMode::Replace => functions_value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use DocAddress;
+    use Score;
+    use Term;
+    use schema::{IndexRecordOption, SchemaBuilder, FAST, TEXT};
+    use collector::tests::{search_docs_and_scores, TestCollector};
+    use query::{Query, TermQuery};
+    use tests::assert_nearly_equals;
+    use super::*;
+
+    #[test]
+    fn test_function_score_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let popularity_field = schema_builder.add_u64_field("popularity", FAST);
+        let timestamp_field = schema_builder.add_i64_field("timestamp", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(
+                text_field => "a",
+                popularity_field => 0u64,
+                timestamp_field => 0i64
+            ));
+            index_writer.add_document(doc!(
+                text_field => "a a",
+                popularity_field => 9u64,
+                timestamp_field => 100i64
+            ));
+            index_writer.add_document(doc!(
+                text_field => "a b",
+                popularity_field => 99u64,
+                timestamp_field => 200i64
+            ));
+            index_writer.add_document(doc!(
+                text_field => "b",
+                popularity_field => 3u64,
+                timestamp_field => 300i64
+            ));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let term_query = || -> Box<Query> {
+            box TermQuery::new(
+                Term::from_field_text(text_field, "a"),
+                IndexRecordOption::WithFreqs,
+            )
+        };
+        let (docs, scores) = search_docs_and_scores(&*searcher, &*term_query());
+        assert_eq!(docs, vec![0, 1, 2]);
+        {
+            let query = FunctionScoreQuery::new(term_query()).with_function(
+                ScoreFunction::field_value_factor(popularity_field, 1f64, FieldValueModifier::Ln1p),
+            );
+            let (function_docs, function_scores) = search_docs_and_scores(&*searcher, &query);
+            assert_eq!(function_docs, docs);
+            assert_eq!(function_scores[0], 0f32);
+            assert_nearly_equals(function_scores[1], scores[1] * 10f32.ln());
+            assert_nearly_equals(function_scores[2], scores[2] * 100f32.ln());
+            assert_eq!(query.count(&*searcher).unwrap(), 3);
+
+            let explanation = query.explain(&*searcher, &DocAddress(0, 2)).unwrap();
+            assert_nearly_equals(explanation.value(), function_scores[2]);
+            assert_eq!(explanation.details().len(), 2);
+        }
+        {
+            let query = FunctionScoreQuery::new(term_query())
+                .with_function(ScoreFunction::custom(popularity_field, |popularity| {
+                    popularity as Score
+                }))
+                .with_score_mode(ScoreMode::Replace);
+            let (_, function_scores) = search_docs_and_scores(&*searcher, &query);
+            assert_eq!(function_scores, vec![0f32, 9f32, 99f32]);
+        }
+        {
+            let query = FunctionScoreQuery::new(term_query())
+                .with_function(ScoreFunction::decay(
+                    timestamp_field,
+                    DecayFunction::Gauss,
+                    200f64,
+                    100f64,
+                    0f64,
+                    0.5f64,
+                ))
+                .with_score_mode(ScoreMode::Replace);
+            let (_, function_scores) = search_docs_and_scores(&*searcher, &query);
+            assert_nearly_equals(function_scores[0], 0.0625f32);
+            assert_nearly_equals(function_scores[1], 0.5f32);
+            assert_nearly_equals(function_scores[2], 1f32);
+        }
+        {
+            let query = FunctionScoreQuery::new(term_query())
+                .with_function(ScoreFunction::custom(popularity_field, |_| 1f32))
+                .with_score_mode(ScoreMode::Sum);
+            let (_, function_scores) = search_docs_and_scores(&*searcher, &query);
+            for (function_score, score) in function_scores.into_iter().zip(scores) {
+                assert_nearly_equals(function_score, score + 1f32);
+            }
+        }
+        {
+            let query = FunctionScoreQuery::new(term_query()).with_function(
+                ScoreFunction::field_value_factor(text_field, 1f64, FieldValueModifier::None),
+            );
+            assert!(query.search(&*searcher, &mut TestCollector::default()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_field_value_modifiers() {
+        assert_eq!(FieldValueModifier::None.apply(-2f64), -2f64);
+        assert_eq!(FieldValueModifier::Ln1p.apply(-2f64), 0f64);
+        assert_eq!(FieldValueModifier::Log1p.apply(-2f64), 0f64);
+        assert_eq!(FieldValueModifier::Log1p.apply(9f64), 1f64);
+        assert_eq!(FieldValueModifier::Sqrt.apply(-4f64), 0f64);
+        assert_eq!(FieldValueModifier::Sqrt.apply(4f64), 2f64);
+        assert_eq!(FieldValueModifier::Square.apply(-3f64), 9f64);
+        assert_eq!(FieldValueModifier::Reciprocal.apply(0f64), 0f64);
+        assert_eq!(FieldValueModifier::Reciprocal.apply(-4f64), -0.25f64);
+        assert_eq!(FieldValueModifier::Square.apply(::std::f64::MAX), 0f64);
+    }
+
+    #[test]
+    fn test_function_score_missing_value() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let rating_field = schema_builder.add_i64_field("rating", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field => "a", rating_field => 4i64));
+            index_writer.add_document(doc!(text_field => "a"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let term_query: Box<Query> = box TermQuery::new(
+            Term::from_field_text(text_field, "a"),
+            IndexRecordOption::Basic,
+        );
+        let query = FunctionScoreQuery::new(term_query)
+            .with_function(ScoreFunction::field_value_factor(
+                rating_field,
+                1f64,
+                FieldValueModifier::Reciprocal,
+            ))
+            .with_score_mode(ScoreMode::Replace);
+        let (docs, scores) = search_docs_and_scores(&*searcher, &query);
+        assert_eq!(docs, vec![0, 1]);
+        assert_eq!(scores, vec![0.25f32, 0f32]);
+    }
+
+    #[test]
+    fn test_function_score_custom_not_finite() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let rating_field = schema_builder.add_i64_field("rating", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field => "a", rating_field => 4i64));
+            index_writer.add_document(doc!(text_field => "a", rating_field => -1i64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let term_query: Box<Query> = box TermQuery::new(
+            Term::from_field_text(text_field, "a"),
+            IndexRecordOption::Basic,
+        );
+        let query = FunctionScoreQuery::new(term_query)
+            .with_function(ScoreFunction::custom(rating_field, |value| {
+                value.sqrt() as Score
+            }))
+            .with_score_mode(ScoreMode::Replace);
+        let (docs, scores) = search_docs_and_scores(&*searcher, &query);
+        assert_eq!(docs, vec![0, 1]);
+        assert_eq!(scores, vec![2f32, 0f32]);
+    }
+
+    #[test]
+    fn test_decay_functions() {
+        for &decay_function in &[DecayFunction::Gauss, DecayFunction::Exp, DecayFunction::Linear] {
+            assert_nearly_equals(decay_function.compute(0f64, 10f64, 0.5f64) as f32, 1f32);
+            assert_nearly_equals(decay_function.compute(10f64, 10f64, 0.5f64) as f32, 0.5f32);
+        }
+        assert_nearly_equals(DecayFunction::Gauss.compute(20f64, 10f64, 0.5f64) as f32, 0.0625f32);
+        assert_nearly_equals(DecayFunction::Exp.compute(20f64, 10f64, 0.5f64) as f32, 0.25f32);
+        assert_eq!(DecayFunction::Linear.compute(20f64, 10f64, 0.5f64), 0f64);
+        assert_eq!(DecayFunction::Linear.compute(30f64, 10f64, 0.5f64), 0f64);
+    }
+
+    #[test]
+    fn test_decay_offset() {
+        let mut schema_builder = SchemaBuilder::default();
+        let field = schema_builder.add_u64_field("field", FAST);
+        let function = ScoreFunction::decay(field, DecayFunction::Exp, 100f64, 10f64, 5f64, 0.5f64);
+        assert_nearly_equals(function.compute(95f64) as f32, 1f32);
+        assert_nearly_equals(function.compute(115f64) as f32, 0.5f32);
+        assert_nearly_equals(function.compute(85f64) as f32, 0.5f32);
+    }
+}
@@ -0,0 +1,190 @@
+use schema::Term;
+use query::{BooleanQuery, Query, Weight};
+use query::term_expansion::expand_terms;
+use core::Searcher;
+use error::ErrorKind;
+use Result;
+use std::str;
+use super::levenshtein_automaton::LevenshteinAutomaton;
+
+/// Maximum edit distance supported by the `FuzzyTermQuery`.
+const MAX_DISTANCE: u8 = 2;
+
+/// `FuzzyTermQuery` matches all of the documents containing a term
+/// within a given Levenshtein distance of the query term.
+///
+/// The distance is expressed in number of edits (insertion, deletion
+/// or substitution of a character), and must be at most 2.
+/// Optionally, the transposition of two adjacent characters can be
+/// counted as a single edit, and the first characters of the term
+/// can be required to match exactly.
+///
+/// The documents are scored as for a disjunction of all of the
+/// matching terms, using BM25.
+///
+/// # Implementation
+///
+/// A Levenshtein automaton is built for the query term, and intersected
+/// with the term dictionary of each segment to enumerate the
+/// matching terms.
+///
+/// # Example
+///
+/// ```rust
+///
+/// # #[macro_use]
+/// # extern crate tantivy;
+/// # use tantivy::Index;
+/// # use tantivy::schema::{SchemaBuilder, Term, TEXT};
+/// # use tantivy::collector::CountCollector;
+/// # use tantivy::query::{FuzzyTermQuery, Query};
+/// # use tantivy::Result;
+/// #
+/// # fn run() -> Result<()> {
+/// #     let mut schema_builder = SchemaBuilder::new();
+/// #     let title_field = schema_builder.add_text_field("title", TEXT);
+/// #     let schema = schema_builder.build();
+/// #
+/// #     let index = Index::create_in_ram(schema);
+/// #     {
+/// #         let mut index_writer = index.writer_with_num_threads(1, 6_000_000).unwrap();
+/// #         index_writer.add_document(doc!(title_field => "The Name of the Wind"));
+/// #         index_writer.add_document(doc!(title_field => "The Diary of Muadib"));
+/// #         index_writer.add_document(doc!(title_field => "A Dairy Cow"));
+/// #         index_writer.commit().unwrap();
+/// #     }
+/// #   index.load_searchers()?;
+/// let searcher = index.searcher();
+///
+/// let term = Term::from_field_text(title_field, "diary");
+/// let query = FuzzyTermQuery::new(term, 1, true);
+///
+/// let mut count_collector = CountCollector::default();
+/// query.search(&*searcher, &mut count_collector)?;
+///
+/// #     assert_eq!(count_collector.count(), 2);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #   run().unwrap()
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FuzzyTermQuery {
+    term: Term,
+    distance: u8,
+    transpositions: bool,
+    prefix_length: usize,
+}
+
+impl FuzzyTermQuery {
+    /// Creates a new `FuzzyTermQuery`, matching the terms within
+    /// `distance` edits of `term`.
+    ///
+    /// If `transpositions` is true, swapping two adjacent characters
+    /// counts as a single edit.
+    pub fn new(term: Term, distance: u8, transpositions: bool) -> FuzzyTermQuery {
+        FuzzyTermQuery {
+            term,
+            distance,
+            transpositions,
+            prefix_length: 0,
+        }
+    }
+
+    /// Requires the first `prefix_length` characters of
+    /// the matching terms to be the same as those of the query term.
+    ///
+    /// A short prefix drastically reduces the number of terms
+    /// that need to be visited.
+    pub fn with_prefix_length(mut self, prefix_length: usize) -> FuzzyTermQuery {
+        self.prefix_length = prefix_length;
+        self
+    }
+
+    /// Returns the term of the query.
+    pub fn term(&self) -> &Term {
+        &self.term
+    }
+
+    /// Returns the maximum number of edits.
+    pub fn distance(&self) -> u8 {
+        self.distance
+    }
+}
+
+impl Query for FuzzyTermQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        if self.distance > MAX_DISTANCE {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "The distance of a fuzzy term query must be at most {}, got {}.",
+                MAX_DISTANCE, self.distance
+            )));
+        }
+        let text = str::from_utf8(self.term.value_bytes()).map_err(|_| {
+            ErrorKind::InvalidArgument(format!("{:?} is not a text term.", self.term))
+        })?;
+        let automaton = LevenshteinAutomaton::new(
+            text,
+            u32::from(self.distance),
+            self.transpositions,
+            self.prefix_length,
+        );
+        let terms = expand_terms(searcher, self.term.field(), &automaton);
+        BooleanQuery::new_multiterms_query(terms).weight(searcher, scoring_enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{SchemaBuilder, Term, TEXT};
+    use collector::{CountCollector, TopCollector};
+    use query::Query;
+    use super::FuzzyTermQuery;
+
+    #[test]
+    fn test_fuzzy_term_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(title_field => "japan"));
+            index_writer.add_document(doc!(title_field => "japon"));
+            index_writer.add_document(doc!(title_field => "jpaan"));
+            index_writer.add_document(doc!(title_field => "korea"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |query: &Query| {
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        let term = Term::from_field_text(title_field, "japan");
+        assert_eq!(count(&FuzzyTermQuery::new(term.clone(), 0, false)), 1);
+        assert_eq!(count(&FuzzyTermQuery::new(term.clone(), 1, false)), 2);
+        assert_eq!(count(&FuzzyTermQuery::new(term.clone(), 1, true)), 3);
+        assert_eq!(count(&FuzzyTermQuery::new(term.clone(), 2, false)), 3);
+        assert_eq!(
+            count(&FuzzyTermQuery::new(term.clone(), 1, true).with_prefix_length(2)),
+            2
+        );
+        let mut top_collector = TopCollector::with_limit(4);
+        FuzzyTermQuery::new(term.clone(), 1, false)
+            .search(&*searcher, &mut top_collector)
+            .unwrap();
+        let score_docs = top_collector.score_docs();
+        assert_eq!(score_docs.len(), 2);
+        assert!(score_docs.iter().all(|&(score, _)| score > 0f32));
+        assert!(
+            FuzzyTermQuery::new(term, 3, false)
+                .search(&*searcher, &mut CountCollector::default())
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use fst::Automaton;
+use std::cmp::min;
+use std::str;
+
+/// State of the `LevenshteinAutomaton`.
+///
+/// `None` is the dead state, from which no match is possible.
+pub type LevenshteinState = Option<LevenshteinStateInner>;
+
+/// State of the `LevenshteinAutomaton`, after having accepted
+/// the bytes of some term prefix.
+pub struct LevenshteinStateInner {
+    /// Number of characters accepted so far.
+    num_chars: usize,
+    /// Bytes of the character being accepted, if its utf-8
+    /// encoding is not complete yet.
+    pending_bytes: Vec<u8>,
+    /// `row[i]` is the edit distance between the first `i` characters
+    /// of the query (prefix excluded) and the characters accepted so far.
+    row: Vec<u32>,
+    /// Row before the last character was accepted, used for transpositions.
+    prev_row: Vec<u32>,
+    /// Last character accepted.
+    prev_char: Option<char>,
+}
+
+/// Automaton matching the terms within a given
+/// Levenshtein distance of a query.
+///
+/// The automaton works on utf-8 bytes, but the distance is computed
+/// over unicode characters.
+/// If `transpositions` is true, the transposition of two adjacent
+/// characters counts as a single edit (this is the optimal string
+/// alignment distance).
+/// The first `prefix_length` characters of the query need to be matched
+/// exactly.
+///
+/// The automaton is computed lazily: its states are the rows of the
+/// dynamic programming matrix of the edit distance, which makes it possible
+/// to prune the branches of the term dictionary which cannot match.
+pub struct LevenshteinAutomaton {
+    prefix: Vec<char>,
+    query: Vec<char>,
+    max_distance: u32,
+    transpositions: bool,
+}
+
+impl LevenshteinAutomaton {
+    pub fn new(
+        text: &str,
+        max_distance: u32,
+        transpositions: bool,
+        prefix_length: usize,
+    ) -> LevenshteinAutomaton {
+        let chars: Vec<char> = text.chars().collect();
+        let prefix_length = min(prefix_length, chars.len());
+        LevenshteinAutomaton {
+            prefix: chars[..prefix_length].to_vec(),
+            query: chars[prefix_length..].to_vec(),
+            max_distance,
+            transpositions,
+        }
+    }
+
+    fn accept_char(&self, state: &LevenshteinStateInner, c: char) -> LevenshteinState {
+        if state.num_chars < self.prefix.len() {
+            if self.prefix[state.num_chars] != c {
+                return None;
+            }
+            return Some(LevenshteinStateInner {
+                num_chars: state.num_chars + 1,
+                pending_bytes: Vec::new(),
+                row: state.row.clone(),
+                prev_row: state.prev_row.clone(),
+                prev_char: None,
+            });
+        }
+        // Distances are capped to `max_distance + 1`, as
+        // any value above `max_distance` is equivalent.
+        let cap = self.max_distance + 1;
+        let row = &state.row;
+        let mut new_row = Vec::with_capacity(row.len());
+        new_row.push(min(row[0] + 1, cap));
+        for i in 1..row.len() {
+            let query_char = self.query[i - 1];
+            let substitution_cost = if query_char == c { 0 } else { 1 };
+            let mut distance = min(
+                row[i - 1] + substitution_cost,
+                min(row[i] + 1, new_row[i - 1] + 1),
+            );
+            if self.transpositions && i > 1 && c == self.query[i - 2]
+                && state.prev_char == Some(query_char)
+            {
+                distance = min(distance, state.prev_row[i - 2] + 1);
+            }
+            new_row.push(min(distance, cap));
+        }
+        Some(LevenshteinStateInner {
+            num_chars: state.num_chars + 1,
+            pending_bytes: Vec::new(),
+            row: new_row,
+            prev_row: row.clone(),
+            prev_char: Some(c),
+        })
+    }
+}
+
+/// Returns the length of the utf-8 encoding of a character,
+/// given its first byte.
+fn utf8_len(first_byte: u8) -> Option<usize> {
+    if first_byte < 0x80 {
+        Some(1)
+    } else if first_byte & 0xE0 == 0xC0 {
+        Some(2)
+    } else if first_byte & 0xF0 == 0xE0 {
+        Some(3)
+    } else if first_byte & 0xF8 == 0xF0 {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+impl Automaton for LevenshteinAutomaton {
+    type State = LevenshteinState;
+
+    fn start(&self) -> LevenshteinState {
+        let row: Vec<u32> = (0..self.query.len() as u32 + 1)
+            .map(|distance| min(distance, self.max_distance + 1))
+            .collect();
+        Some(LevenshteinStateInner {
+            num_chars: 0,
+            pending_bytes: Vec::new(),
+            prev_row: row.clone(),
+            row,
+            prev_char: None,
+        })
+    }
+
+    fn is_match(&self, state: &LevenshteinState) -> bool {
+        match *state {
+            Some(ref state) => {
+                state.pending_bytes.is_empty() && state.num_chars >= self.prefix.len()
+                    && state.row[self.query.len()] <= self.max_distance
+            }
+            None => false,
+        }
+    }
+
+    fn can_match(&self, state: &LevenshteinState) -> bool {
+        match *state {
+            Some(ref state) => {
+                state.num_chars < self.prefix.len()
+                    || state.row.iter().any(|&distance| distance <= self.max_distance)
+            }
+            None => false,
+        }
+    }
+
+    fn accept(&self, state: &LevenshteinState, byte: u8) -> LevenshteinState {
+        let state = state.as_ref()?;
+        let mut pending_bytes = state.pending_bytes.clone();
+        pending_bytes.push(byte);
+        let char_len = utf8_len(pending_bytes[0])?;
+        if pending_bytes.len() < char_len {
+            return Some(LevenshteinStateInner {
+                num_chars: state.num_chars,
+                pending_bytes,
+                row: state.row.clone(),
+                prev_row: state.prev_row.clone(),
+                prev_char: state.prev_char,
+            });
+        }
+        let c = str::from_utf8(&pending_bytes).ok()?.chars().next()?;
+        self.accept_char(state, c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn is_match(automaton: &LevenshteinAutomaton, text: &str) -> bool {
+        let mut state = automaton.start();
+        for &byte in text.as_bytes() {
+            state = automaton.accept(&state, byte);
+        }
+        automaton.is_match(&state)
+    }
+
+    #[test]
+    fn test_levenshtein_automaton() {
+        let automaton = LevenshteinAutomaton::new("apple", 1, false, 0);
+        assert!(is_match(&automaton, "apple"));
+        assert!(is_match(&automaton, "appple"));
+        assert!(is_match(&automaton, "aple"));
+        assert!(is_match(&automaton, "apble"));
+        assert!(!is_match(&automaton, "aplpe"));
+        assert!(!is_match(&automaton, "pple "));
+        assert!(!is_match(&automaton, "banana"));
+        let automaton = LevenshteinAutomaton::new("apple", 2, false, 0);
+        assert!(is_match(&automaton, "aplpe"));
+        assert!(is_match(&automaton, "pple "));
+    }
+
+    #[test]
+    fn test_levenshtein_automaton_transpositions() {
+        let automaton = LevenshteinAutomaton::new("apple", 1, true, 0);
+        assert!(is_match(&automaton, "aplpe"));
+        assert!(is_match(&automaton, "paple"));
+        assert!(!is_match(&automaton, "palpe"));
+    }
+
+    #[test]
+    fn test_levenshtein_automaton_prefix() {
+        let automaton = LevenshteinAutomaton::new("apple", 1, false, 2);
+        assert!(is_match(&automaton, "apXle"));
+        assert!(!is_match(&automaton, "Xpple"));
+        assert!(!is_match(&automaton, "pple"));
+        assert!(!is_match(&automaton, "ap"));
+    }
+
+    #[test]
+    fn test_levenshtein_automaton_unicode() {
+        let automaton = LevenshteinAutomaton::new("café", 1, false, 0);
+        assert!(is_match(&automaton, "cafe"));
+        assert!(is_match(&automaton, "cafés"));
+        assert!(is_match(&automaton, "caf"));
+        let automaton = LevenshteinAutomaton::new("cafe", 1, false, 0);
+        assert!(is_match(&automaton, "café"));
+        assert!(!is_match(&automaton, "cafés"));
+    }
+}
@@ -0,0 +1,4 @@
+mod levenshtein_automaton;
+mod fuzzy_term_query;
+
+pub use self::fuzzy_term_query::FuzzyTermQuery;
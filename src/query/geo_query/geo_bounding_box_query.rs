@@ -0,0 +1,68 @@
+use schema::{Field, GeoPoint};
+use query::{Query, Weight};
+use core::Searcher;
+use Result;
+use super::geo_weight::{compute_cells, GeoFilter, GeoWeight, QuantizedBox};
+
+/// `GeoBoundingBoxQuery` matches all documents that have at least
+/// one geo point within a rectangular area.
+///
+/// The area is defined by its top left and its bottom right corners.
+/// If the longitude of the top left corner is greater than the one of the
+/// bottom right corner, the area is assumed to cross the antimeridian.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// # Implementation
+///
+/// The area is decomposed into the cells of a quadtree, each of them
+/// matching a range of Morton codes.
+/// Only these ranges of the term dictionary are scanned, and
+/// only the terms in the cells at the border of the area are checked
+/// individually.
+///
+/// The field is required to be indexed.
+#[derive(Debug)]
+pub struct GeoBoundingBoxQuery {
+    field: Field,
+    top_left: GeoPoint,
+    bottom_right: GeoPoint,
+}
+
+impl GeoBoundingBoxQuery {
+    /// Creates a new `GeoBoundingBoxQuery`.
+    pub fn new(field: Field, top_left: GeoPoint, bottom_right: GeoPoint) -> GeoBoundingBoxQuery {
+        GeoBoundingBoxQuery {
+            field,
+            top_left,
+            bottom_right,
+        }
+    }
+
+    fn boxes(&self) -> Vec<QuantizedBox> {
+        let lat_min = self.bottom_right.lat.min(self.top_left.lat);
+        let lat_max = self.bottom_right.lat.max(self.top_left.lat);
+        if self.top_left.lon <= self.bottom_right.lon {
+            vec![
+                QuantizedBox::new(lat_min, lat_max, self.top_left.lon, self.bottom_right.lon),
+            ]
+        } else {
+            vec![
+                QuantizedBox::new(lat_min, lat_max, self.top_left.lon, 180f64),
+                QuantizedBox::new(lat_min, lat_max, -180f64, self.bottom_right.lon),
+            ]
+        }
+    }
+}
+
+impl Query for GeoBoundingBoxQuery {
+    fn weight(&self, _searcher: &Searcher, _scoring_enabled: bool) -> Result<Box<Weight>> {
+        let boxes = self.boxes();
+        let cells = compute_cells(&boxes, false);
+        Ok(box GeoWeight::new(
+            self.field,
+            cells,
+            GeoFilter::BoundingBoxes(boxes),
+        ))
+    }
+}
@@ -0,0 +1,85 @@
+use schema::{meters_to_degrees, Field, GeoPoint};
+use query::{Query, Weight};
+use core::Searcher;
+use Result;
+use super::geo_weight::{compute_cells, GeoFilter, GeoWeight, QuantizedBox};
+
+/// `GeoDistanceQuery` matches all documents that have at least
+/// one geo point within a given distance of an origin.
+///
+/// Distances are expressed in meters, and computed using
+/// the haversine formula. (see `GeoPoint::distance`)
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// # Implementation
+///
+/// The bounding box of the circle is decomposed into the cells
+/// of a quadtree, each of them matching a range of Morton codes.
+/// Only these ranges of the term dictionary are scanned, and
+/// the distance is checked for each of the terms encountered.
+///
+/// The field is required to be indexed.
+#[derive(Debug)]
+pub struct GeoDistanceQuery {
+    field: Field,
+    origin: GeoPoint,
+    distance: f64,
+}
+
+impl GeoDistanceQuery {
+    /// Creates a new `GeoDistanceQuery`, matching the documents
+    /// within `distance` meters of `origin`.
+    pub fn new(field: Field, origin: GeoPoint, distance: f64) -> GeoDistanceQuery {
+        GeoDistanceQuery {
+            field,
+            origin,
+            distance,
+        }
+    }
+
+    /// Computes the boxes covering the circle.
+    fn boxes(&self) -> Vec<QuantizedBox> {
+        let delta_lat = meters_to_degrees(self.distance);
+        let lat_min = self.origin.lat - delta_lat;
+        let lat_max = self.origin.lat + delta_lat;
+        if lat_min <= -90f64 || lat_max >= 90f64 {
+            // The circle contains a pole.
+            return vec![QuantizedBox::new(lat_min, lat_max, -180f64, 180f64)];
+        }
+        let sin_delta_lon = delta_lat.to_radians().sin() / self.origin.lat.to_radians().cos();
+        if sin_delta_lon >= 1f64 {
+            return vec![QuantizedBox::new(lat_min, lat_max, -180f64, 180f64)];
+        }
+        let delta_lon = sin_delta_lon.asin().to_degrees();
+        let lon_min = self.origin.lon - delta_lon;
+        let lon_max = self.origin.lon + delta_lon;
+        if lon_min < -180f64 {
+            vec![
+                QuantizedBox::new(lat_min, lat_max, lon_min + 360f64, 180f64),
+                QuantizedBox::new(lat_min, lat_max, -180f64, lon_max),
+            ]
+        } else if lon_max > 180f64 {
+            vec![
+                QuantizedBox::new(lat_min, lat_max, lon_min, 180f64),
+                QuantizedBox::new(lat_min, lat_max, -180f64, lon_max - 360f64),
+            ]
+        } else {
+            vec![QuantizedBox::new(lat_min, lat_max, lon_min, lon_max)]
+        }
+    }
+}
+
+impl Query for GeoDistanceQuery {
+    fn weight(&self, _searcher: &Searcher, _scoring_enabled: bool) -> Result<Box<Weight>> {
+        let cells = compute_cells(&self.boxes(), true);
+        Ok(box GeoWeight::new(
+            self.field,
+            cells,
+            GeoFilter::Distance {
+                origin: self.origin,
+                distance: self.distance,
+            },
+        ))
+    }
+}
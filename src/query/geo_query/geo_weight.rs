@@ -0,0 +1,263 @@
+use schema::{morton, quantize_lat, quantize_lon, unmorton, Field, GeoPoint, IndexRecordOption,
+             Term};
+use query::{BitSetDocSet, ConstScorer, Scorer, Weight};
+use termdict::{TermDictionary, TermStreamer, TermStreamerBuilder};
+use byteorder::{BigEndian, ByteOrder};
+use core::SegmentReader;
+use common::BitSet;
+use Result;
+
+/// Number of levels the quadtree is refined by, below the level
+/// of the cells that have the size of the searched area.
+///
+/// Each level roughly doubles the number of term ranges scanned,
+/// and halves the number of documents that need to be checked.
+const DETAIL_LEVELS: u32 = 6;
+
+/// Rectangular area, expressed with quantized coordinates.
+/// All bounds are inclusive.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct QuantizedBox {
+    lat_min: u32,
+    lat_max: u32,
+    lon_min: u32,
+    lon_max: u32,
+}
+
+impl QuantizedBox {
+    /// Creates a box given its bounds, in degrees.
+    ///
+    /// The box is not allowed to cross the antimeridian.
+    pub fn new(lat_min: f64, lat_max: f64, lon_min: f64, lon_max: f64) -> QuantizedBox {
+        QuantizedBox {
+            lat_min: quantize_lat(lat_min),
+            lat_max: quantize_lat(lat_max),
+            lon_min: quantize_lon(lon_min),
+            lon_max: quantize_lon(lon_max),
+        }
+    }
+
+    fn contains(&self, lat_q: u32, lon_q: u32) -> bool {
+        self.lat_min <= lat_q && lat_q <= self.lat_max && self.lon_min <= lon_q
+            && lon_q <= self.lon_max
+    }
+}
+
+/// Range of Morton codes, matching a cell of the quadtree.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CellRange {
+    start: u64,
+    end: u64,
+    /// If false, all of the points in the range are known to match.
+    needs_check: bool,
+}
+
+/// Decomposes a box into the cells of the quadtree, and appends
+/// the matching ranges of Morton codes to `cells`.
+///
+/// Cells that are fully contained by the box are emitted as is,
+/// while the cells at the border of the box are refined until
+/// `max_level` is reached.
+fn decompose(
+    bbox: &QuantizedBox,
+    level: u32,
+    lat_cell: u64,
+    lon_cell: u64,
+    max_level: u32,
+    cells: &mut Vec<CellRange>,
+) {
+    let shift = 32 - level;
+    let lat_min = lat_cell << shift;
+    let lat_max = lat_min + (1u64 << shift) - 1;
+    let lon_min = lon_cell << shift;
+    let lon_max = lon_min + (1u64 << shift) - 1;
+    if lat_max < u64::from(bbox.lat_min) || lat_min > u64::from(bbox.lat_max)
+        || lon_max < u64::from(bbox.lon_min) || lon_min > u64::from(bbox.lon_max)
+    {
+        return;
+    }
+    let contained = u64::from(bbox.lat_min) <= lat_min && lat_max <= u64::from(bbox.lat_max)
+        && u64::from(bbox.lon_min) <= lon_min && lon_max <= u64::from(bbox.lon_max);
+    if contained || level == max_level {
+        cells.push(CellRange {
+            start: morton(lat_min as u32, lon_min as u32),
+            end: morton(lat_max as u32, lon_max as u32),
+            needs_check: !contained,
+        });
+        return;
+    }
+    // Children are visited in the order of their Morton codes.
+    for &(lat_bit, lon_bit) in &[(0, 0), (0, 1), (1, 0), (1, 1)] {
+        decompose(
+            bbox,
+            level + 1,
+            lat_cell * 2 + lat_bit,
+            lon_cell * 2 + lon_bit,
+            max_level,
+            cells,
+        );
+    }
+}
+
+/// Computes the sorted ranges of Morton codes covering the given boxes.
+///
+/// If `check_all` is true, all of the ranges are marked as
+/// needing to be checked.
+pub(crate) fn compute_cells(boxes: &[QuantizedBox], check_all: bool) -> Vec<CellRange> {
+    let mut cells = Vec::new();
+    for bbox in boxes {
+        if bbox.lat_min > bbox.lat_max || bbox.lon_min > bbox.lon_max {
+            continue;
+        }
+        let lat_extent = u64::from(bbox.lat_max - bbox.lat_min);
+        let lon_extent = u64::from(bbox.lon_max - bbox.lon_min);
+        let extent = lat_extent.max(lon_extent) + 1;
+        // level at which cells have roughly the size of the box.
+        let box_level = extent.leading_zeros() - 31;
+        let max_level = (box_level + DETAIL_LEVELS).min(32);
+        decompose(bbox, 0, 0, 0, max_level, &mut cells);
+    }
+    if check_all {
+        for cell in &mut cells {
+            cell.needs_check = true;
+        }
+    }
+    cells.sort_by_key(|cell| cell.start);
+    // Merging contiguous ranges saves some lookups in the term dictionary.
+    let mut merged_cells: Vec<CellRange> = Vec::with_capacity(cells.len());
+    for cell in cells {
+        if let Some(last_cell) = merged_cells.last_mut() {
+            let is_contiguous = last_cell.end.wrapping_add(1) == cell.start;
+            if is_contiguous && last_cell.needs_check == cell.needs_check {
+                last_cell.end = cell.end;
+                continue;
+            }
+        }
+        merged_cells.push(cell);
+    }
+    merged_cells
+}
+
+/// Exact condition checked on the points located
+/// in the cells at the border of the searched area.
+#[derive(Clone, Debug)]
+pub(crate) enum GeoFilter {
+    /// The point must be within one of the boxes.
+    BoundingBoxes(Vec<QuantizedBox>),
+    /// The point must be within `distance` meters of `origin`.
+    Distance { origin: GeoPoint, distance: f64 },
+}
+
+impl GeoFilter {
+    fn accept(&self, morton_code: u64) -> bool {
+        match *self {
+            GeoFilter::BoundingBoxes(ref boxes) => {
+                let (lat_q, lon_q) = unmorton(morton_code);
+                boxes.iter().any(|bbox| bbox.contains(lat_q, lon_q))
+            }
+            GeoFilter::Distance { origin, distance } => {
+                origin.distance(&GeoPoint::from_morton(morton_code)) <= distance
+            }
+        }
+    }
+}
+
+/// Weight shared by the geo queries.
+///
+/// The documents are found by scanning the ranges of
+/// the term dictionary matching the cells covering the area searched for.
+pub(crate) struct GeoWeight {
+    field: Field,
+    cells: Vec<CellRange>,
+    filter: GeoFilter,
+}
+
+impl GeoWeight {
+    pub fn new(field: Field, cells: Vec<CellRange>, filter: GeoFilter) -> GeoWeight {
+        GeoWeight {
+            field,
+            cells,
+            filter,
+        }
+    }
+}
+
+impl Weight for GeoWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+
+        let inverted_index = reader.inverted_index(self.field);
+        let term_dict = inverted_index.terms();
+        for cell in &self.cells {
+            let start_term = Term::from_field_u64(self.field, cell.start);
+            let end_term = Term::from_field_u64(self.field, cell.end);
+            let mut term_range = term_dict
+                .range()
+                .ge(start_term.value_bytes())
+                .le(end_term.value_bytes())
+                .into_stream();
+            while term_range.advance() {
+                if cell.needs_check {
+                    let morton_code = BigEndian::read_u64(term_range.key());
+                    if !self.filter.accept(morton_code) {
+                        continue;
+                    }
+                }
+                let term_info = term_range.value();
+                let mut block_segment_postings = inverted_index
+                    .read_block_postings_from_terminfo(term_info, IndexRecordOption::Basic);
+                while block_segment_postings.advance() {
+                    for &doc in block_segment_postings.docs() {
+                        doc_bitset.insert(doc);
+                    }
+                }
+            }
+        }
+        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(box ConstScorer::new(doc_bitset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_compute_cells_covers_box() {
+        let bbox = QuantizedBox::new(48.8, 48.9, 2.2, 2.4);
+        let cells = compute_cells(&[bbox], false);
+        assert!(!cells.is_empty());
+        for window in cells.windows(2) {
+            assert!(window[0].end < window[1].start);
+        }
+        let is_covered = |point: GeoPoint| {
+            let code = point.to_morton();
+            cells
+                .iter()
+                .any(|cell| cell.start <= code && code <= cell.end)
+        };
+        assert!(is_covered(GeoPoint::new(48.8, 2.2)));
+        assert!(is_covered(GeoPoint::new(48.85, 2.3)));
+        assert!(is_covered(GeoPoint::new(48.9, 2.4)));
+        assert!(!is_covered(GeoPoint::new(10.0, 2.3)));
+        assert!(!is_covered(GeoPoint::new(48.85, -100.0)));
+    }
+
+    #[test]
+    fn test_compute_cells_whole_world() {
+        let bbox = QuantizedBox::new(-90.0, 90.0, -180.0, 180.0);
+        let cells = compute_cells(&[bbox], false);
+        assert_eq!(
+            cells,
+            vec![
+                CellRange {
+                    start: 0u64,
+                    end: u64::max_value(),
+                    needs_check: false,
+                },
+            ]
+        );
+    }
+}
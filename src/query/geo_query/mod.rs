@@ -0,0 +1,100 @@
+mod geo_weight;
+mod geo_bounding_box_query;
+mod geo_distance_query;
+
+pub use self::geo_bounding_box_query::GeoBoundingBoxQuery;
+pub use self::geo_distance_query::GeoDistanceQuery;
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{Field, GeoPoint, GeoPointOptions, SchemaBuilder};
+    use collector::CountCollector;
+    use query::{GeoBoundingBoxQuery, GeoDistanceQuery, Query};
+
+    fn count(index: &Index, query: &Query) -> usize {
+        let searcher = index.searcher();
+        let mut count_collector = CountCollector::default();
+        query.search(&*searcher, &mut count_collector).unwrap();
+        count_collector.count()
+    }
+
+    fn build_index() -> (Index, Field) {
+        let mut schema_builder = SchemaBuilder::default();
+        let location_field = schema_builder.add_geo_point_field(
+            "location",
+            GeoPointOptions::default().set_indexed().set_fast(),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            let cities = [
+                (48.8566, 2.3522),     // Paris
+                (48.8049, 2.1204),     // Versailles
+                (51.5074, -0.1278),    // London
+                (40.7128, -74.0060),   // New York
+                (-36.8485, 174.7633),  // Auckland
+                (-16.5000, -179.9000), // Fiji, east of the antimeridian
+            ];
+            for &(lat, lon) in &cities {
+                index_writer.add_document(doc!(location_field => GeoPoint::new(lat, lon)));
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        (index, location_field)
+    }
+
+    #[test]
+    fn test_geo_bounding_box_query() {
+        let (index, location_field) = build_index();
+        let europe = GeoBoundingBoxQuery::new(
+            location_field,
+            GeoPoint::new(60.0, -10.0),
+            GeoPoint::new(40.0, 20.0),
+        );
+        assert_eq!(count(&index, &europe), 3);
+        let ile_de_france = GeoBoundingBoxQuery::new(
+            location_field,
+            GeoPoint::new(49.0, 2.0),
+            GeoPoint::new(48.0, 3.0),
+        );
+        assert_eq!(count(&index, &ile_de_france), 2);
+        let pacific = GeoBoundingBoxQuery::new(
+            location_field,
+            GeoPoint::new(0.0, 170.0),
+            GeoPoint::new(-50.0, -170.0),
+        );
+        assert_eq!(count(&index, &pacific), 2);
+        let nowhere = GeoBoundingBoxQuery::new(
+            location_field,
+            GeoPoint::new(10.0, 10.0),
+            GeoPoint::new(0.0, 20.0),
+        );
+        assert_eq!(count(&index, &nowhere), 0);
+    }
+
+    #[test]
+    fn test_geo_distance_query() {
+        let (index, location_field) = build_index();
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        assert_eq!(
+            count(&index, &GeoDistanceQuery::new(location_field, paris, 10_000.0)),
+            1
+        );
+        assert_eq!(
+            count(&index, &GeoDistanceQuery::new(location_field, paris, 30_000.0)),
+            2
+        );
+        assert_eq!(
+            count(&index, &GeoDistanceQuery::new(location_field, paris, 400_000.0)),
+            3
+        );
+        let fiji_west = GeoPoint::new(-16.5, 179.9);
+        assert_eq!(
+            count(&index, &GeoDistanceQuery::new(location_field, fiji_west, 50_000.0)),
+            1
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use DocId;
+use Score;
+use docset::{DocSet, SkipResult};
+use query::{Scorer, Union};
+use query::score_combiner::SumWithCoordsCombiner;
+
+/// Matches the documents on which at least `minimum_should_match`
+/// of the given scorers are positioned.
+///
+/// This is used for the `Should` clauses of a `BooleanQuery`
+/// with a `minimum_should_match` parameter, e.g. to require at least
+/// 3 of the 5 terms of a query to match.
+///
+/// The scorers are merged in a `Union`, whose `SumWithCoordsCombiner`
+/// counts the number of scorers matching each document.
+pub struct MinShouldMatchScorer<TScorer: Scorer> {
+    union: Union<TScorer, SumWithCoordsCombiner>,
+    minimum_should_match: usize,
+}
+
+impl<TScorer: Scorer> MinShouldMatchScorer<TScorer> {
+    /// Creates a new `MinShouldMatchScorer`.
+    pub fn new(
+        scorers: Vec<TScorer>,
+        minimum_should_match: usize,
+    ) -> MinShouldMatchScorer<TScorer> {
+        MinShouldMatchScorer {
+            union: Union::from(scorers),
+            minimum_should_match,
+        }
+    }
+
+    fn is_match(&self) -> bool {
+        self.union.doc_score_combiner().num_fields() >= self.minimum_should_match
+    }
+}
+
+impl<TScorer: Scorer> DocSet for MinShouldMatchScorer<TScorer> {
+    fn advance(&mut self) -> bool {
+        while self.union.advance() {
+            if self.is_match() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn skip_next(&mut self, target: DocId) -> SkipResult {
+        match self.union.skip_next(target) {
+            SkipResult::End => SkipResult::End,
+            skip_result => {
+                if self.is_match() {
+                    skip_result
+                } else if self.advance() {
+                    SkipResult::OverStep
+                } else {
+                    SkipResult::End
+                }
+            }
+        }
+    }
+
+    fn doc(&self) -> DocId {
+        self.union.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.union.size_hint()
+    }
+}
+
+impl<TScorer: Scorer> Scorer for MinShouldMatchScorer<TScorer> {
+    fn score(&mut self) -> Score {
+        self.union.score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::MinShouldMatchScorer;
+    use docset::{DocSet, SkipResult};
+    use query::{ConstScorer, Scorer, VecDocSet};
+
+    fn scorers(docs: &[&[u32]]) -> Vec<ConstScorer<VecDocSet>> {
+        docs.iter()
+            .map(|docs| ConstScorer::new(VecDocSet::from(docs.to_vec())))
+            .collect()
+    }
+
+    #[test]
+    fn test_min_should_match_scorer() {
+        let docs: &[&[u32]] = &[&[1, 2, 3, 8], &[2, 3, 5, 8], &[3, 5, 8, 9]];
+        let mut scorer = MinShouldMatchScorer::new(scorers(docs), 2);
+        let mut matched = Vec::new();
+        while scorer.advance() {
+            matched.push((scorer.doc(), scorer.score()));
+        }
+        assert_eq!(matched, vec![(2, 2f32), (3, 3f32), (5, 2f32), (8, 3f32)]);
+        let mut scorer = MinShouldMatchScorer::new(scorers(docs), 3);
+        assert_eq!(scorer.skip_next(3), SkipResult::Reached);
+        assert_eq!(scorer.skip_next(4), SkipResult::OverStep);
+        assert_eq!(scorer.doc(), 8);
+        assert!(!scorer.advance());
+    }
+}
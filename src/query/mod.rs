@@ -13,11 +13,28 @@ mod phrase_query;
 mod all_query;
 mod bitset;
 mod range_query;
+mod fast_field_range_query;
+mod geo_query;
+mod fuzzy_query;
+mod regex_query;
+mod prefix_query;
+mod wildcard_query;
+mod exists_query;
+mod term_set_query;
+mod span_query;
+mod boost_query;
+mod constant_score_query;
+mod function_score_query;
+mod disjunction_max_query;
+mod automaton_weight;
+mod term_expansion;
 mod exclude;
 mod union;
 mod intersection;
 mod reqopt_scorer;
+mod min_should_match_scorer;
 mod bm25;
+mod explanation;
 
 #[cfg(test)]
 mod vec_docset;
@@ -33,9 +50,9 @@ pub use self::vec_docset::VecDocSet;
 pub use self::reqopt_scorer::RequiredOptionalScorer;
 pub use self::exclude::Exclude;
 pub use self::bitset::BitSetDocSet;
-pub use self::boolean_query::BooleanQuery;
+pub use self::boolean_query::{BooleanQuery, MinimumShouldMatch};
 pub use self::occur::Occur;
-pub use self::phrase_query::PhraseQuery;
+pub use self::phrase_query::{MultiPhraseQuery, PhraseQuery};
 pub use self::query_parser::QueryParserError;
 pub use self::query_parser::QueryParser;
 pub use self::query::Query;
@@ -43,7 +60,23 @@ pub use self::scorer::EmptyScorer;
 pub use self::scorer::Scorer;
 pub use self::term_query::TermQuery;
 pub use self::weight::Weight;
+pub use self::explanation::Explanation;
 pub use self::all_query::{AllQuery, AllScorer, AllWeight};
 pub use self::range_query::RangeQuery;
+pub use self::fast_field_range_query::FastFieldRangeQuery;
+pub use self::geo_query::{GeoBoundingBoxQuery, GeoDistanceQuery};
+pub use self::fuzzy_query::FuzzyTermQuery;
+pub use self::regex_query::RegexQuery;
+pub use self::prefix_query::PrefixQuery;
+pub use self::wildcard_query::WildcardQuery;
+pub use self::exists_query::ExistsQuery;
+pub use self::term_set_query::TermSetQuery;
+pub use self::boost_query::BoostQuery;
+pub use self::constant_score_query::ConstantScoreQuery;
+pub use self::function_score_query::{DecayFunction, FieldValueModifier, FunctionScoreQuery,
+                                     ScoreFunction, ScoreMode};
+pub use self::disjunction_max_query::DisjunctionMaxQuery;
+pub use self::span_query::{Span, SpanFirstQuery, SpanNearQuery, SpanNotQuery, SpanOrQuery,
+                           SpanQuery, SpanScorer, SpanTermQuery, SpanWeight, Spans};
 pub use self::scorer::ConstScorer;
 pub use self::intersection::intersect_scorers;
@@ -1,5 +1,8 @@
 /// Defines whether a term in a query must be present,
 /// should be present or must not be present.
+///
+/// A `Filter` term must be present as well, but does not
+/// contribute to the score of the documents.
 #[derive(Debug, Clone, Hash, Copy, Eq, PartialEq)]
 pub enum Occur {
     /// For a given document to be considered for scoring,
@@ -11,4 +14,7 @@ pub enum Occur {
     /// Document that contain the term are excluded from the
     /// search.
     MustNot,
+    /// Document without the term are excluded from the search,
+    /// but the term is not taken in account for scoring.
+    Filter,
 }
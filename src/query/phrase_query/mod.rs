@@ -1,10 +1,15 @@
 mod phrase_query;
 mod phrase_weight;
 mod phrase_scorer;
+mod multi_phrase_query;
+mod multi_phrase_weight;
+mod union_postings;
 
 pub use self::phrase_query::PhraseQuery;
 pub use self::phrase_weight::PhraseWeight;
 pub use self::phrase_scorer::PhraseScorer;
+pub use self::multi_phrase_query::MultiPhraseQuery;
+pub use self::multi_phrase_weight::MultiPhraseWeight;
 
 #[cfg(test)]
 mod tests {
@@ -91,6 +96,89 @@ mod tests {
 
     }
 
+    #[test]
+    pub fn test_phrase_query_slop() {
+        let index = create_index(&[
+            "quick fox",
+            "quick brown fox",
+            "fox quick",
+            "quick brown lazy fox",
+            "fox"
+        ]);
+        let schema = index.schema();
+        let text_field = schema.get_field("text").unwrap();
+        let searcher = index.searcher();
+        let test_query = |texts: Vec<&str>, slop: u32| {
+            let mut test_collector = TestCollector::default();
+            let terms: Vec<Term> = texts
+                .iter()
+                .map(|text| Term::from_field_text(text_field, text))
+                .collect();
+            let phrase_query = PhraseQuery::new(terms).with_slop(slop);
+            searcher
+                .search(&phrase_query, &mut test_collector)
+                .expect("search should succeed");
+            test_collector.docs_and_scores()
+        };
+        assert_eq!(test_query(vec!["quick", "fox"], 0).0, vec![0]);
+        assert_eq!(test_query(vec!["quick", "fox"], 1).0, vec![0, 1]);
+        assert_eq!(test_query(vec!["fox", "quick"], 1).0, vec![2]);
+        let (docs, scores) = test_query(vec!["quick", "fox"], 2);
+        assert_eq!(docs, vec![0, 1, 2, 3]);
+        assert!(scores[0] > scores[1]);
+        assert!(scores[1] > scores[3]);
+    }
+
+    #[test]
+    pub fn test_multi_phrase_query() {
+        let index = create_index(&[
+            "the quick brown fox",
+            "the fast brown fox",
+            "the slow brown fox",
+            "quick fast brown fox",
+            "the fast red fox"
+        ]);
+        let schema = index.schema();
+        let text_field = schema.get_field("text").unwrap();
+        let searcher = index.searcher();
+        let test_query = |texts: Vec<Vec<&str>>, slop: u32| {
+            let mut test_collector = TestCollector::default();
+            let terms: Vec<Vec<Term>> = texts
+                .iter()
+                .map(|position_texts| {
+                    position_texts
+                        .iter()
+                        .map(|text| Term::from_field_text(text_field, text))
+                        .collect()
+                })
+                .collect();
+            let multi_phrase_query = MultiPhraseQuery::new(terms).with_slop(slop);
+            searcher
+                .search(&multi_phrase_query, &mut test_collector)
+                .expect("search should succeed");
+            test_collector.docs_and_scores()
+        };
+        let (docs, scores) = test_query(vec![vec!["fast", "quick"], vec!["brown"], vec!["fox"]], 0);
+        assert_eq!(docs, vec![0, 1, 3]);
+        assert_nearly_equals(scores[0], scores[1]);
+        assert_eq!(test_query(vec![vec!["fast", "quick"], vec!["fox"]], 0).0, Vec::<u32>::new());
+        assert_eq!(test_query(vec![vec!["fast", "quick"], vec!["fox"]], 1).0, vec![0, 1, 3, 4]);
+        assert_eq!(test_query(vec![vec!["the"], vec!["fast", "nonexistent"]], 0).0, vec![1, 4]);
+        assert!(test_query(vec![vec!["the"], vec!["nonexistent"]], 0).0.is_empty());
+
+        // The idf of a position is the highest idf of its terms,
+        // here the one of `quick`, which is rarer than `fast`.
+        let (docs, scores) = test_query(vec![vec!["quick", "fast"], vec!["brown"]], 0);
+        assert_eq!(docs, vec![0, 1, 3]);
+        let phrase_query = PhraseQuery::new(vec![
+            Term::from_field_text(text_field, "quick"),
+            Term::from_field_text(text_field, "brown"),
+        ]);
+        let mut phrase_collector = TestCollector::default();
+        searcher.search(&phrase_query, &mut phrase_collector).unwrap();
+        assert_nearly_equals(scores[0], phrase_collector.scores()[0]);
+    }
+
     #[test] // motivated by #234
     pub fn test_phrase_query_docfreq_order() {
         let mut schema_builder = SchemaBuilder::default();
@@ -0,0 +1,77 @@
+use schema::Term;
+use query::Query;
+use core::searcher::Searcher;
+use super::MultiPhraseWeight;
+use query::Weight;
+use Result;
+use query::bm25::BM25Weight;
+
+/// `MultiPhraseQuery` matches a sequence of words, where
+/// several alternative terms may be accepted at each position.
+///
+/// For instance, the multi phrase query for `[["fast", "quick"], ["brown"], ["fox"]]`
+/// matches both **the quick brown fox** and **the fast brown fox**.
+/// This makes it possible to express the synonyms of a phrase term.
+///
+/// The documents are scored as for a `PhraseQuery`. The idf of a position
+/// accepting several terms is the highest idf of these terms.
+///
+/// Using a `MultiPhraseQuery` on a field requires positions
+/// to be indexed for this field.
+///
+#[derive(Debug)]
+pub struct MultiPhraseQuery {
+    phrase_terms: Vec<Vec<Term>>,
+    slop: u32,
+}
+
+impl MultiPhraseQuery {
+
+    /// Creates a new `MultiPhraseQuery` given, for each position of the phrase,
+    /// the list of the terms accepted at this position.
+    ///
+    /// There must be at least two positions, each of them with at least one term,
+    /// and all terms must belong to the same field.
+    pub fn new(terms: Vec<Vec<Term>>) -> MultiPhraseQuery {
+        assert!(terms.len() > 1, "A multi phrase query is required to have strictly more than one position.");
+        assert!(terms.iter().all(|position_terms| !position_terms.is_empty()), "All positions of a multi phrase query must have at least one term.");
+        let field = terms[0][0].field();
+        assert!(terms.iter().flat_map(|position_terms| position_terms.iter()).all(|term| term.field() == field), "All terms from a multi phrase query must belong to the same field");
+        MultiPhraseQuery {
+            phrase_terms: terms,
+            slop: 0u32
+        }
+    }
+
+    /// Sets the slop of the query, i.e. the number of
+    /// position moves allowed for its terms to match.
+    ///
+    /// See [`PhraseQuery`](./struct.PhraseQuery.html).
+    pub fn with_slop(mut self, slop: u32) -> MultiPhraseQuery {
+        self.slop = slop;
+        self
+    }
+}
+
+impl Query for MultiPhraseQuery {
+    /// Create the weight associated to a query.
+    ///
+    /// See [`Weight`](./trait.Weight.html).
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        let terms = self.phrase_terms.clone();
+        if scoring_enabled {
+            let bm25_weight = {
+                let positions: Vec<&[Term]> = terms.iter().map(|position_terms| &position_terms[..]).collect();
+                BM25Weight::for_positions(searcher, &positions)
+            };
+            Ok(box MultiPhraseWeight::new(
+                terms,
+                bm25_weight,
+                true,
+                self.slop
+            ))
+        } else {
+            Ok(box MultiPhraseWeight::new(terms, BM25Weight::null(), false, self.slop))
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use query::Weight;
+use query::Scorer;
+use schema::Term;
+use schema::IndexRecordOption;
+use core::SegmentReader;
+use super::PhraseScorer;
+use super::union_postings::UnionPostings;
+use query::EmptyScorer;
+use Result;
+use query::bm25::BM25Weight;
+
+pub struct MultiPhraseWeight {
+    phrase_terms: Vec<Vec<Term>>,
+    similarity_weight: BM25Weight,
+    score_needed: bool,
+    slop: u32,
+}
+
+impl MultiPhraseWeight {
+    /// Creates a new multi phrase weight.
+    pub fn new(phrase_terms: Vec<Vec<Term>>,
+               similarity_weight: BM25Weight,
+               score_needed: bool,
+               slop: u32) -> MultiPhraseWeight {
+        MultiPhraseWeight {
+            phrase_terms,
+            similarity_weight,
+            score_needed,
+            slop
+        }
+    }
+}
+
+impl Weight for MultiPhraseWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let similarity_weight = self.similarity_weight.clone();
+        let field = self.phrase_terms[0][0].field();
+        let fieldnorm_reader = reader.get_fieldnorms_reader(field);
+        let inverted_index = reader.inverted_index(field);
+        let mut position_postings_list = Vec::new();
+        for position_terms in &self.phrase_terms {
+            let mut term_postings_list = Vec::new();
+            for term in position_terms {
+                let postings_opt = if reader.has_deletes() {
+                    inverted_index.read_postings(term, IndexRecordOption::WithFreqsAndPositions)
+                } else {
+                    inverted_index.read_postings_no_deletes(term, IndexRecordOption::WithFreqsAndPositions)
+                };
+                if let Some(postings) = postings_opt {
+                    term_postings_list.push(postings);
+                }
+            }
+            if term_postings_list.is_empty() {
+                return Ok(box EmptyScorer);
+            }
+            position_postings_list.push(UnionPostings::new(term_postings_list));
+        }
+        Ok(box PhraseScorer::new(position_postings_list, similarity_weight, fieldnorm_reader, self.score_needed, self.slop))
+    }
+}
@@ -20,9 +20,22 @@ use query::bm25::BM25Weight;
 /// Using a `PhraseQuery` on a field requires positions
 /// to be indexed for this field.
 ///
+/// # Slop
+///
+/// By default, the terms of the phrase need to be adjacent and in order.
+/// With a slop of `n`, they may also be up to `n` moves away from
+/// their expected position: with a slop of 1, `"part time"` matches
+/// **part of time**, and with a slop of 2, it also matches
+/// **part of the time** and **time part**.
+///
+/// Sloppy matches are scored according to their sloppy frequency:
+/// each match contributes `1 / (1 + distance)` to the frequency of
+/// the phrase, so that closer matches get a higher score.
+///
 #[derive(Debug)]
 pub struct PhraseQuery {
     phrase_terms: Vec<Term>,
+    slop: u32,
 }
 
 impl PhraseQuery {
@@ -35,9 +48,22 @@ impl PhraseQuery {
         assert!(terms.len() > 1, "A phrase query is required to have strictly more than one term.");
         assert!(terms[1..].iter().all(|term| term.field() == terms[0].field()), "All terms from a phrase query must belong to the same field");
         PhraseQuery {
-            phrase_terms: terms
+            phrase_terms: terms,
+            slop: 0u32
         }
     }
+
+    /// Sets the slop of the phrase query, i.e. the number of
+    /// position moves allowed for its terms to match.
+    pub fn with_slop(mut self, slop: u32) -> PhraseQuery {
+        self.slop = slop;
+        self
+    }
+
+    /// Returns the slop of the phrase query.
+    pub fn slop(&self) -> u32 {
+        self.slop
+    }
 }
 
 impl Query for PhraseQuery {
@@ -51,10 +77,11 @@ impl Query for PhraseQuery {
             Ok(box PhraseWeight::new(
                 terms,
                 bm25_weight,
-                true
+                true,
+                self.slop
             ))
         } else {
-            Ok(box PhraseWeight::new(terms, BM25Weight::null(), false))
+            Ok(box PhraseWeight::new(terms, BM25Weight::null(), false, self.slop))
         }
 
     }
@@ -46,10 +46,14 @@ pub struct PhraseScorer<TPostings: Postings> {
     num_docsets: usize,
     left: Vec<u32>,
     right: Vec<u32>,
-    phrase_count: u32,
+    term_positions: Vec<Vec<u32>>,
+    offsets: Vec<u32>,
+    cursors: Vec<usize>,
+    phrase_freq: f32,
     fieldnorm_reader: FieldNormReader,
     similarity_weight: BM25Weight,
-    score_needed: bool
+    score_needed: bool,
+    slop: u32
 }
 
 
@@ -118,41 +122,144 @@ fn intersection(left: &mut [u32], right: &[u32]) -> usize {
     count
 }
 
+/// Returns true iff the terms pointed at by the cursors
+/// are all at a different position in the document.
+fn distinct_positions(term_positions: &[Vec<u32>], offsets: &[u32], cursors: &[usize]) -> bool {
+    let position = |term_ord: usize| term_positions[term_ord][cursors[term_ord]] - offsets[term_ord];
+    for i in 1..cursors.len() {
+        for j in 0..i {
+            if position(i) == position(j) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Computes the sloppy frequency of a phrase in a document.
+///
+/// `term_positions` contains the sorted positions of each of the terms
+/// of the phrase, shifted by the term offset, so that the terms of an
+/// exact match share the same position.
+/// The distance of a match is then the difference between its largest
+/// and its smallest positions. Each match whose distance is at most
+/// `slop` contributes `1 / (1 + distance)` to the frequency.
+///
+/// `cursors` is a buffer, used to avoid allocations.
+fn sloppy_phrase_freq(term_positions: &[Vec<u32>],
+                      offsets: &[u32],
+                      slop: u32,
+                      cursors: &mut Vec<usize>) -> f32 {
+    let num_terms = term_positions.len();
+    if term_positions.iter().any(|positions| positions.is_empty()) {
+        return 0f32;
+    }
+    cursors.clear();
+    cursors.resize(num_terms, 0);
+    let mut max_position = term_positions
+        .iter()
+        .map(|positions| positions[0])
+        .max()
+        .unwrap_or(0u32);
+    let mut freq = 0f32;
+    loop {
+        // Finds the term with the smallest position,
+        // as well as the second smallest position.
+        let mut min_term_ord = 0;
+        let mut min_position = term_positions[0][cursors[0]];
+        let mut second_min_position = u32::max_value();
+        for term_ord in 1..num_terms {
+            let position = term_positions[term_ord][cursors[term_ord]];
+            if position < min_position {
+                second_min_position = min_position;
+                min_position = position;
+                min_term_ord = term_ord;
+            } else if position < second_min_position {
+                second_min_position = position;
+            }
+        }
+        // The smallest position is advanced as long as it remains the smallest,
+        // in order to consider the tightest match.
+        let min_term_positions = &term_positions[min_term_ord];
+        while cursors[min_term_ord] + 1 < min_term_positions.len()
+            && min_term_positions[cursors[min_term_ord] + 1] <= second_min_position {
+            cursors[min_term_ord] += 1;
+            min_position = min_term_positions[cursors[min_term_ord]];
+        }
+        let distance = max_position - min_position;
+        if distance <= slop && distinct_positions(term_positions, offsets, &cursors[..]) {
+            freq += 1f32 / (1 + distance) as f32;
+        }
+        cursors[min_term_ord] += 1;
+        if cursors[min_term_ord] == min_term_positions.len() {
+            return freq;
+        }
+        let position = min_term_positions[cursors[min_term_ord]];
+        if position > max_position {
+            max_position = position;
+        }
+    }
+}
+
 
 impl<TPostings: Postings> PhraseScorer<TPostings> {
 
     pub fn new(term_postings: Vec<TPostings>,
                similarity_weight: BM25Weight,
                fieldnorm_reader: FieldNormReader,
-               score_needed: bool) -> PhraseScorer<TPostings> {
+               score_needed: bool,
+               slop: u32) -> PhraseScorer<TPostings> {
         let num_docsets = term_postings.len();
         let postings_with_offsets = term_postings
             .into_iter()
             .enumerate()
             .map(|(offset, postings)| PostingsWithOffset::new(postings, (num_docsets - offset) as u32))
             .collect::<Vec<_>>();
+        let mut intersection_docset = Intersection::new(postings_with_offsets);
+        // The intersection reorders the postings, so the offsets
+        // are listed in the same order as within the intersection.
+        let offsets = (0..num_docsets)
+            .map(|ord| intersection_docset.docset_mut_specialized(ord).offset)
+            .collect();
         PhraseScorer {
-            intersection_docset: Intersection::new(postings_with_offsets),
+            intersection_docset,
             num_docsets,
             left: Vec::with_capacity(100),
             right: Vec::with_capacity(100),
-            phrase_count: 0u32,
+            term_positions: vec![Vec::with_capacity(100); num_docsets],
+            offsets,
+            cursors: Vec::with_capacity(num_docsets),
+            phrase_freq: 0f32,
             similarity_weight,
             fieldnorm_reader,
             score_needed,
+            slop,
         }
     }
 
     fn phrase_match(&mut self) -> bool {
-        if self.score_needed {
+        if self.slop > 0 {
+            let freq = self.sloppy_phrase_freq();
+            self.phrase_freq = freq;
+            freq > 0f32
+        } else if self.score_needed {
             let count = self.phrase_count();
-            self.phrase_count = count;
+            self.phrase_freq = count as f32;
             count > 0u32
         } else {
             self.phrase_exists()
         }
     }
 
+    fn sloppy_phrase_freq(&mut self) -> f32 {
+        for (term_ord, positions) in self.term_positions.iter_mut().enumerate() {
+            self.intersection_docset
+                .docset_mut_specialized(term_ord)
+                .positions(positions);
+        }
+        sloppy_phrase_freq(&self.term_positions[..], &self.offsets[..], self.slop, &mut self.cursors)
+    }
+
 
     fn phrase_exists(&mut self) -> bool {
         {
@@ -238,7 +345,7 @@ impl<TPostings: Postings> Scorer for PhraseScorer<TPostings> {
     fn score(&mut self) -> f32 {
         let doc = self.doc();
         let fieldnorm_id = self.fieldnorm_reader.fieldnorm_id(doc);
-        self.similarity_weight.score(fieldnorm_id, self.phrase_count)
+        self.similarity_weight.score_with_freq(fieldnorm_id, self.phrase_freq)
     }
 }
 
@@ -246,7 +353,8 @@ impl<TPostings: Postings> Scorer for PhraseScorer<TPostings> {
 mod tests {
 
     use test::Bencher;
-    use super::{intersection_count, intersection};
+    use super::{intersection_count, intersection, sloppy_phrase_freq};
+    use tests::assert_nearly_equals;
 
 
     fn test_intersection_sym(left: &[u32], right: &[u32], expected: &[u32]) {
@@ -271,6 +379,36 @@ mod tests {
         test_intersection_sym(&[1, 5, 6, 9, 10, 12], &[6, 8, 9, 12], &[6, 9, 12]);
     }
 
+    fn test_sloppy_phrase_freq_aux(term_positions: &[&[u32]], slop: u32) -> f32 {
+        let num_terms = term_positions.len();
+        let offsets: Vec<u32> = (0..num_terms)
+            .map(|term_ord| (num_terms - term_ord) as u32)
+            .collect();
+        let term_positions: Vec<Vec<u32>> = term_positions
+            .iter()
+            .zip(offsets.iter())
+            .map(|(positions, &offset)| positions.iter().map(|&pos| pos + offset).collect())
+            .collect();
+        sloppy_phrase_freq(&term_positions[..], &offsets[..], slop, &mut Vec::new())
+    }
+
+    #[test]
+    fn test_sloppy_phrase_freq() {
+        // "quick fox"
+        assert_nearly_equals(test_sloppy_phrase_freq_aux(&[&[0], &[1]], 0), 1f32);
+        // "quick brown fox"
+        assert_nearly_equals(test_sloppy_phrase_freq_aux(&[&[0], &[2]], 0), 0f32);
+        assert_nearly_equals(test_sloppy_phrase_freq_aux(&[&[0], &[2]], 1), 0.5f32);
+        // "fox quick"
+        assert_nearly_equals(test_sloppy_phrase_freq_aux(&[&[1], &[0]], 1), 0f32);
+        assert_nearly_equals(test_sloppy_phrase_freq_aux(&[&[1], &[0]], 2), 1f32 / 3f32);
+        // "quick fox ... quick brown fox"
+        assert_nearly_equals(test_sloppy_phrase_freq_aux(&[&[0, 5], &[1, 7]], 1), 1.5f32);
+        // the same position cannot be used twice for a repeated term.
+        assert_nearly_equals(test_sloppy_phrase_freq_aux(&[&[0], &[0]], 1), 0f32);
+        assert_nearly_equals(test_sloppy_phrase_freq_aux(&[&[0, 2], &[0, 2]], 1), 0.5f32);
+    }
+
     #[bench]
     fn bench_intersection_short(b: &mut Bencher) {
         b.iter(|| {
@@ -12,17 +12,23 @@ pub struct PhraseWeight {
     phrase_terms: Vec<Term>,
     similarity_weight: BM25Weight,
     score_needed: bool,
+    slop: u32,
 }
 
 impl PhraseWeight {
     /// Creates a new phrase weight.
+    ///
+    /// If `slop` is not 0, the terms of the phrase may be up to
+    /// `slop` moves away from their expected position.
     pub fn new(phrase_terms: Vec<Term>,
                similarity_weight: BM25Weight,
-               score_needed: bool) -> PhraseWeight {
+               score_needed: bool,
+               slop: u32) -> PhraseWeight {
         PhraseWeight {
             phrase_terms,
             similarity_weight,
-            score_needed
+            score_needed,
+            slop
         }
     }
 }
@@ -43,7 +49,7 @@ impl Weight for PhraseWeight {
                     return Ok(box EmptyScorer);
                 }
             }
-            Ok(box PhraseScorer::new(term_postings_list, similarity_weight, fieldnorm_reader, self.score_needed))
+            Ok(box PhraseScorer::new(term_postings_list, similarity_weight, fieldnorm_reader, self.score_needed, self.slop))
         } else {
             let mut term_postings_list = Vec::new();
             for term in &self.phrase_terms {
@@ -55,7 +61,7 @@ impl Weight for PhraseWeight {
                     return Ok(box EmptyScorer);
                 }
             }
-            Ok(box PhraseScorer::new(term_postings_list, similarity_weight, fieldnorm_reader, self.score_needed))
+            Ok(box PhraseScorer::new(term_postings_list, similarity_weight, fieldnorm_reader, self.score_needed, self.slop))
         }
     }
 }
@@ -0,0 +1,165 @@
+use DocId;
+use docset::{DocSet, SkipResult};
+use postings::Postings;
+
+/// `Postings` of the union of several terms.
+///
+/// The union is used by the `MultiPhraseQuery`, to consider
+/// all of the alternative terms at a given position of the phrase
+/// as if they were a single term.
+///
+/// The term frequency is the sum of the frequencies of the terms,
+/// and the positions are the union of their positions.
+pub struct UnionPostings<TPostings: Postings> {
+    /// Postings that are not exhausted yet.
+    postings: Vec<TPostings>,
+    doc: DocId,
+    started: bool,
+    positions_buffer: Vec<u32>,
+}
+
+impl<TPostings: Postings> UnionPostings<TPostings> {
+    /// Creates a new `UnionPostings`.
+    pub fn new(postings: Vec<TPostings>) -> UnionPostings<TPostings> {
+        let postings = postings
+            .into_iter()
+            .filter_map(|mut postings| if postings.advance() { Some(postings) } else { None })
+            .collect();
+        UnionPostings {
+            postings,
+            doc: 0u32,
+            started: false,
+            positions_buffer: Vec::with_capacity(100),
+        }
+    }
+
+    /// Positions the union on the smallest document of the remaining postings.
+    ///
+    /// Returns false if all of the postings are exhausted.
+    fn update_doc(&mut self) -> bool {
+        match self.postings.iter().map(|postings| postings.doc()).min() {
+            Some(doc) => {
+                self.doc = doc;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<TPostings: Postings> DocSet for UnionPostings<TPostings> {
+    fn advance(&mut self) -> bool {
+        if self.started {
+            let doc = self.doc;
+            let mut i = 0;
+            while i < self.postings.len() {
+                if self.postings[i].doc() == doc && !self.postings[i].advance() {
+                    self.postings.swap_remove(i);
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        self.started = true;
+        self.update_doc()
+    }
+
+    fn skip_next(&mut self, target: DocId) -> SkipResult {
+        if self.started && target <= self.doc {
+            // Like the default implementation, we move to the next document.
+            return if self.advance() {
+                SkipResult::OverStep
+            } else {
+                SkipResult::End
+            };
+        }
+        self.started = true;
+        let mut i = 0;
+        while i < self.postings.len() {
+            if self.postings[i].doc() < target
+                && self.postings[i].skip_next(target) == SkipResult::End
+            {
+                self.postings.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        if !self.update_doc() {
+            SkipResult::End
+        } else if self.doc == target {
+            SkipResult::Reached
+        } else {
+            SkipResult::OverStep
+        }
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.postings.iter().map(|postings| postings.size_hint()).sum()
+    }
+}
+
+impl<TPostings: Postings> Postings for UnionPostings<TPostings> {
+    fn term_freq(&self) -> u32 {
+        let doc = self.doc;
+        self.postings
+            .iter()
+            .filter(|postings| postings.doc() == doc)
+            .map(|postings| postings.term_freq())
+            .sum()
+    }
+
+    fn positions_with_offset(&mut self, offset: u32, output: &mut Vec<u32>) {
+        output.clear();
+        let doc = self.doc;
+        for postings in &mut self.postings {
+            if postings.doc() == doc {
+                postings.positions_with_offset(offset, &mut self.positions_buffer);
+                output.extend_from_slice(&self.positions_buffer);
+            }
+        }
+        output.sort();
+        output.dedup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::UnionPostings;
+    use docset::{DocSet, SkipResult};
+    use postings::SegmentPostings;
+
+    #[test]
+    fn test_union_postings() {
+        let left = SegmentPostings::create_from_docs(&[1, 3, 5]);
+        let right = SegmentPostings::create_from_docs(&[2, 3, 8]);
+        let mut union_postings = UnionPostings::new(vec![left, right]);
+        assert_eq!(union_postings.size_hint(), 6);
+        let mut docs = Vec::new();
+        while union_postings.advance() {
+            docs.push(union_postings.doc());
+        }
+        assert_eq!(docs, vec![1, 2, 3, 5, 8]);
+        assert!(!union_postings.advance());
+    }
+
+    #[test]
+    fn test_union_postings_skip_next() {
+        let left = SegmentPostings::create_from_docs(&[1, 3, 5]);
+        let right = SegmentPostings::create_from_docs(&[2, 3, 8]);
+        let mut union_postings = UnionPostings::new(vec![left, right]);
+        assert_eq!(union_postings.skip_next(2), SkipResult::Reached);
+        assert_eq!(union_postings.doc(), 2);
+        assert_eq!(union_postings.skip_next(3), SkipResult::Reached);
+        assert_eq!(union_postings.doc(), 3);
+        assert!(union_postings.advance());
+        assert_eq!(union_postings.doc(), 5);
+        assert_eq!(union_postings.skip_next(6), SkipResult::OverStep);
+        assert_eq!(union_postings.doc(), 8);
+        assert_eq!(union_postings.skip_next(9), SkipResult::End);
+    }
+}
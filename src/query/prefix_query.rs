@@ -0,0 +1,185 @@
+use schema::{Field, Term};
+use query::{Query, RangeQuery, Weight};
+use query::term_expansion::{check_max_expansions, DEFAULT_MAX_EXPANSIONS};
+use termdict::{TermDictionary, TermStreamerBuilder};
+use core::Searcher;
+use std::collections::Bound;
+use Result;
+
+/// Returns the smallest byte string greater than all of the
+/// byte strings starting with `prefix`.
+///
+/// Returns `None` if there is no such string, i.e. if the prefix
+/// only contains `0xFF` bytes.
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut upper_bound = prefix.to_vec();
+    while let Some(last_byte) = upper_bound.pop() {
+        if last_byte < u8::max_value() {
+            upper_bound.push(last_byte + 1);
+            return Some(upper_bound);
+        }
+    }
+    None
+}
+
+/// `PrefixQuery` matches all of the documents containing
+/// a term starting with a given prefix.
+///
+/// The number of distinct terms matching the prefix is capped
+/// (by default to 1024). Running the query returns a `TooManyTerms`
+/// error if the cap is exceeded.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// # Implementation
+///
+/// The terms starting with the prefix form a contiguous range
+/// of the term dictionary. The query is therefore executed as a
+/// `RangeQuery` from the prefix (included) to the prefix
+/// with its last byte incremented (excluded).
+///
+/// # Example
+///
+/// ```rust
+///
+/// # #[macro_use]
+/// # extern crate tantivy;
+/// # use tantivy::Index;
+/// # use tantivy::schema::{SchemaBuilder, Term, TEXT};
+/// # use tantivy::collector::CountCollector;
+/// # use tantivy::query::{PrefixQuery, Query};
+/// # use tantivy::Result;
+/// #
+/// # fn run() -> Result<()> {
+/// #     let mut schema_builder = SchemaBuilder::new();
+/// #     let title_field = schema_builder.add_text_field("title", TEXT);
+/// #     let schema = schema_builder.build();
+/// #
+/// #     let index = Index::create_in_ram(schema);
+/// #     {
+/// #         let mut index_writer = index.writer_with_num_threads(1, 6_000_000).unwrap();
+/// #         index_writer.add_document(doc!(title_field => "The Name of the Wind"));
+/// #         index_writer.add_document(doc!(title_field => "The Diary of Muadib"));
+/// #         index_writer.add_document(doc!(title_field => "A Dairy Cow"));
+/// #         index_writer.commit().unwrap();
+/// #     }
+/// #   index.load_searchers()?;
+/// let searcher = index.searcher();
+///
+/// let query = PrefixQuery::new(Term::from_field_text(title_field, "di"));
+///
+/// let mut count_collector = CountCollector::default();
+/// query.search(&*searcher, &mut count_collector)?;
+///
+/// #     assert_eq!(count_collector.count(), 1);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #   run().unwrap()
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PrefixQuery {
+    prefix: Term,
+    max_expansions: usize,
+}
+
+impl PrefixQuery {
+    /// Creates a new `PrefixQuery`, matching the terms
+    /// of the field of `prefix` starting with its value.
+    pub fn new(prefix: Term) -> PrefixQuery {
+        PrefixQuery {
+            prefix,
+            max_expansions: DEFAULT_MAX_EXPANSIONS,
+        }
+    }
+
+    /// Sets the maximum number of distinct terms the prefix
+    /// may expand into.
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> PrefixQuery {
+        self.max_expansions = max_expansions;
+        self
+    }
+
+    /// Returns the prefix of the query.
+    pub fn prefix(&self) -> &Term {
+        &self.prefix
+    }
+}
+
+impl Query for PrefixQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        let field: Field = self.prefix.field();
+        let prefix_bytes = self.prefix.value_bytes();
+        let upper_bound = prefix_upper_bound(prefix_bytes);
+        check_max_expansions(searcher, field, self.max_expansions, |term_dict| {
+            let stream_builder = term_dict.range().ge(prefix_bytes);
+            match upper_bound {
+                Some(ref upper_bound) => stream_builder.lt(upper_bound),
+                None => stream_builder,
+            }
+        })?;
+        let upper_bound = match upper_bound {
+            Some(upper_bound) => Bound::Excluded(Term::from_field_bytes(field, &upper_bound)),
+            None => Bound::Unbounded,
+        };
+        RangeQuery::new_term_bounds(field, Bound::Included(self.prefix.clone()), upper_bound)
+            .weight(searcher, scoring_enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{SchemaBuilder, Term, TEXT};
+    use collector::CountCollector;
+    use query::Query;
+    use error::{Error, ErrorKind};
+    use super::{prefix_upper_bound, PrefixQuery};
+
+    #[test]
+    fn test_prefix_upper_bound() {
+        assert_eq!(prefix_upper_bound(b"abc"), Some(b"abd".to_vec()));
+        assert_eq!(prefix_upper_bound(b"ab\xFF"), Some(b"ac".to_vec()));
+        assert_eq!(prefix_upper_bound(b"\xFF\xFF"), None);
+        assert_eq!(prefix_upper_bound(b""), None);
+    }
+
+    #[test]
+    fn test_prefix_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(title_field => "japan"));
+            index_writer.add_document(doc!(title_field => "japon"));
+            index_writer.commit().unwrap();
+            index_writer.add_document(doc!(title_field => "japan jazz"));
+            index_writer.add_document(doc!(title_field => "korea"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |query: &Query| {
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        let prefix_query =
+            |prefix: &str| PrefixQuery::new(Term::from_field_text(title_field, prefix));
+        assert_eq!(count(&prefix_query("jap")), 3);
+        assert_eq!(count(&prefix_query("ja")), 3);
+        assert_eq!(count(&prefix_query("jaz")), 1);
+        assert_eq!(count(&prefix_query("")), 4);
+        assert_eq!(count(&prefix_query("x")), 0);
+        // `japan` appears in both segments, but only counts once.
+        assert_eq!(count(&prefix_query("jap").with_max_expansions(2)), 3);
+        let result = prefix_query("ja")
+            .with_max_expansions(2)
+            .search(&*searcher, &mut CountCollector::default());
+        assert_matches!(result, Err(Error(ErrorKind::TooManyTerms(2), _)));
+    }
+}
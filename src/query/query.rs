@@ -1,8 +1,9 @@
 use Result;
 use collector::Collector;
 use core::searcher::Searcher;
+use DocAddress;
 use SegmentLocalId;
-use super::Weight;
+use super::{Explanation, Weight};
 use std::fmt;
 
 /// The `Query` trait defines a set of documents and a scoring method
@@ -57,6 +58,16 @@ pub trait Query: fmt::Debug {
         Ok(result)
     }
 
+    /// Returns an [`Explanation`](./struct.Explanation.html) of the
+    /// score of the document at the given address.
+    ///
+    /// Returns an error if the document does not match the query.
+    fn explain(&self, searcher: &Searcher, doc_address: &DocAddress) -> Result<Explanation> {
+        let segment_reader = searcher.segment_reader(doc_address.segment_ord());
+        let weight = self.weight(searcher, true)?;
+        weight.explain(segment_reader, doc_address.doc())
+    }
+
     /// Search works as follows :
     ///
     /// First the weight object associated to the query is created.
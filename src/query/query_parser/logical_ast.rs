@@ -1,23 +1,49 @@
 use std::fmt;
-use schema::Term;
-use query::Occur;
+use std::collections::Bound;
+use schema::{Field, Term};
+use query::{MinimumShouldMatch, Occur};
 
 #[derive(Clone)]
 pub enum LogicalLiteral {
     Term(Term),
-    Phrase(Vec<Term>),
+    Phrase(Vec<Term>, u32),
+    Fuzzy {
+        term: Term,
+        distance: u8,
+        /// Number of leading characters of the term value that must match exactly.
+        prefix_length: usize,
+    },
+    Range {
+        field: Field,
+        lower: Bound<Term>,
+        upper: Bound<Term>,
+    },
+    Regex {
+        field: Field,
+        pattern: String,
+    },
+    Prefix(Term),
+    Wildcard {
+        field: Field,
+        pattern: String,
+    },
+    Exists(Field),
 }
 
 #[derive(Clone)]
 pub enum LogicalAST {
     Clause(Vec<(Occur, LogicalAST)>),
     Leaf(Box<LogicalLiteral>),
+    DisjunctionMax(Vec<LogicalAST>, f32),
+    MinimumShouldMatch(Box<LogicalAST>, MinimumShouldMatch),
+    Boost(Box<LogicalAST>, f32),
 }
 
 fn occur_letter(occur: Occur) -> &'static str {
     match occur {
         Occur::Must => "+",
         Occur::MustNot => "-",
+        Occur::Filter => "#",
         Occur::Should => "",
     }
 }
@@ -39,6 +65,22 @@ impl fmt::Debug for LogicalAST {
                 Ok(())
             }
             LogicalAST::Leaf(ref literal) => write!(formatter, "{:?}", literal),
+            LogicalAST::DisjunctionMax(ref disjuncts, _) => {
+                write!(formatter, "dismax(")?;
+                for (i, disjunct) in disjuncts.iter().enumerate() {
+                    if i > 0 {
+                        write!(formatter, " ")?;
+                    }
+                    write!(formatter, "{:?}", disjunct)?;
+                }
+                formatter.write_str(")")
+            }
+            LogicalAST::MinimumShouldMatch(ref clause, minimum_should_match) => {
+                write!(formatter, "{:?}~{}", clause, minimum_should_match)
+            }
+            LogicalAST::Boost(ref subquery, boost) => {
+                write!(formatter, "({:?})^{}", subquery, boost)
+            }
         }
     }
 }
@@ -53,7 +95,27 @@ impl fmt::Debug for LogicalLiteral {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
             LogicalLiteral::Term(ref term) => write!(formatter, "{:?}", term),
-            LogicalLiteral::Phrase(ref terms) => write!(formatter, "\"{:?}\"", terms),
+            LogicalLiteral::Phrase(ref terms, slop) => {
+                write!(formatter, "\"{:?}\"", terms)?;
+                if slop > 0 {
+                    write!(formatter, "~{}", slop)?;
+                }
+                Ok(())
+            }
+            LogicalLiteral::Fuzzy {
+                ref term,
+                distance,
+                ..
+            } => write!(formatter, "{:?}~{}", term, distance),
+            LogicalLiteral::Range {
+                ref lower,
+                ref upper,
+                ..
+            } => write!(formatter, "({:?} TO {:?})", lower, upper),
+            LogicalLiteral::Regex { ref pattern, .. } => write!(formatter, "/{}/", pattern),
+            LogicalLiteral::Prefix(ref term) => write!(formatter, "{:?}*", term),
+            LogicalLiteral::Wildcard { ref pattern, .. } => write!(formatter, "{}", pattern),
+            LogicalLiteral::Exists(field) => write!(formatter, "_exists_:{:?}", field),
         }
     }
 }
@@ -1,59 +1,263 @@
 use combine::*;
 use combine::char::*;
 use super::user_input_ast::*;
+use std::str::FromStr;
+
+fn field<I>(input: I) -> ParseResult<String, I>
+where
+    I: Stream<Item = char>,
+{
+    (
+        letter(),
+        many(satisfy(|c: char| c.is_alphanumeric() || c == '_' || c == '.')),
+    ).map(|(s1, s2): (char, String)| format!("{}{}", s1, s2))
+        .parse_stream(input)
+}
 
 fn literal<I>(input: I) -> ParseResult<UserInputAST, I>
 where
     I: Stream<Item = char>,
 {
-    let term_val = || {
-        let word = many1(satisfy(|c: char| c.is_alphanumeric()));
-        let phrase = (char('"'), many1(satisfy(|c| c != '"')), char('"')).map(|(_, s, _)| s);
-        phrase.or(word)
+    let word = || {
+        (
+            satisfy(|c: char| c.is_alphanumeric()),
+            many(satisfy(|c: char| c.is_alphanumeric() || c == '.')),
+        ).map(|(s1, s2): (char, String)| format!("{}{}", s1, s2))
+    };
+    // A phrase may be followed by a slop, to also match its
+    // terms when they are not adjacent. (e.g. `"quick fox"~2`)
+    let phrase = || {
+        (
+            (char('"'), many1(satisfy(|c| c != '"')), char('"')).map(|(_, s, _)| s),
+            optional((char('~'), many1(digit())).map(|(_, slop): (char, String)| {
+                u32::from_str(&slop).unwrap_or(u32::max_value())
+            })),
+        ).map(|(phrase, slop): (String, Option<u32>)| {
+            (phrase, None, slop.unwrap_or(0u32), true)
+        })
     };
 
-    let negative_numbers = (char('-'), many1(satisfy(|c: char| c.is_numeric())))
-        .map(|(s1, s2): (char, String)| format!("{}{}", s1, s2));
+    // A word may be followed by a maximum edit distance of 1 or 2, to
+    // express a fuzzy term. (e.g. `diary~1`)
+    let fuzzy_distance = || {
+        optional(
+            (char('~'), one_of("12".chars()))
+                .map(|(_, d): (char, char)| d.to_digit(10).unwrap() as u8),
+        )
+    };
 
-    let field = (
-        letter(),
-        many(satisfy(|c: char| c.is_alphanumeric() || c == '_')),
+    let negative_numbers = (
+        char('-'),
+        many1(satisfy(|c: char| c.is_numeric() || c == '.')),
     ).map(|(s1, s2): (char, String)| format!("{}{}", s1, s2));
 
-    let term_val_with_field = negative_numbers.or(term_val());
+    // Values attached to a field may also contain `:` and `/`,
+    // to express IPv6 addresses and CIDR blocks. (e.g. `src:2001:db8::/32`)
+    // The query parser only accepts them for IP address fields.
+    let field_word = (
+        satisfy(|c: char| c.is_alphanumeric() || c == ':'),
+        many(satisfy(|c: char| {
+            c.is_alphanumeric() || c == '.' || c == ':' || c == '/'
+        })),
+    ).map(|(s1, s2): (char, String)| format!("{}{}", s1, s2));
+
+    let field_word_with_fuzzy = (negative_numbers.or(field_word), fuzzy_distance())
+        .map(|(word, fuzzy_distance)| (word, fuzzy_distance, 0u32, false));
+    let term_val_with_field = phrase().or(field_word_with_fuzzy);
 
-    let term_query =
-        (field, char(':'), term_val_with_field).map(|(field_name, _, phrase)| UserInputLiteral {
+    let term_query = (parser(field), char(':'), term_val_with_field).map(
+        |(field_name, _, (phrase, fuzzy_distance, slop, quoted))| UserInputLiteral {
             field_name: Some(field_name),
             phrase,
+            quoted,
+            fuzzy_distance,
+            slop,
+        },
+    );
+    let term_default_field = phrase()
+        .or((word(), fuzzy_distance())
+            .map(|(word, fuzzy_distance)| (word, fuzzy_distance, 0u32, false)))
+        .map(|(phrase, fuzzy_distance, slop, quoted)| UserInputLiteral {
+            field_name: None,
+            phrase,
+            quoted,
+            fuzzy_distance,
+            slop,
         });
-    let term_default_field = term_val().map(|phrase| UserInputLiteral {
-        field_name: None,
-        phrase,
-    });
     try(term_query)
         .or(term_default_field)
         .map(UserInputAST::from)
         .parse_stream(input)
 }
 
-fn leaf<I>(input: I) -> ParseResult<UserInputAST, I>
+/// Builds a range bound out of its boundary character and its value.
+/// `*` stands for an unbounded side.
+fn make_bound(inclusive: bool, word: String) -> UserInputBound {
+    if word == "*" {
+        UserInputBound::Unbounded
+    } else if inclusive {
+        UserInputBound::Inclusive(word)
+    } else {
+        UserInputBound::Exclusive(word)
+    }
+}
+
+/// Parses a range, e.g. `year:[1960 TO 1970}` or `ts:[2020-01-01T00:00:00Z TO *]`.
+///
+/// `[` and `]` denote inclusive bounds, while `{` and `}` denote exclusive bounds.
+fn range<I>(input: I) -> ParseResult<UserInputAST, I>
+where
+    I: Stream<Item = char>,
+{
+    let lower_bound = (
+        one_of("[{".chars()),
+        spaces(),
+        many1(satisfy(|c: char| !c.is_whitespace())),
+    ).map(|(boundary, _, word): (char, _, String)| make_bound(boundary == '[', word));
+    let upper_bound = (
+        many1(satisfy(|c: char| !c.is_whitespace() && c != ']' && c != '}')),
+        spaces(),
+        one_of("]}".chars()),
+    ).map(|(word, _, boundary): (String, _, char)| make_bound(boundary == ']', word));
+    (
+        parser(field),
+        char(':'),
+        lower_bound,
+        skip_many1(space()),
+        string("TO"),
+        skip_many1(space()),
+        upper_bound,
+    ).map(|(field_name, _, lower, _, _, _, upper)| {
+        UserInputAST::from(UserInputRange {
+            field_name,
+            lower,
+            upper,
+        })
+    })
+        .parse_stream(input)
+}
+
+/// Parses a regular expression, e.g. `sku:/AB-[0-9]{4}/`.
+///
+/// A `/` within the expression needs to be escaped as `\/`.
+fn regex<I>(input: I) -> ParseResult<UserInputAST, I>
 where
     I: Stream<Item = char>,
 {
-    (char('-'), parser(leaf))
-        .map(|(_, expr)| UserInputAST::Not(box expr))
-        .or((char('+'), parser(leaf)).map(|(_, expr)| UserInputAST::Must(box expr)))
-        .or((char('('), parser(parse_to_ast), char(')')).map(|(_, expr, _)| expr))
-        .or(parser(literal))
+    let escaped_char = (char('\\'), any()).map(|(_, c): (char, char)| {
+        if c == '/' {
+            c.to_string()
+        } else {
+            format!("\\{}", c)
+        }
+    });
+    let regex_char = escaped_char.or(satisfy(|c: char| c != '/' && c != '\\').map(|c: char| {
+        c.to_string()
+    }));
+    (
+        parser(field),
+        char(':'),
+        char('/'),
+        many1(regex_char),
+        char('/'),
+    ).map(|(field_name, _, _, pattern_parts, _): (String, _, _, Vec<String>, _)| {
+        UserInputAST::from(UserInputRegex {
+            field_name,
+            pattern: pattern_parts.concat(),
+        })
+    })
         .parse_stream(input)
 }
 
-pub fn parse_to_ast<I>(input: I) -> ParseResult<UserInputAST, I>
+/// Parses a wildcard pattern, e.g. `foo*` or `title:f?o`.
+///
+/// `*` matches any sequence of characters, while `?` matches a single character.
+fn wildcard<I>(input: I) -> ParseResult<UserInputAST, I>
 where
     I: Stream<Item = char>,
 {
-    sep_by(parser(leaf), spaces())
+    let wildcard_word = (
+        many(satisfy(|c: char| c.is_alphanumeric() || c == '.')),
+        one_of("*?".chars()),
+        many(satisfy(|c: char| {
+            c.is_alphanumeric() || c == '.' || c == '*' || c == '?'
+        })),
+    ).map(|(s1, c, s2): (String, char, String)| format!("{}{}{}", s1, c, s2));
+    (optional(try((parser(field), char(':')))), wildcard_word)
+        .map(|(field_name, pattern)| {
+            UserInputAST::from(UserInputWildcard {
+                field_name: field_name.map(|(field_name, _)| field_name),
+                pattern,
+            })
+        })
+        .parse_stream(input)
+}
+
+/// Parses an existence clause, e.g. `_exists_:email`.
+fn exists<I>(input: I) -> ParseResult<UserInputAST, I>
+where
+    I: Stream<Item = char>,
+{
+    (string("_exists_"), char(':'), parser(field))
+        .map(|(_, _, field_name)| UserInputAST::Exists(field_name))
+        .parse_stream(input)
+}
+
+/// Parses a boost, e.g. `^2.0`.
+fn boost<I>(input: I) -> ParseResult<f32, I>
+where
+    I: Stream<Item = char>,
+{
+    (
+        char('^'),
+        many1(digit()),
+        optional((char('.'), many1(digit()))),
+    ).map(
+        |(_, integer_part, decimal_part): (_, String, Option<(char, String)>)| {
+            let boost = match decimal_part {
+                Some((_, decimal_part)) => format!("{}.{}", integer_part, decimal_part),
+                None => integer_part,
+            };
+            f32::from_str(&boost).unwrap()
+        },
+    )
+        .parse_stream(input)
+}
+
+fn leaf<I>(filter_prefix: char, input: I) -> ParseResult<UserInputAST, I>
+where
+    I: Stream<Item = char>,
+{
+    let unboosted_leaf = (char(filter_prefix), env_parser(filter_prefix, leaf::<I>))
+        .map(|(_, expr)| UserInputAST::Filter(box expr))
+        .or((char('-'), env_parser(filter_prefix, leaf::<I>))
+            .map(|(_, expr)| UserInputAST::Not(box expr)))
+        .or((char('+'), env_parser(filter_prefix, leaf::<I>))
+            .map(|(_, expr)| UserInputAST::Must(box expr)))
+        .or((char('('), env_parser(filter_prefix, parse_to_ast::<I>), char(')'))
+            .map(|(_, expr, _)| expr))
+        .or(try(parser(exists)))
+        .or(try(parser(range)))
+        .or(try(parser(regex)))
+        .or(try(parser(wildcard)))
+        .or(parser(literal));
+    // Any leaf may be followed by a boost. (e.g. `title:foo^2.0`)
+    (unboosted_leaf, optional(parser(boost)))
+        .map(|(expr, boost)| match boost {
+            Some(boost) => UserInputAST::Boost(box expr, boost),
+            None => expr,
+        })
+        .parse_stream(input)
+}
+
+/// Parses a user query.
+///
+/// Clauses prefixed by `filter_prefix` are parsed as filters.
+pub fn parse_to_ast<I>(filter_prefix: char, input: I) -> ParseResult<UserInputAST, I>
+where
+    I: Stream<Item = char>,
+{
+    sep_by(env_parser(filter_prefix, leaf::<I>), spaces())
         .map(|subqueries: Vec<UserInputAST>| {
             if subqueries.len() == 1 {
                 subqueries.into_iter().next().unwrap()
@@ -70,13 +274,13 @@ mod test {
     use super::*;
 
     fn test_parse_query_to_ast_helper(query: &str, expected: &str) {
-        let query = parse_to_ast(query).unwrap().0;
+        let query = parse_to_ast('#', query).unwrap().0;
         let query_str = format!("{:?}", query);
         assert_eq!(query_str, expected);
     }
 
     fn test_is_parse_err(query: &str) {
-        assert!(parse_to_ast(query).is_err());
+        assert!(parse_to_ast('#', query).is_err());
     }
 
     #[test]
@@ -91,6 +295,100 @@ mod test {
         test_parse_query_to_ast_helper("-abc:toto", "-(abc:\"toto\")");
         test_parse_query_to_ast_helper("abc:a b", "(abc:\"a\" \"b\")");
         test_parse_query_to_ast_helper("abc:\"a b\"", "abc:\"a b\"");
+        test_parse_query_to_ast_helper("abc:1.5", "abc:\"1.5\"");
+        test_parse_query_to_ast_helper("abc:-1.5", "abc:\"-1.5\"");
         test_is_parse_err("abc +    ");
     }
+
+    #[test]
+    fn test_parse_query_fuzzy() {
+        test_parse_query_to_ast_helper("abc:toto~1", "abc:\"toto\"~1");
+        test_parse_query_to_ast_helper("toto~2 titi", "(\"toto\"~2 \"titi\")");
+        test_parse_query_to_ast_helper("+abc:toto~1", "+(abc:\"toto\"~1)");
+        test_is_parse_err("toto~a");
+        test_is_parse_err("toto~0");
+        test_is_parse_err("abc:toto~3");
+        test_is_parse_err("toto~9");
+    }
+
+    #[test]
+    fn test_parse_query_ip() {
+        test_parse_query_to_ast_helper("src:10.0.0.0/8", "src:\"10.0.0.0/8\"");
+        test_parse_query_to_ast_helper("src:::1", "src:\"::1\"");
+        test_parse_query_to_ast_helper("src:2001:db8::/32 a", "(src:\"2001:db8::/32\" \"a\")");
+    }
+
+    #[test]
+    fn test_parse_query_json_path() {
+        test_parse_query_to_ast_helper("attributes.color:red", "attributes.color:\"red\"");
+        test_parse_query_to_ast_helper(
+            "+attributes.dims.width:3 a",
+            "(+(attributes.dims.width:\"3\") \"a\")",
+        );
+    }
+
+    #[test]
+    fn test_parse_query_slop() {
+        test_parse_query_to_ast_helper("\"quick fox\"~2", "\"quick fox\"~2");
+        test_parse_query_to_ast_helper("title:\"quick fox\"~12", "title:\"quick fox\"~12");
+        test_parse_query_to_ast_helper("\"quick fox\"~0 a", "(\"quick fox\" \"a\")");
+    }
+
+    #[test]
+    fn test_parse_query_regex() {
+        test_parse_query_to_ast_helper("sku:/AB-[0-9]{4}/", "sku:/AB-[0-9]{4}/");
+        test_parse_query_to_ast_helper("+sku:/a.*/ b", "(+(sku:/a.*/) \"b\")");
+        test_parse_query_to_ast_helper("url:/a\\/b\\.c/", "url:/a/b\\.c/");
+    }
+
+    #[test]
+    fn test_parse_query_wildcard() {
+        test_parse_query_to_ast_helper("foo*", "foo*");
+        test_parse_query_to_ast_helper("title:f?o", "title:f?o");
+        test_parse_query_to_ast_helper("+title:*oo* bar", "(+(title:*oo*) \"bar\")");
+        test_parse_query_to_ast_helper("title:foo", "title:\"foo\"");
+    }
+
+    #[test]
+    fn test_parse_query_boost() {
+        test_parse_query_to_ast_helper("title:foo^2.0", "(title:\"foo\")^2");
+        test_parse_query_to_ast_helper("foo^1.5 bar", "((\"foo\")^1.5 \"bar\")");
+        test_parse_query_to_ast_helper("-foo~1^3", "-((\"foo\"~1)^3)");
+        test_parse_query_to_ast_helper("(a b)^2", "((\"a\" \"b\"))^2");
+        test_parse_query_to_ast_helper("\"a b\"~1^0.5", "(\"a b\"~1)^0.5");
+        test_is_parse_err("foo^a");
+    }
+
+    #[test]
+    fn test_parse_query_filter() {
+        test_parse_query_to_ast_helper("#a b", "(#(\"a\") \"b\")");
+        test_parse_query_to_ast_helper(
+            "+title:foo #year:[2000 TO *]",
+            "(+(title:\"foo\") #(year:[\"2000\" TO \"*\"}))",
+        );
+        test_parse_query_to_ast_helper("#(a b)", "#((\"a\" \"b\"))");
+        let query = parse_to_ast('!', "!a").unwrap().0;
+        assert_eq!(format!("{:?}", query), "#(\"a\")");
+    }
+
+    #[test]
+    fn test_parse_query_exists() {
+        test_parse_query_to_ast_helper("_exists_:email", "_exists_:email");
+        test_parse_query_to_ast_helper("-_exists_:email a", "(-(_exists_:email) \"a\")");
+        test_is_parse_err("_exists_:");
+    }
+
+    #[test]
+    fn test_parse_query_range() {
+        test_parse_query_to_ast_helper("year:[1960 TO 1970}", "year:[\"1960\" TO \"1970\"}");
+        test_parse_query_to_ast_helper("year:{1960 TO 1970]", "year:{\"1960\" TO \"1970\"]");
+        test_parse_query_to_ast_helper(
+            "ts:[2020-01-01T00:00:00Z TO *]",
+            "ts:[\"2020-01-01T00:00:00Z\" TO \"*\"}",
+        );
+        test_parse_query_to_ast_helper(
+            "+year:[* TO 1970] title:toto",
+            "(+(year:{\"*\" TO \"1970\"]) title:\"toto\")",
+        );
+    }
 }
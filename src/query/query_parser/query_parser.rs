@@ -1,6 +1,8 @@
 use schema::{Field, Schema};
 use query::Query;
-use query::BooleanQuery;
+use query::{BooleanQuery, MinimumShouldMatch};
+use query::BoostQuery;
+use query::DisjunctionMaxQuery;
 use super::logical_ast::*;
 use super::user_input_ast::*;
 use super::query_grammar::parse_to_ast;
@@ -8,11 +10,31 @@ use query::Occur;
 use query::TermQuery;
 use schema::IndexRecordOption;
 use query::PhraseQuery;
-use schema::{FieldType, Term};
+use query::FuzzyTermQuery;
+use schema::{Cardinality, FieldType, GeoPoint, Term};
+use fastfield::int_fast_field_cardinality;
 use std::str::FromStr;
 use tokenizer::TokenizerManager;
-use std::num::ParseIntError;
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::ParseBoolError;
+use std::collections::Bound;
 use core::Index;
+use query::RangeQuery;
+use query::RegexQuery;
+use query::{ExistsQuery, PrefixQuery, WildcardQuery};
+use chrono::{self, DateTime as ChronoDateTime, Utc};
+use base64::{self, DecodeError};
+use common;
+use std::net::{AddrParseError, IpAddr, Ipv6Addr};
+
+/// Filter prefix used by default, e.g. `#year:[2015 TO *]`.
+const DEFAULT_FILTER_PREFIX: char = '#';
+
+/// Characters which have a meaning in the query syntax,
+/// and cannot be used as the filter prefix.
+///
+/// `_` starts the `_exists_:` keyword.
+const RESERVED_CHARS: &str = "+-()\"[]{}/\\*?~^:._";
 
 /// Possible error that may happen when parsing a query.
 #[derive(Debug, PartialEq, Eq)]
@@ -25,6 +47,31 @@ pub enum QueryParserError {
     /// The query contains a term for a `u64`-field, but the value
     /// is not a u64.
     ExpectedInt(ParseIntError),
+    /// The query contains a term for a `f64`-field, but the value
+    /// is not a f64.
+    ExpectedFloat(ParseFloatError),
+    /// The query contains a term for a `bool`-field, but the value
+    /// is neither `true` nor `false`.
+    ExpectedBool(ParseBoolError),
+    /// The query contains a term for a `Date`-field, but the value
+    /// is not a valid RFC 3339 date.
+    DateFormatError(chrono::ParseError),
+    /// The query contains a term for a `Bytes`-field, but the value
+    /// is not valid base64.
+    ExpectedBase64(DecodeError),
+    /// The query contains a term for an `Ip`-field, but the value
+    /// is not a valid IPv4 or IPv6 address.
+    ExpectedIp(AddrParseError),
+    /// The query contains a CIDR block for an `Ip`-field, but its
+    /// prefix length exceeds the size of the address.
+    InvalidCidrPrefix(String),
+    /// The query references a JSON object field, without the path
+    /// of the leaf searched for (e.g. `attributes:red` instead of `attributes.color:red`).
+    ExpectedJsonPath(String),
+    /// The query applies an operator that only makes sense for text, such as a fuzzy
+    /// term, a regular expression or a wildcard (e.g. `unsigned:3*`), to a field
+    /// that is not a text field.
+    ExpectedTextField(String),
     /// It is forbidden queries that are only "excluding". (e.g. -title:pop)
     AllButQueryForbidden,
     /// If no default field is declared, running a query without any
@@ -44,6 +91,60 @@ impl From<ParseIntError> for QueryParserError {
     }
 }
 
+impl From<ParseFloatError> for QueryParserError {
+    fn from(err: ParseFloatError) -> QueryParserError {
+        QueryParserError::ExpectedFloat(err)
+    }
+}
+
+impl From<ParseBoolError> for QueryParserError {
+    fn from(err: ParseBoolError) -> QueryParserError {
+        QueryParserError::ExpectedBool(err)
+    }
+}
+
+impl From<chrono::ParseError> for QueryParserError {
+    fn from(err: chrono::ParseError) -> QueryParserError {
+        QueryParserError::DateFormatError(err)
+    }
+}
+
+impl From<DecodeError> for QueryParserError {
+    fn from(err: DecodeError) -> QueryParserError {
+        QueryParserError::ExpectedBase64(err)
+    }
+}
+
+impl From<AddrParseError> for QueryParserError {
+    fn from(err: AddrParseError) -> QueryParserError {
+        QueryParserError::ExpectedIp(err)
+    }
+}
+
+/// Computes the first and the last address of a CIDR block,
+/// e.g. `10.0.0.0/8` or `2001:db8::/32`.
+fn cidr_bounds(cidr: &str) -> Result<(Ipv6Addr, Ipv6Addr), QueryParserError> {
+    let mut parts = cidr.splitn(2, '/');
+    let ip = IpAddr::from_str(parts.next().unwrap_or(""))?;
+    let prefix_len = u8::from_str(parts.next().unwrap_or(""))?;
+    // IPv4 addresses are mapped to IPv6, behind a 96 bits prefix.
+    let (max_prefix_len, offset) = match ip {
+        IpAddr::V4(_) => (32u8, 96usize),
+        IpAddr::V6(_) => (128u8, 0usize),
+    };
+    if prefix_len > max_prefix_len {
+        return Err(QueryParserError::InvalidCidrPrefix(cidr.to_string()));
+    }
+    let mut lower = common::ip_to_ipv6(ip).octets();
+    let mut upper = lower;
+    for bit in (offset + prefix_len as usize)..128 {
+        let mask = 0x80u8 >> (bit % 8);
+        lower[bit / 8] &= !mask;
+        upper[bit / 8] |= mask;
+    }
+    Ok((Ipv6Addr::from(lower), Ipv6Addr::from(upper)))
+}
+
 /// Tantivy's Query parser
 ///
 /// The language covered by the current parser is extremely simple.
@@ -72,10 +173,60 @@ impl From<ParseIntError> for QueryParserError {
 ///
 /// * must terms: By prepending a term by a `+`, a term can be made required for the search.
 ///
+/// * filter terms: By prepending a term by a `#`, a term can be made required for the search
+///   without contributing to the score. e.g. `rust #year:[2015 TO *]`.
+///   The prefix can be changed via `.set_filter_prefix(...)`.
+///
+/// * range terms: Range searches can be done by specifying the start and end bound.
+///   Inclusive bounds use `[` and `]`, exclusive bounds use `{` and `}`, and `*` leaves
+///   a side unbounded.
+///   e.g. `year:[1960 TO 1970}` or `ts:[2020-01-01T00:00:00Z TO *]`.
+///   Dates are expressed using the RFC 3339 format. Numeric fields that are
+///   not indexed can be searched by range as long as they are single-valued fast fields.
+///
+/// * CIDR blocks: IP address fields can be searched by CIDR block.
+///   e.g. `src:10.0.0.0/8` or `src:2001:db8::/32`. Other fields only accept
+///   `:` and `/` within quotes. e.g. `url:"http://example.com"`.
+///
+/// * JSON paths: the leaves of JSON object fields are addressed by their
+///   dotted path. e.g. `attributes.color:red` or `attributes.dims.width:3`.
+///   The value matches text, number and boolean leaves alike.
+///
+/// * fuzzy terms: A word followed by `~` and a maximum edit distance (1 or 2)
+///   also matches the terms within that Levenshtein distance, transpositions
+///   counting as a single edit. e.g. `title:diary~1` matches `dairy`.
+///   A word that the tokenizer splits into several tokens is searched as a phrase.
+///   On a JSON path, the edits only apply to the value: the path must match exactly.
+///
+/// * phrase slop: A phrase followed by `~` and a slop also matches documents in
+///   which its terms are up to that many moves apart (or in a different order).
+///   e.g. `"quick fox"~1` matches `quick brown fox`.
+///
+/// * regular expressions: A regular expression between slashes matches the terms
+///   of a text field, without going through the tokenizer.
+///   e.g. `sku:/AB-[0-9]{4}/`. A `/` within the expression is escaped as `\/`.
+///
+/// * wildcards: In a word, `*` matches any sequence of characters and `?` matches
+///   a single character. e.g. `foo*` or `title:f?o`. As for regular expressions,
+///   the pattern is matched against the terms of text fields, without going through
+///   the tokenizer.
+///
+/// * boosts: A query followed by `^` and a factor gets its scores multiplied
+///   by this factor. e.g. `title:foo^2.0 body:foo` gives twice as much weight
+///   to a match in the title. Groups can be boosted as well. e.g. `(a b)^0.5`.
+///
+/// * existence: `_exists_:` followed by a field name matches the documents having
+///   a value for this field. e.g. `_exists_:email`, or `-_exists_:email` for the
+///   documents missing it. The field needs to be indexed or to be a multivalued
+///   int fast field.
+///
 pub struct QueryParser {
     schema: Schema,
     default_fields: Vec<Field>,
     conjunction_by_default: bool,
+    default_fields_tie_breaker: Option<f32>,
+    filter_prefix: char,
+    minimum_should_match: Option<MinimumShouldMatch>,
     tokenizer_manager: TokenizerManager,
 }
 
@@ -94,6 +245,9 @@ impl QueryParser {
             default_fields,
             tokenizer_manager,
             conjunction_by_default: false,
+            default_fields_tie_breaker: None,
+            filter_prefix: DEFAULT_FILTER_PREFIX,
+            minimum_should_match: None,
         }
     }
 
@@ -114,6 +268,42 @@ impl QueryParser {
         self.conjunction_by_default = true;
     }
 
+    /// Combines the queries on the different default fields with a
+    /// `DisjunctionMaxQuery`, rather than with a `BooleanQuery`.
+    ///
+    /// By default, the query `rust` is equivalent to `title:rust OR body:rust`,
+    /// and the scores of both fields are summed. After calling
+    /// `.set_disjunction_max_across_fields(tie_breaker)`, only the best
+    /// of the two scores counts, plus the other one multiplied by `tie_breaker`.
+    pub fn set_disjunction_max_across_fields(&mut self, tie_breaker: f32) {
+        self.default_fields_tie_breaker = Some(tie_breaker);
+    }
+
+    /// Requires the documents to match a minimum number of the
+    /// optional clauses of the query, e.g. 3 of the 5 terms of `a b c d e`.
+    ///
+    /// The setting applies to each group of clauses of the query,
+    /// and is ignored when the clauses are combined as a conjunction.
+    pub fn set_minimum_should_match(&mut self, minimum_should_match: MinimumShouldMatch) {
+        self.minimum_should_match = Some(minimum_should_match);
+    }
+
+    /// Sets the character marking a clause as a filter. It is `#` by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the character is alphanumeric, a whitespace, or
+    /// is already part of the query syntax.
+    pub fn set_filter_prefix(&mut self, filter_prefix: char) {
+        assert!(
+            !filter_prefix.is_alphanumeric() && !filter_prefix.is_whitespace()
+                && !RESERVED_CHARS.contains(filter_prefix),
+            "{:?} cannot be used as the filter prefix.",
+            filter_prefix
+        );
+        self.filter_prefix = filter_prefix;
+    }
+
     /// Parse a query
     ///
     /// Note that `parse_query` returns an error if the input
@@ -132,7 +322,7 @@ impl QueryParser {
     /// Parse the user query into an AST.
     fn parse_query_to_logical_ast(&self, query: &str) -> Result<LogicalAST, QueryParserError> {
         let (user_input_ast, _remaining) =
-            parse_to_ast(query).map_err(|_| QueryParserError::SyntaxError)?;
+            parse_to_ast(self.filter_prefix, query).map_err(|_| QueryParserError::SyntaxError)?;
         self.compute_logical_ast(user_input_ast)
     }
 
@@ -142,20 +332,133 @@ impl QueryParser {
             .ok_or_else(|| QueryParserError::FieldDoesNotExist(String::from(field_name)))
     }
 
+    /// Checks that the field is an IP address field, as only their values
+    /// may contain `:` and `/` without being quoted.
+    fn check_ip_field(&self, field_name: &str) -> Result<(), QueryParserError> {
+        if self.resolve_json_path(field_name).is_none() {
+            let field = self.resolve_field_name(field_name)?;
+            if let FieldType::Ip(_) = *self.schema.get_field_entry(field).field_type() {
+                return Ok(());
+            }
+        }
+        Err(QueryParserError::SyntaxError)
+    }
+
+    /// Splits a dotted name such as `attributes.color` into
+    /// a JSON object field and the path of a leaf within the object.
+    ///
+    /// Returns `None` if the name does not start with the name of a JSON object field.
+    fn resolve_json_path<'b>(&self, full_path: &'b str) -> Option<(Field, &'b str)> {
+        let mut parts = full_path.splitn(2, '.');
+        let field_name = parts.next().unwrap_or("");
+        let json_path = parts.next()?;
+        let field = self.schema.get_field(field_name)?;
+        match *self.schema.get_field_entry(field).field_type() {
+            FieldType::Json(_) => Some((field, json_path)),
+            _ => None,
+        }
+    }
+
     fn compute_logical_ast(
         &self,
         user_input_ast: UserInputAST,
     ) -> Result<LogicalAST, QueryParserError> {
         let (occur, ast) = self.compute_logical_ast_with_occur(user_input_ast)?;
-        if occur == Occur::MustNot {
-            return Err(QueryParserError::AllButQueryForbidden);
+        match occur {
+            Occur::MustNot => Err(QueryParserError::AllButQueryForbidden),
+            Occur::Filter => Ok(LogicalAST::Clause(vec![(Occur::Filter, ast)])),
+            Occur::Must | Occur::Should => Ok(ast),
+        }
+    }
+
+    /// Builds the term matching exactly a given value,
+    /// without going through the field tokenizer.
+    ///
+    /// This is used for non-text fields, and for range bounds.
+    fn compute_boundary_term(&self, field: Field, phrase: &str) -> Result<Term, QueryParserError> {
+        let field_entry = self.schema.get_field_entry(field);
+        match *field_entry.field_type() {
+            FieldType::I64(_) => {
+                let val: i64 = i64::from_str(phrase)?;
+                Ok(Term::from_field_i64(field, val))
+            }
+            FieldType::U64(_) => {
+                let val: u64 = u64::from_str(phrase)?;
+                Ok(Term::from_field_u64(field, val))
+            }
+            FieldType::F64(_) => {
+                let val: f64 = f64::from_str(phrase)?;
+                Ok(Term::from_field_f64(field, val))
+            }
+            FieldType::Bool(_) => {
+                let val: bool = bool::from_str(phrase)?;
+                Ok(Term::from_field_bool(field, val))
+            }
+            FieldType::Date(_) => {
+                let date_time = ChronoDateTime::parse_from_rfc3339(phrase)?;
+                Ok(Term::from_field_date(field, &date_time.with_timezone(&Utc)))
+            }
+            FieldType::Bytes(_) => {
+                let bytes = base64::decode(phrase)?;
+                Ok(Term::from_field_bytes(field, &bytes))
+            }
+            FieldType::Ip(_) => {
+                let ip = IpAddr::from_str(phrase)?;
+                Ok(Term::from_field_ip(field, common::ip_to_ipv6(ip)))
+            }
+            FieldType::GeoPoint(_) => {
+                let mut coordinates = phrase.splitn(2, ',');
+                let lat = f64::from_str(coordinates.next().unwrap_or("").trim())?;
+                let lon = f64::from_str(coordinates.next().unwrap_or("").trim())?;
+                Ok(Term::from_field_geo_point(field, &GeoPoint::new(lat, lon)))
+            }
+            FieldType::Str(_) | FieldType::HierarchicalFacet => {
+                Ok(Term::from_field_text(field, phrase))
+            }
+            FieldType::Json(_) => Err(QueryParserError::ExpectedJsonPath(
+                field_entry.name().to_string(),
+            )),
+        }
+    }
+
+    /// Checks that the field is an indexed text field, on which
+    /// regular expressions and wildcards can be applied.
+    fn check_indexed_text_field(&self, field: Field) -> Result<(), QueryParserError> {
+        let field_entry = self.schema.get_field_entry(field);
+        let field_name = field_entry.name().to_string();
+        match *field_entry.field_type() {
+            FieldType::Str(ref str_options) => {
+                if str_options.get_indexing_options().is_none() {
+                    return Err(QueryParserError::FieldNotIndexed(field_name));
+                }
+                Ok(())
+            }
+            _ => Err(QueryParserError::ExpectedTextField(field_name)),
+        }
+    }
+
+    fn resolve_bound(
+        &self,
+        field: Field,
+        bound: &UserInputBound,
+    ) -> Result<Bound<Term>, QueryParserError> {
+        match *bound {
+            UserInputBound::Inclusive(ref phrase) => {
+                Ok(Bound::Included(self.compute_boundary_term(field, phrase)?))
+            }
+            UserInputBound::Exclusive(ref phrase) => {
+                Ok(Bound::Excluded(self.compute_boundary_term(field, phrase)?))
+            }
+            UserInputBound::Unbounded => Ok(Bound::Unbounded),
         }
-        Ok(ast)
     }
+
     fn compute_logical_ast_for_leaf(
         &self,
         field: Field,
         phrase: &str,
+        fuzzy_distance: Option<u8>,
+        slop: u32,
     ) -> Result<Option<LogicalLiteral>, QueryParserError> {
         let field_entry = self.schema.get_field_entry(field);
         let field_type = field_entry.field_type();
@@ -163,16 +466,38 @@ impl QueryParser {
             let field_name = field_entry.name().to_string();
             return Err(QueryParserError::FieldNotIndexed(field_name));
         }
+        if fuzzy_distance.is_some() {
+            match *field_type {
+                FieldType::Str(_) | FieldType::Json(_) => {}
+                _ => {
+                    let field_name = field_entry.name().to_string();
+                    return Err(QueryParserError::ExpectedTextField(field_name));
+                }
+            }
+        }
         match *field_type {
-            FieldType::I64(_) => {
-                let val: i64 = i64::from_str(phrase)?;
-                let term = Term::from_field_i64(field, val);
+            FieldType::I64(_)
+            | FieldType::U64(_)
+            | FieldType::F64(_)
+            | FieldType::Bool(_)
+            | FieldType::Date(_)
+            | FieldType::Bytes(_)
+            | FieldType::GeoPoint(_) => {
+                let term = self.compute_boundary_term(field, phrase)?;
                 Ok(Some(LogicalLiteral::Term(term)))
             }
-            FieldType::U64(_) => {
-                let val: u64 = u64::from_str(phrase)?;
-                let term = Term::from_field_u64(field, val);
-                Ok(Some(LogicalLiteral::Term(term)))
+            FieldType::Ip(_) => {
+                if phrase.contains('/') {
+                    let (lower, upper) = cidr_bounds(phrase)?;
+                    Ok(Some(LogicalLiteral::Range {
+                        field,
+                        lower: Bound::Included(Term::from_field_ip(field, lower)),
+                        upper: Bound::Included(Term::from_field_ip(field, upper)),
+                    }))
+                } else {
+                    let term = self.compute_boundary_term(field, phrase)?;
+                    Ok(Some(LogicalLiteral::Term(term)))
+                }
             }
             FieldType::Str(ref str_options) => {
                 if let Some(option) = str_options.get_indexing_options() {
@@ -193,11 +518,17 @@ impl QueryParser {
                     if terms.is_empty() {
                         Ok(None)
                     } else if terms.len() == 1 {
-                        Ok(Some(LogicalLiteral::Term(
-                            terms.into_iter().next().unwrap(),
-                        )))
+                        let term = terms.into_iter().next().unwrap();
+                        match fuzzy_distance {
+                            Some(distance) => Ok(Some(LogicalLiteral::Fuzzy {
+                                term,
+                                distance,
+                                prefix_length: 0,
+                            })),
+                            None => Ok(Some(LogicalLiteral::Term(term))),
+                        }
                     } else {
-                        Ok(Some(LogicalLiteral::Phrase(terms)))
+                        Ok(Some(LogicalLiteral::Phrase(terms, slop)))
                     }
                 } else {
                     // This should have been seen earlier really.
@@ -210,6 +541,81 @@ impl QueryParser {
                 let term = Term::from_field_text(field, phrase);
                 Ok(Some(LogicalLiteral::Term(term)))
             }
+            FieldType::Json(_) => Err(QueryParserError::ExpectedJsonPath(
+                field_entry.name().to_string(),
+            )),
+        }
+    }
+
+    /// Builds the query matching a leaf of a JSON object field.
+    ///
+    /// The type of the leaf is not known in advance, so the resulting
+    /// query matches any of the text, number or boolean leaves
+    /// the phrase can be interpreted as.
+    /// The fuzzy distance and the slop only apply to the text leaves.
+    fn compute_logical_ast_for_json_leaf(
+        &self,
+        field: Field,
+        json_path: &str,
+        phrase: &str,
+        fuzzy_distance: Option<u8>,
+        slop: u32,
+    ) -> Result<Option<LogicalAST>, QueryParserError> {
+        let field_entry = self.schema.get_field_entry(field);
+        let indexing_options = match *field_entry.field_type() {
+            FieldType::Json(ref json_object_options) => json_object_options.get_indexing_options(),
+            _ => None,
+        }.ok_or_else(|| QueryParserError::FieldNotIndexed(field_entry.name().to_string()))?;
+        let tokenizer = self.tokenizer_manager
+            .get(indexing_options.tokenizer())
+            .ok_or_else(|| {
+                QueryParserError::UnknownTokenizer(
+                    field_entry.name().to_string(),
+                    indexing_options.tokenizer().to_string(),
+                )
+            })?;
+        let mut terms: Vec<Term> = Vec::new();
+        let mut token_stream = tokenizer.token_stream(phrase);
+        token_stream.process(&mut |token| {
+            let term = Term::from_field_json_text(field, json_path, &token.text);
+            terms.push(term);
+        });
+        let mut literals: Vec<LogicalLiteral> = Vec::new();
+        if terms.len() == 1 {
+            let term = terms.into_iter().next().unwrap();
+            literals.push(match fuzzy_distance {
+                // The path and the type code of the term must match exactly:
+                // only the text is subject to edits.
+                Some(distance) => LogicalLiteral::Fuzzy {
+                    term,
+                    distance,
+                    prefix_length: json_path.chars().count() + 2,
+                },
+                None => LogicalLiteral::Term(term),
+            });
+        } else if terms.len() > 1 {
+            literals.push(LogicalLiteral::Phrase(terms, slop));
+        }
+        if let Ok(val) = i64::from_str(phrase) {
+            let term = Term::from_field_json_i64(field, json_path, val);
+            literals.push(LogicalLiteral::Term(term));
+        }
+        if let Ok(val) = f64::from_str(phrase) {
+            let term = Term::from_field_json_f64(field, json_path, val);
+            literals.push(LogicalLiteral::Term(term));
+        }
+        if let Ok(val) = bool::from_str(phrase) {
+            let term = Term::from_field_json_bool(field, json_path, val);
+            literals.push(LogicalLiteral::Term(term));
+        }
+        if literals.len() <= 1 {
+            Ok(literals.into_iter().next().map(LogicalAST::from))
+        } else {
+            let sub_asts = literals
+                .into_iter()
+                .map(|literal| (Occur::Should, LogicalAST::from(literal)))
+                .collect();
+            Ok(Some(LogicalAST::Clause(sub_asts)))
         }
     }
 
@@ -221,6 +627,17 @@ impl QueryParser {
         }
     }
 
+    /// Combines the logical ASTs of a query on several default fields.
+    fn combine_field_asts(&self, asts: Vec<LogicalAST>) -> LogicalAST {
+        if asts.len() == 1 {
+            return asts.into_iter().next().unwrap();
+        }
+        match self.default_fields_tie_breaker {
+            Some(tie_breaker) => LogicalAST::DisjunctionMax(asts, tie_breaker),
+            None => LogicalAST::Clause(asts.into_iter().map(|ast| (Occur::Should, ast)).collect()),
+        }
+    }
+
     fn compute_logical_ast_with_occur(
         &self,
         user_input_ast: UserInputAST,
@@ -234,7 +651,14 @@ impl QueryParser {
                     let new_occur = compose_occur(default_occur, occur);
                     logical_sub_queries.push((new_occur, sub_ast));
                 }
-                Ok((Occur::Should, LogicalAST::Clause(logical_sub_queries)))
+                let clause = LogicalAST::Clause(logical_sub_queries);
+                match self.minimum_should_match {
+                    Some(minimum_should_match) if !self.conjunction_by_default => Ok((
+                        Occur::Should,
+                        LogicalAST::MinimumShouldMatch(box clause, minimum_should_match),
+                    )),
+                    _ => Ok((Occur::Should, clause)),
+                }
             }
             UserInputAST::Not(subquery) => {
                 let (occur, logical_sub_queries) = self.compute_logical_ast_with_occur(*subquery)?;
@@ -244,9 +668,28 @@ impl QueryParser {
                 let (occur, logical_sub_queries) = self.compute_logical_ast_with_occur(*subquery)?;
                 Ok((compose_occur(Occur::Must, occur), logical_sub_queries))
             }
+            UserInputAST::Filter(subquery) => {
+                let (occur, logical_sub_queries) = self.compute_logical_ast_with_occur(*subquery)?;
+                Ok((compose_occur(Occur::Filter, occur), logical_sub_queries))
+            }
             UserInputAST::Leaf(literal) => {
                 let term_phrases: Vec<(Field, String)> = match literal.field_name {
                     Some(ref field_name) => {
+                        let is_address = |c: char| c == ':' || c == '/';
+                        if !literal.quoted && literal.phrase.contains(is_address) {
+                            self.check_ip_field(field_name)?;
+                        }
+                        if let Some((field, json_path)) = self.resolve_json_path(field_name) {
+                            let json_ast = self.compute_logical_ast_for_json_leaf(
+                                field,
+                                json_path,
+                                &literal.phrase,
+                                literal.fuzzy_distance,
+                                literal.slop,
+                            )?
+                                .ok_or(QueryParserError::SyntaxError)?;
+                            return Ok((Occur::Should, json_ast));
+                        }
                         let field = self.resolve_field_name(field_name)?;
                         vec![(field, literal.phrase.clone())]
                     }
@@ -263,40 +706,125 @@ impl QueryParser {
                 };
                 let mut asts: Vec<LogicalAST> = Vec::new();
                 for (field, phrase) in term_phrases {
-                    if let Some(ast) = self.compute_logical_ast_for_leaf(field, &phrase)? {
+                    if let Some(ast) = self.compute_logical_ast_for_leaf(
+                        field,
+                        &phrase,
+                        literal.fuzzy_distance,
+                        literal.slop,
+                    )? {
                         asts.push(LogicalAST::Leaf(box ast));
                     }
                 }
-                let result_ast = if asts.is_empty() {
+                if asts.is_empty() {
                     // this should never happen
                     return Err(QueryParserError::SyntaxError);
-                } else if asts.len() == 1 {
-                    asts[0].clone()
-                } else {
-                    LogicalAST::Clause(asts.into_iter().map(|ast| (Occur::Should, ast)).collect())
+                }
+                Ok((Occur::Should, self.combine_field_asts(asts)))
+            }
+            UserInputAST::Range(range) => {
+                let field = self.resolve_field_name(&range.field_name)?;
+                let field_entry = self.schema.get_field_entry(field);
+                // The fast field of a geo point cannot be searched by range.
+                let is_single_value_fast = match *field_entry.field_type() {
+                    FieldType::GeoPoint(_) => false,
+                    ref field_type => {
+                        int_fast_field_cardinality(field_type) == Some(Cardinality::SingleValue)
+                    }
+                };
+                if !field_entry.is_indexed() && !is_single_value_fast {
+                    let field_name = field_entry.name().to_string();
+                    return Err(QueryParserError::FieldNotIndexed(field_name));
+                }
+                let logical_literal = LogicalLiteral::Range {
+                    field,
+                    lower: self.resolve_bound(field, &range.lower)?,
+                    upper: self.resolve_bound(field, &range.upper)?,
+                };
+                Ok((Occur::Should, LogicalAST::from(logical_literal)))
+            }
+            UserInputAST::Regex(regex) => {
+                let field = self.resolve_field_name(&regex.field_name)?;
+                self.check_indexed_text_field(field)?;
+                let logical_literal = LogicalLiteral::Regex {
+                    field,
+                    pattern: regex.pattern,
+                };
+                Ok((Occur::Should, LogicalAST::from(logical_literal)))
+            }
+            UserInputAST::Exists(field_name) => {
+                let field = self.resolve_field_name(&field_name)?;
+                let field_entry = self.schema.get_field_entry(field);
+                let is_multivalued_fast = int_fast_field_cardinality(field_entry.field_type())
+                    == Some(Cardinality::MultiValues);
+                if !field_entry.is_indexed() && !is_multivalued_fast {
+                    let field_name = field_entry.name().to_string();
+                    return Err(QueryParserError::FieldNotIndexed(field_name));
+                }
+                Ok((Occur::Should, LogicalAST::from(LogicalLiteral::Exists(field))))
+            }
+            UserInputAST::Boost(subquery, boost) => {
+                let (occur, logical_sub_query) = self.compute_logical_ast_with_occur(*subquery)?;
+                Ok((occur, LogicalAST::Boost(box logical_sub_query, boost)))
+            }
+            UserInputAST::Wildcard(wildcard) => {
+                let fields: Vec<Field> = match wildcard.field_name {
+                    Some(ref field_name) => vec![self.resolve_field_name(field_name)?],
+                    None => {
+                        if self.default_fields.is_empty() {
+                            return Err(QueryParserError::NoDefaultFieldDeclared);
+                        }
+                        self.default_fields.clone()
+                    }
                 };
-                Ok((Occur::Should, result_ast))
+                let mut asts: Vec<LogicalAST> = Vec::new();
+                for field in fields {
+                    self.check_indexed_text_field(field)?;
+                    let logical_literal = compute_wildcard_literal(field, &wildcard.pattern);
+                    asts.push(LogicalAST::from(logical_literal));
+                }
+                Ok((Occur::Should, self.combine_field_asts(asts)))
             }
         }
     }
 }
 
+/// Builds the literal for a wildcard pattern.
+///
+/// Patterns whose only wildcard is a trailing `*` are
+/// searched for as a prefix.
+fn compute_wildcard_literal(field: Field, pattern: &str) -> LogicalLiteral {
+    let prefix = pattern.trim_right_matches('*');
+    if pattern.len() == prefix.len() + 1 && !prefix.contains(|c: char| c == '*' || c == '?') {
+        LogicalLiteral::Prefix(Term::from_field_text(field, prefix))
+    } else {
+        LogicalLiteral::Wildcard {
+            field,
+            pattern: pattern.to_string(),
+        }
+    }
+}
+
 /// Compose two occur values.
 fn compose_occur(left: Occur, right: Occur) -> Occur {
     match left {
         Occur::Should => right,
-        Occur::Must => {
+        Occur::Must => match right {
+            Occur::MustNot => Occur::MustNot,
+            Occur::Filter => Occur::Filter,
+            Occur::Must | Occur::Should => Occur::Must,
+        },
+        Occur::MustNot => {
             if right == Occur::MustNot {
-                Occur::MustNot
-            } else {
                 Occur::Must
+            } else {
+                Occur::MustNot
             }
         }
-        Occur::MustNot => {
+        Occur::Filter => {
             if right == Occur::MustNot {
-                Occur::Must
-            } else {
                 Occur::MustNot
+            } else {
+                Occur::Filter
             }
         }
     }
@@ -305,26 +833,56 @@ fn compose_occur(left: Occur, right: Occur) -> Occur {
 fn convert_literal_to_query(logical_literal: LogicalLiteral) -> Box<Query> {
     match logical_literal {
         LogicalLiteral::Term(term) => box TermQuery::new(term, IndexRecordOption::WithFreqs),
-        LogicalLiteral::Phrase(terms) => box PhraseQuery::new(terms),
+        LogicalLiteral::Phrase(terms, slop) => box PhraseQuery::new(terms).with_slop(slop),
+        LogicalLiteral::Fuzzy {
+            term,
+            distance,
+            prefix_length,
+        } => box FuzzyTermQuery::new(term, distance, true).with_prefix_length(prefix_length),
+        LogicalLiteral::Range {
+            field,
+            lower,
+            upper,
+        } => box RangeQuery::new_term_bounds(field, lower, upper),
+        LogicalLiteral::Regex { field, pattern } => box RegexQuery::new(field, &pattern),
+        LogicalLiteral::Prefix(term) => box PrefixQuery::new(term),
+        LogicalLiteral::Wildcard { field, pattern } => box WildcardQuery::new(field, &pattern),
+        LogicalLiteral::Exists(field) => box ExistsQuery::new(field),
     }
 }
 
+fn convert_clause_to_query(clause: Vec<(Occur, LogicalAST)>) -> BooleanQuery {
+    let occur_subqueries = clause
+        .into_iter()
+        .map(|(occur, subquery)| (occur, convert_to_query(subquery)))
+        .collect::<Vec<_>>();
+    BooleanQuery::from(occur_subqueries)
+}
+
 fn convert_to_query(logical_ast: LogicalAST) -> Box<Query> {
     match logical_ast {
-        LogicalAST::Clause(clause) => {
-            let occur_subqueries = clause
-                .into_iter()
-                .map(|(occur, subquery)| (occur, convert_to_query(subquery)))
-                .collect::<Vec<_>>();
-            box BooleanQuery::from(occur_subqueries)
-        }
+        LogicalAST::Clause(clause) => box convert_clause_to_query(clause),
+        LogicalAST::MinimumShouldMatch(logical_ast, minimum_should_match) => match *logical_ast {
+            LogicalAST::Clause(clause) => {
+                box convert_clause_to_query(clause).with_minimum_should_match(minimum_should_match)
+            }
+            logical_ast => convert_to_query(logical_ast),
+        },
         LogicalAST::Leaf(logical_literal) => convert_literal_to_query(*logical_literal),
+        LogicalAST::DisjunctionMax(disjuncts, tie_breaker) => {
+            let disjuncts = disjuncts.into_iter().map(convert_to_query).collect();
+            box DisjunctionMaxQuery::new(disjuncts, tie_breaker)
+        }
+        LogicalAST::Boost(logical_ast, boost) => {
+            box BoostQuery::new(convert_to_query(*logical_ast), boost)
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use schema::{SchemaBuilder, Term, INT_INDEXED, STORED, STRING, TEXT};
+    use schema::{BytesOptions, GeoPointOptions, IpOptions, JsonObjectOptions, SchemaBuilder,
+                 Term, INT_INDEXED, STORED, STRING, TEXT};
     use tokenizer::TokenizerManager;
     use query::Query;
     use schema::Field;
@@ -334,6 +892,8 @@ mod test {
     use Index;
     use tokenizer::SimpleTokenizer;
     use super::super::logical_ast::*;
+    use std::collections::Bound;
+    use chrono::{TimeZone, Utc};
 
     fn make_query_parser() -> QueryParser {
         let mut schema_builder = SchemaBuilder::default();
@@ -345,6 +905,15 @@ mod test {
         schema_builder.add_text_field("notindexed_u64", STORED);
         schema_builder.add_text_field("notindexed_i64", STORED);
         schema_builder.add_text_field("nottokenized", STRING);
+        schema_builder.add_f64_field("float", INT_INDEXED);
+        schema_builder.add_date_field("date", INT_INDEXED);
+        schema_builder.add_bytes_field("bytes", BytesOptions::default().set_indexed());
+        schema_builder.add_bool_field("flag", INT_INDEXED);
+        schema_builder.add_ip_field("src", IpOptions::default().set_indexed());
+        schema_builder.add_json_object_field(
+            "attributes",
+            JsonObjectOptions::default().set_indexing_options(TextFieldIndexing::default()),
+        );
         let schema = schema_builder.build();
         let default_fields = vec![title, text];
         let tokenizer_manager = TokenizerManager::default();
@@ -453,6 +1022,213 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_floats() {
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("float:2324").is_ok());
+        assert!(query_parser.parse_query("float:\"-1.5e3\"").is_ok());
+        assert_matches!(
+            query_parser.parse_query("float:1.5a"),
+            Err(QueryParserError::ExpectedFloat(_))
+        );
+        test_parse_query_to_logical_ast_helper(
+            "float:1.5",
+            &format!("{:?}", Term::from_field_f64(Field(8u32), 1.5f64)),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "float:-0.25",
+            &format!("{:?}", Term::from_field_f64(Field(8u32), -0.25f64)),
+            false,
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_range_fast_geo_point() {
+        let mut schema_builder = SchemaBuilder::default();
+        schema_builder.add_geo_point_field("location", GeoPointOptions::default().set_fast());
+        let schema = schema_builder.build();
+        let query_parser = QueryParser::new(schema, vec![], TokenizerManager::default());
+        assert_matches!(
+            query_parser.parse_query("location:[0 TO 1]"),
+            Err(QueryParserError::FieldNotIndexed(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_ranges() {
+        let query_parser = make_query_parser();
+        test_parse_query_to_logical_ast_helper(
+            "unsigned:[1960 TO 1970}",
+            &format!(
+                "({:?} TO {:?})",
+                Bound::Included(Term::from_field_u64(Field(3u32), 1960)),
+                Bound::Excluded(Term::from_field_u64(Field(3u32), 1970))
+            ),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "signed:{-5 TO *]",
+            &format!(
+                "({:?} TO {:?})",
+                Bound::Excluded(Term::from_field_i64(Field(2u32), -5)),
+                Bound::Unbounded::<Term>
+            ),
+            false,
+        );
+        let date = Utc.ymd(2020, 1, 1).and_hms(0, 0, 0);
+        test_parse_query_to_logical_ast_helper(
+            "date:[2020-01-01T00:00:00Z TO *]",
+            &format!(
+                "({:?} TO {:?})",
+                Bound::Included(Term::from_field_date(Field(9u32), &date)),
+                Bound::Unbounded::<Term>
+            ),
+            false,
+        );
+        assert!(query_parser.parse_query("title:[a TO b]").is_ok());
+        assert_matches!(
+            query_parser.parse_query("date:[2020-01-01 TO *]"),
+            Err(QueryParserError::DateFormatError(_))
+        );
+        assert_matches!(
+            query_parser.parse_query("unsigned:[a TO 3]"),
+            Err(QueryParserError::ExpectedInt(_))
+        );
+        assert_matches!(
+            query_parser.parse_query("notindexed_u64:[1 TO 3]"),
+            Err(QueryParserError::FieldNotIndexed(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_date() {
+        let query_parser = make_query_parser();
+        let date = Utc.ymd(2020, 1, 1).and_hms(12, 0, 0);
+        test_parse_query_to_logical_ast_helper(
+            "date:\"2020-01-01T13:00:00+01:00\"",
+            &format!("{:?}", Term::from_field_date(Field(9u32), &date)),
+            false,
+        );
+        assert_matches!(
+            query_parser.parse_query("date:2020"),
+            Err(QueryParserError::DateFormatError(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_bytes() {
+        let query_parser = make_query_parser();
+        test_parse_query_to_logical_ast_helper(
+            "bytes:\"AAEC/w==\"",
+            &format!("{:?}", Term::from_field_bytes(Field(10u32), &[0u8, 1u8, 2u8, 255u8])),
+            false,
+        );
+        assert_matches!(
+            query_parser.parse_query("bytes:\"AA*C\""),
+            Err(QueryParserError::ExpectedBase64(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_bool() {
+        let query_parser = make_query_parser();
+        test_parse_query_to_logical_ast_helper(
+            "flag:true",
+            &format!("{:?}", Term::from_field_bool(Field(11u32), true)),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "flag:false",
+            &format!("{:?}", Term::from_field_bool(Field(11u32), false)),
+            false,
+        );
+        assert_matches!(
+            query_parser.parse_query("flag:yes"),
+            Err(QueryParserError::ExpectedBool(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_ip() {
+        let query_parser = make_query_parser();
+        test_parse_query_to_logical_ast_helper(
+            "src:10.0.0.1",
+            &format!(
+                "{:?}",
+                Term::from_field_ip(Field(12u32), "::ffff:10.0.0.1".parse().unwrap())
+            ),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "src:::1",
+            &format!("{:?}", Term::from_field_ip(Field(12u32), "::1".parse().unwrap())),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "src:10.0.0.0/8",
+            &format!(
+                "({:?} TO {:?})",
+                Bound::Included(Term::from_field_ip(
+                    Field(12u32),
+                    "::ffff:10.0.0.0".parse().unwrap()
+                )),
+                Bound::Included(Term::from_field_ip(
+                    Field(12u32),
+                    "::ffff:10.255.255.255".parse().unwrap()
+                ))
+            ),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "src:2001:db8::/32",
+            &format!(
+                "({:?} TO {:?})",
+                Bound::Included(Term::from_field_ip(
+                    Field(12u32),
+                    "2001:db8::".parse().unwrap()
+                )),
+                Bound::Included(Term::from_field_ip(
+                    Field(12u32),
+                    "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff".parse().unwrap()
+                ))
+            ),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "src:[10.0.0.1 TO 10.0.0.255]",
+            &format!(
+                "({:?} TO {:?})",
+                Bound::Included(Term::from_field_ip(
+                    Field(12u32),
+                    "::ffff:10.0.0.1".parse().unwrap()
+                )),
+                Bound::Included(Term::from_field_ip(
+                    Field(12u32),
+                    "::ffff:10.0.0.255".parse().unwrap()
+                ))
+            ),
+            false,
+        );
+        assert_matches!(
+            query_parser.parse_query("src:10.0.0.256"),
+            Err(QueryParserError::ExpectedIp(_))
+        );
+        assert_matches!(
+            query_parser.parse_query("src:10.0.0.0/33"),
+            Err(QueryParserError::InvalidCidrPrefix(_))
+        );
+        assert_matches!(
+            query_parser.parse_query("title:b:c"),
+            Err(QueryParserError::SyntaxError)
+        );
+        assert_matches!(
+            query_parser.parse_query("title:a/b"),
+            Err(QueryParserError::SyntaxError)
+        );
+        assert!(query_parser.parse_query("title:\"b:c\"").is_ok());
+    }
+
     #[test]
     pub fn test_parse_query_to_ast_disjunction() {
         test_parse_query_to_logical_ast_helper(
@@ -492,6 +1268,208 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_fuzzy() {
+        test_parse_query_to_logical_ast_helper(
+            "title:toto~1",
+            "Term([0, 0, 0, 0, 116, 111, 116, 111])~1",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "toto~2",
+            "(Term([0, 0, 0, 0, 116, 111, 116, 111])~2 \
+             Term([0, 0, 0, 1, 116, 111, 116, 111])~2)",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "attributes.color:red~1",
+            &format!("{:?}~1", Term::from_field_json_text(Field(13u32), "color", "red")),
+            false,
+        );
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("title:toto~1").is_ok());
+        assert_matches!(
+            query_parser.parse_query("unsigned:3~1"),
+            Err(QueryParserError::ExpectedTextField(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_slop() {
+        test_parse_query_to_logical_ast_helper(
+            "title:\"a b\"~2",
+            "\"[Term([0, 0, 0, 0, 97]), \
+             Term([0, 0, 0, 0, 98])]\"~2",
+            false,
+        );
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("\"quick fox\"~2").is_ok());
+    }
+
+    #[test]
+    pub fn test_parse_query_regex() {
+        test_parse_query_to_logical_ast_helper(
+            "nottokenized:/AB-[0-9]{4}/",
+            "/AB-[0-9]{4}/",
+            false,
+        );
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("title:/to.*/").is_ok());
+        assert_matches!(
+            query_parser.parse_query("unsigned:/3.*/"),
+            Err(QueryParserError::ExpectedTextField(_))
+        );
+        assert_matches!(
+            query_parser.parse_query("notindexed_text:/a.*/"),
+            Err(QueryParserError::FieldNotIndexed(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_wildcard() {
+        test_parse_query_to_logical_ast_helper(
+            "title:to*",
+            "Term([0, 0, 0, 0, 116, 111])*",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "to*",
+            "(Term([0, 0, 0, 0, 116, 111])* Term([0, 0, 0, 1, 116, 111])*)",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper("title:t?to", "t?to", false);
+        test_parse_query_to_logical_ast_helper("title:to**", "to**", false);
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("title:t*o").is_ok());
+        assert_matches!(
+            query_parser.parse_query("unsigned:3*"),
+            Err(QueryParserError::ExpectedTextField(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_exists() {
+        test_parse_query_to_logical_ast_helper("_exists_:title", "_exists_:Field(0)", false);
+        test_parse_query_to_logical_ast_helper(
+            "a -_exists_:text",
+            "((Term([0, 0, 0, 0, 97]) Term([0, 0, 0, 1, 97])) -_exists_:Field(1))",
+            false,
+        );
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("_exists_:unsigned").is_ok());
+        assert_matches!(
+            query_parser.parse_query("_exists_:notindexed_text"),
+            Err(QueryParserError::FieldNotIndexed(_))
+        );
+        assert_matches!(
+            query_parser.parse_query("_exists_:nonexistingfield"),
+            Err(QueryParserError::FieldDoesNotExist(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_boost() {
+        test_parse_query_to_logical_ast_helper(
+            "title:foo^2.0",
+            "(Term([0, 0, 0, 0, 102, 111, 111]))^2",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "-title:foo^0.5 text:a",
+            "(-(Term([0, 0, 0, 0, 102, 111, 111]))^0.5 Term([0, 0, 0, 1, 97]))",
+            false,
+        );
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("title:foo^2 text:foo").is_ok());
+        assert_matches!(
+            query_parser.parse_query("title:foo^"),
+            Err(QueryParserError::SyntaxError)
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_disjunction_max() {
+        let mut query_parser = make_query_parser();
+        query_parser.set_disjunction_max_across_fields(0.1);
+        let query = query_parser.parse_query_to_logical_ast("a title:b").unwrap();
+        assert_eq!(
+            format!("{:?}", query),
+            "(dismax(Term([0, 0, 0, 0, 97]) Term([0, 0, 0, 1, 97])) Term([0, 0, 0, 0, 98]))"
+        );
+        let query = query_parser.parse_query_to_logical_ast("to*").unwrap();
+        assert_eq!(
+            format!("{:?}", query),
+            "dismax(Term([0, 0, 0, 0, 116, 111])* Term([0, 0, 0, 1, 116, 111])*)"
+        );
+        assert!(query_parser.parse_query("a b").is_ok());
+    }
+
+    #[test]
+    pub fn test_parse_query_filter() {
+        test_parse_query_to_logical_ast_helper(
+            "#title:a text:b",
+            "(#Term([0, 0, 0, 0, 97]) Term([0, 0, 0, 1, 98]))",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "#title:a text:b",
+            "(#Term([0, 0, 0, 0, 97]) +Term([0, 0, 0, 1, 98]))",
+            true,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "#title:a",
+            "(#Term([0, 0, 0, 0, 97]))",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "-#title:a text:b",
+            "(-Term([0, 0, 0, 0, 97]) Term([0, 0, 0, 1, 98]))",
+            false,
+        );
+        let mut query_parser = make_query_parser();
+        query_parser.set_filter_prefix('!');
+        let query = query_parser.parse_query_to_logical_ast("!title:a").unwrap();
+        assert_eq!(format!("{:?}", query), "(#Term([0, 0, 0, 0, 97]))");
+        assert!(query_parser.parse_query("text:b !title:a").is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_parse_query_filter_prefix_reserved() {
+        make_query_parser().set_filter_prefix('+');
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_parse_query_filter_prefix_exists() {
+        make_query_parser().set_filter_prefix('_');
+    }
+
+    #[test]
+    pub fn test_parse_query_minimum_should_match() {
+        let mut query_parser = make_query_parser();
+        query_parser.set_minimum_should_match(MinimumShouldMatch::Percentage(60));
+        let query = query_parser
+            .parse_query_to_logical_ast("title:a title:b (title:c title:d)")
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", query),
+            "(Term([0, 0, 0, 0, 97]) Term([0, 0, 0, 0, 98]) \
+             (Term([0, 0, 0, 0, 99]) Term([0, 0, 0, 0, 100]))~60%)~60%"
+        );
+        let query = query_parser.parse_query_to_logical_ast("title:a").unwrap();
+        assert_eq!(format!("{:?}", query), "Term([0, 0, 0, 0, 97])");
+        assert!(query_parser.parse_query("a b c").is_ok());
+        query_parser.set_conjunction_by_default();
+        let query = query_parser
+            .parse_query_to_logical_ast("title:a title:b")
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", query),
+            "(+Term([0, 0, 0, 0, 97]) +Term([0, 0, 0, 0, 98]))"
+        );
+    }
+
     #[test]
     pub fn test_query_parser_field_does_not_exist() {
         let query_parser = make_query_parser();
@@ -545,6 +1523,44 @@ mod test {
         assert!(query_parser.parse_query("title:\"happy tax\"").is_ok());
     }
 
+    #[test]
+    pub fn test_parse_query_json_path() {
+        let attributes = Field(13u32);
+        test_parse_query_to_logical_ast_helper(
+            "attributes.color:red",
+            &format!("{:?}", Term::from_field_json_text(attributes, "color", "red")),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "attributes.dims.width:3",
+            &format!(
+                "({:?} {:?} {:?})",
+                Term::from_field_json_text(attributes, "dims.width", "3"),
+                Term::from_field_json_i64(attributes, "dims.width", 3i64),
+                Term::from_field_json_f64(attributes, "dims.width", 3f64)
+            ),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "attributes.available:true",
+            &format!(
+                "({:?} {:?})",
+                Term::from_field_json_text(attributes, "available", "true"),
+                Term::from_field_json_bool(attributes, "available", true)
+            ),
+            false,
+        );
+        let query_parser = make_query_parser();
+        assert_matches!(
+            query_parser.parse_query("attributes:red"),
+            Err(QueryParserError::ExpectedJsonPath(_))
+        );
+        assert_matches!(
+            query_parser.parse_query("title.color:red"),
+            Err(QueryParserError::FieldDoesNotExist(_))
+        );
+    }
+
     #[test]
     pub fn test_query_parser_expected_int() {
         let query_parser = make_query_parser();
@@ -3,6 +3,10 @@ use std::fmt;
 pub struct UserInputLiteral {
     pub field_name: Option<String>,
     pub phrase: String,
+    /// Whether the value was quoted, rather than written as a single word.
+    pub quoted: bool,
+    pub fuzzy_distance: Option<u8>,
+    pub slop: u32,
 }
 
 impl fmt::Debug for UserInputLiteral {
@@ -10,6 +14,77 @@ impl fmt::Debug for UserInputLiteral {
         match self.field_name {
             Some(ref field_name) => write!(formatter, "{}:\"{}\"", field_name, self.phrase),
             None => write!(formatter, "\"{}\"", self.phrase),
+        }?;
+        if let Some(distance) = self.fuzzy_distance {
+            write!(formatter, "~{}", distance)?;
+        }
+        if self.slop > 0 {
+            write!(formatter, "~{}", self.slop)?;
+        }
+        Ok(())
+    }
+}
+
+pub enum UserInputBound {
+    Inclusive(String),
+    Exclusive(String),
+    Unbounded,
+}
+
+impl UserInputBound {
+    fn display_lower(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            UserInputBound::Inclusive(ref word) => write!(formatter, "[\"{}\"", word),
+            UserInputBound::Exclusive(ref word) => write!(formatter, "{{\"{}\"", word),
+            UserInputBound::Unbounded => write!(formatter, "{{\"*\""),
+        }
+    }
+
+    fn display_upper(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            UserInputBound::Inclusive(ref word) => write!(formatter, "\"{}\"]", word),
+            UserInputBound::Exclusive(ref word) => write!(formatter, "\"{}\"}}", word),
+            UserInputBound::Unbounded => write!(formatter, "\"*\"}}"),
+        }
+    }
+}
+
+pub struct UserInputRange {
+    pub field_name: String,
+    pub lower: UserInputBound,
+    pub upper: UserInputBound,
+}
+
+impl fmt::Debug for UserInputRange {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(formatter, "{}:", self.field_name)?;
+        self.lower.display_lower(formatter)?;
+        write!(formatter, " TO ")?;
+        self.upper.display_upper(formatter)
+    }
+}
+
+pub struct UserInputRegex {
+    pub field_name: String,
+    pub pattern: String,
+}
+
+impl fmt::Debug for UserInputRegex {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(formatter, "{}:/{}/", self.field_name, self.pattern)
+    }
+}
+
+pub struct UserInputWildcard {
+    pub field_name: Option<String>,
+    pub pattern: String,
+}
+
+impl fmt::Debug for UserInputWildcard {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self.field_name {
+            Some(ref field_name) => write!(formatter, "{}:{}", field_name, self.pattern),
+            None => write!(formatter, "{}", self.pattern),
         }
     }
 }
@@ -18,7 +93,13 @@ pub enum UserInputAST {
     Clause(Vec<Box<UserInputAST>>),
     Not(Box<UserInputAST>),
     Must(Box<UserInputAST>),
+    Filter(Box<UserInputAST>),
     Leaf(Box<UserInputLiteral>),
+    Range(Box<UserInputRange>),
+    Regex(Box<UserInputRegex>),
+    Wildcard(Box<UserInputWildcard>),
+    Exists(String),
+    Boost(Box<UserInputAST>, f32),
 }
 
 impl From<UserInputLiteral> for UserInputAST {
@@ -27,6 +108,24 @@ impl From<UserInputLiteral> for UserInputAST {
     }
 }
 
+impl From<UserInputRange> for UserInputAST {
+    fn from(range: UserInputRange) -> UserInputAST {
+        UserInputAST::Range(box range)
+    }
+}
+
+impl From<UserInputRegex> for UserInputAST {
+    fn from(regex: UserInputRegex) -> UserInputAST {
+        UserInputAST::Regex(box regex)
+    }
+}
+
+impl From<UserInputWildcard> for UserInputAST {
+    fn from(wildcard: UserInputWildcard) -> UserInputAST {
+        UserInputAST::Wildcard(box wildcard)
+    }
+}
+
 impl fmt::Debug for UserInputAST {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
@@ -45,7 +144,15 @@ impl fmt::Debug for UserInputAST {
                 Ok(())
             }
             UserInputAST::Not(ref subquery) => write!(formatter, "-({:?})", subquery),
+            UserInputAST::Filter(ref subquery) => write!(formatter, "#({:?})", subquery),
             UserInputAST::Leaf(ref subquery) => write!(formatter, "{:?}", subquery),
+            UserInputAST::Range(ref range) => write!(formatter, "{:?}", range),
+            UserInputAST::Regex(ref regex) => write!(formatter, "{:?}", regex),
+            UserInputAST::Wildcard(ref wildcard) => write!(formatter, "{:?}", wildcard),
+            UserInputAST::Exists(ref field_name) => write!(formatter, "_exists_:{}", field_name),
+            UserInputAST::Boost(ref subquery, boost) => {
+                write!(formatter, "({:?})^{}", subquery, boost)
+            }
         }
     }
 }
@@ -1,5 +1,7 @@
-use schema::{Field, IndexRecordOption, Term};
+use schema::{Cardinality, Field, FieldType, IndexRecordOption, Term};
 use query::{Query, Scorer, Weight};
+use query::fast_field_range_query::FastFieldRangeWeight;
+use fastfield::int_fast_field_cardinality;
 use termdict::{TermDictionary, TermStreamer, TermStreamerBuilder};
 use core::SegmentReader;
 use common::BitSet;
@@ -9,11 +11,14 @@ use query::BitSetDocSet;
 use query::ConstScorer;
 use std::collections::Bound;
 use std::collections::range::RangeArgument;
+use DateTime;
+use common;
+use std::net::IpAddr;
 
-fn map_bound<TFrom, Transform: Fn(TFrom) -> Vec<u8>>(
+pub(crate) fn map_bound<TFrom, TTo, Transform: Fn(TFrom) -> TTo>(
     bound: Bound<TFrom>,
     transform: &Transform,
-) -> Bound<Vec<u8>> {
+) -> Bound<TTo> {
     use self::Bound::*;
     match bound {
         Excluded(from_val) => Excluded(transform(from_val)),
@@ -31,6 +36,16 @@ fn map_bound<TFrom, Transform: Fn(TFrom) -> Vec<u8>>(
 /// The current implement will iterate over the terms within the range
 /// and append all of the document cross into a `BitSet`.
 ///
+/// If the field is not indexed but is a single-valued int fast field,
+/// the query is run as a [`FastFieldRangeQuery`](./struct.FastFieldRangeQuery.html)
+/// instead, scanning the values of the fast field. (Geo points are
+/// not supported, as their fast field holds an interleaved encoding.)
+///
+/// In that case, the documents without any value for the field are read
+/// as having the default value of the field type (`0`, or `0.0` for `f64`
+/// fields): a range containing this value, such as `[-1 TO 1]`,
+/// matches them as well.
+///
 /// # Example
 ///
 /// ```rust
@@ -113,6 +128,66 @@ impl RangeQuery {
         }
     }
 
+    /// Create a new `RangeQuery` over a `f64` field.
+    pub fn new_f64<TRangeArgument: RangeArgument<f64>>(
+        field: Field,
+        range: TRangeArgument,
+    ) -> RangeQuery {
+        let make_term_val = |val: &f64| Term::from_field_f64(field, *val).value_bytes().to_owned();
+        RangeQuery {
+            field,
+            left_bound: map_bound(range.start(), &make_term_val),
+            right_bound: map_bound(range.end(), &make_term_val),
+        }
+    }
+
+    /// Create a new `RangeQuery` over a `Date` field.
+    pub fn new_date<TRangeArgument: RangeArgument<DateTime>>(
+        field: Field,
+        range: TRangeArgument,
+    ) -> RangeQuery {
+        let make_term_val =
+            |val: &DateTime| Term::from_field_date(field, val).value_bytes().to_owned();
+        RangeQuery {
+            field,
+            left_bound: map_bound(range.start(), &make_term_val),
+            right_bound: map_bound(range.end(), &make_term_val),
+        }
+    }
+
+    /// Create a new `RangeQuery` over an `Ip` field.
+    ///
+    /// IPv4 addresses are mapped to IPv6 (`::ffff:a.b.c.d`)
+    /// before being compared.
+    pub fn new_ip<TRangeArgument: RangeArgument<IpAddr>>(
+        field: Field,
+        range: TRangeArgument,
+    ) -> RangeQuery {
+        let make_term_val = |val: &IpAddr| {
+            Term::from_field_ip(field, common::ip_to_ipv6(*val))
+                .value_bytes()
+                .to_owned()
+        };
+        RangeQuery {
+            field,
+            left_bound: map_bound(range.start(), &make_term_val),
+            right_bound: map_bound(range.end(), &make_term_val),
+        }
+    }
+
+    /// Create a new `RangeQuery` given two bounds expressed as `Term`s.
+    ///
+    /// The terms are expected to belong to `field`, and to
+    /// be encoded according to its type.
+    pub fn new_term_bounds(field: Field, left: Bound<Term>, right: Bound<Term>) -> RangeQuery {
+        let make_term_val = |term: Term| term.value_bytes().to_owned();
+        RangeQuery {
+            field,
+            left_bound: map_bound(left, &make_term_val),
+            right_bound: map_bound(right, &make_term_val),
+        }
+    }
+
     /// Create a new `RangeQuery` over a `Str` field.
     pub fn new_str<'b, TRangeArgument: RangeArgument<&'b str>>(
         field: Field,
@@ -166,6 +241,21 @@ impl RangeWeight {
 
 impl Weight for RangeWeight {
     fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let field_entry = reader.schema().get_field_entry(self.field);
+        let is_single_value_fast = match *field_entry.field_type() {
+            FieldType::GeoPoint(_) => false,
+            ref field_type => {
+                int_fast_field_cardinality(field_type) == Some(Cardinality::SingleValue)
+            }
+        };
+        if !field_entry.is_indexed() && is_single_value_fast {
+            return FastFieldRangeWeight::from_term_bounds(
+                self.field,
+                &self.left_bound,
+                &self.right_bound,
+            )?.scorer(reader);
+        }
+
         let max_doc = reader.max_doc();
         let mut doc_bitset = BitSet::with_max_value(max_doc);
 
@@ -191,12 +281,14 @@ impl Weight for RangeWeight {
 mod tests {
 
     use Index;
-    use schema::{Document, Field, SchemaBuilder, INT_INDEXED};
+    use schema::{Document, Field, IpOptions, SchemaBuilder, INT_INDEXED};
     use collector::CountCollector;
     use std::collections::Bound;
     use query::Query;
     use Result;
     use super::RangeQuery;
+    use chrono::{TimeZone, Utc};
+    use std::net::{IpAddr, Ipv4Addr};
 
     #[test]
     fn test_range_query_simple() {
@@ -284,4 +376,132 @@ mod tests {
         assert_eq!(count_multiples(RangeQuery::new_i64(int_field, 9..)), 91);
     }
 
+    #[test]
+    fn test_range_query_f64() {
+        let float_field: Field;
+        let schema = {
+            let mut schema_builder = SchemaBuilder::new();
+            float_field = schema_builder.add_f64_field("floatfield", INT_INDEXED);
+            schema_builder.build()
+        };
+
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 6_000_000).unwrap();
+            for i in -50..50 {
+                let mut doc = Document::new();
+                doc.add_f64(float_field, i as f64 / 4f64);
+                index_writer.add_document(doc);
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |range_query: RangeQuery| {
+            let mut count_collector = CountCollector::default();
+            range_query
+                .search(&*searcher, &mut count_collector)
+                .unwrap();
+            count_collector.count()
+        };
+
+        assert_eq!(count(RangeQuery::new_f64(float_field, -1f64..1f64)), 8);
+        assert_eq!(
+            count(RangeQuery::new_f64(
+                float_field,
+                (Bound::Excluded(-1f64), Bound::Included(1f64))
+            )),
+            8
+        );
+        assert_eq!(count(RangeQuery::new_f64(float_field, ..0f64)), 50);
+        assert_eq!(count(RangeQuery::new_f64(float_field, 0.1f64..)), 49);
+    }
+
+    #[test]
+    fn test_range_query_date() {
+        let date_field: Field;
+        let schema = {
+            let mut schema_builder = SchemaBuilder::new();
+            date_field = schema_builder.add_date_field("date", INT_INDEXED);
+            schema_builder.build()
+        };
+
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 6_000_000).unwrap();
+            for day in 1..31 {
+                let mut doc = Document::new();
+                doc.add_date(date_field, &Utc.ymd(2018, 1, day).and_hms(12, 0, 0));
+                index_writer.add_document(doc);
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |range_query: RangeQuery| {
+            let mut count_collector = CountCollector::default();
+            range_query
+                .search(&*searcher, &mut count_collector)
+                .unwrap();
+            count_collector.count()
+        };
+
+        let start = Utc.ymd(2018, 1, 10).and_hms(0, 0, 0);
+        let end = Utc.ymd(2018, 1, 20).and_hms(12, 0, 0);
+        assert_eq!(count(RangeQuery::new_date(date_field, start..end)), 10);
+        assert_eq!(
+            count(RangeQuery::new_date(
+                date_field,
+                (Bound::Included(start), Bound::Included(end))
+            )),
+            11
+        );
+        assert_eq!(count(RangeQuery::new_date(date_field, end..)), 11);
+    }
+
+    #[test]
+    fn test_range_query_ip() {
+        let ip_field: Field;
+        let schema = {
+            let mut schema_builder = SchemaBuilder::new();
+            ip_field = schema_builder.add_ip_field("src", IpOptions::default().set_indexed());
+            schema_builder.build()
+        };
+
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 6_000_000).unwrap();
+            for i in 0..20u8 {
+                let mut doc = Document::new();
+                doc.add_ip(ip_field, IpAddr::V4(Ipv4Addr::new(10, 0, i, 1)));
+                index_writer.add_document(doc);
+            }
+            let mut doc = Document::new();
+            doc.add_ip(ip_field, "2001:db8::1".parse().unwrap());
+            index_writer.add_document(doc);
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |range_query: RangeQuery| {
+            let mut count_collector = CountCollector::default();
+            range_query
+                .search(&*searcher, &mut count_collector)
+                .unwrap();
+            count_collector.count()
+        };
+
+        let start = IpAddr::V4(Ipv4Addr::new(10, 0, 5, 0));
+        let end = IpAddr::V4(Ipv4Addr::new(10, 0, 10, 1));
+        assert_eq!(count(RangeQuery::new_ip(ip_field, start..end)), 5);
+        assert_eq!(
+            count(RangeQuery::new_ip(
+                ip_field,
+                (Bound::Included(start), Bound::Included(end))
+            )),
+            6
+        );
+        assert_eq!(count(RangeQuery::new_ip(ip_field, start..)), 16);
+    }
+
 }
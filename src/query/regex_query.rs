@@ -0,0 +1,155 @@
+use schema::Field;
+use query::{Query, Weight};
+use query::automaton_weight::AutomatonWeight;
+use core::Searcher;
+use error::ErrorKind;
+use fst_regex::Regex;
+use Result;
+
+/// `RegexQuery` matches all of the documents containing
+/// a term matching a regular expression.
+///
+/// The regular expression must match the whole term, as if it were
+/// surrounded by `^` and `$`. Anchors, word boundaries and
+/// lazy repetitions are not supported.
+///
+/// Since the expression is matched against the terms of the field,
+/// it is best suited for untokenized text fields, such as
+/// identifiers or SKUs.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// # Implementation
+///
+/// The regular expression is compiled into an automaton, which is
+/// intersected with the `fst` of the term dictionary of each segment.
+/// Only the matching terms are visited, and their documents
+/// are appended into a `BitSet`.
+///
+/// # Example
+///
+/// ```rust
+///
+/// # #[macro_use]
+/// # extern crate tantivy;
+/// # use tantivy::Index;
+/// # use tantivy::schema::{SchemaBuilder, STRING};
+/// # use tantivy::collector::CountCollector;
+/// # use tantivy::query::{Query, RegexQuery};
+/// # use tantivy::Result;
+/// #
+/// # fn run() -> Result<()> {
+/// #     let mut schema_builder = SchemaBuilder::new();
+/// #     let sku_field = schema_builder.add_text_field("sku", STRING);
+/// #     let schema = schema_builder.build();
+/// #
+/// #     let index = Index::create_in_ram(schema);
+/// #     {
+/// #         let mut index_writer = index.writer_with_num_threads(1, 6_000_000).unwrap();
+/// #         index_writer.add_document(doc!(sku_field => "AB-1234"));
+/// #         index_writer.add_document(doc!(sku_field => "AB-123"));
+/// #         index_writer.add_document(doc!(sku_field => "CD-5678"));
+/// #         index_writer.commit().unwrap();
+/// #     }
+/// #   index.load_searchers()?;
+/// let searcher = index.searcher();
+///
+/// let query = RegexQuery::new(sku_field, "AB-[0-9]{4}");
+///
+/// let mut count_collector = CountCollector::default();
+/// query.search(&*searcher, &mut count_collector)?;
+///
+/// #     assert_eq!(count_collector.count(), 1);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #   run().unwrap()
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RegexQuery {
+    field: Field,
+    regex_pattern: String,
+}
+
+impl RegexQuery {
+    /// Creates a new `RegexQuery`, matching the terms of `field`
+    /// that match `regex_pattern`.
+    ///
+    /// The pattern is only compiled when the query is executed.
+    /// An invalid pattern then results in an `InvalidArgument` error.
+    pub fn new(field: Field, regex_pattern: &str) -> RegexQuery {
+        RegexQuery {
+            field,
+            regex_pattern: regex_pattern.to_string(),
+        }
+    }
+
+    /// Returns the field of the query.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Returns the regular expression of the query.
+    pub fn regex_pattern(&self) -> &str {
+        &self.regex_pattern
+    }
+}
+
+impl Query for RegexQuery {
+    fn weight(&self, _searcher: &Searcher, _scoring_enabled: bool) -> Result<Box<Weight>> {
+        let automaton = Regex::new(&self.regex_pattern).map_err(|err| {
+            ErrorKind::InvalidArgument(format!(
+                "Invalid regular expression {:?}: {}",
+                self.regex_pattern, err
+            ))
+        })?;
+        Ok(box AutomatonWeight::new(self.field, automaton))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{SchemaBuilder, STRING, TEXT};
+    use collector::CountCollector;
+    use query::Query;
+    use super::RegexQuery;
+
+    #[test]
+    fn test_regex_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let sku_field = schema_builder.add_text_field("sku", STRING);
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(sku_field => "AB-1234", text_field => "japan"));
+            index_writer.add_document(doc!(sku_field => "AB-123", text_field => "japon"));
+            index_writer.commit().unwrap();
+            index_writer.add_document(doc!(sku_field => "AB-9999", text_field => "korea"));
+            index_writer.add_document(doc!(sku_field => "CD-1234"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |query: &Query| {
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        assert_eq!(count(&RegexQuery::new(sku_field, "AB-[0-9]{4}")), 2);
+        assert_eq!(count(&RegexQuery::new(sku_field, "[A-Z]{2}-1234")), 2);
+        assert_eq!(count(&RegexQuery::new(sku_field, "AB-.*")), 3);
+        assert_eq!(count(&RegexQuery::new(sku_field, "AB")), 0);
+        assert_eq!(count(&RegexQuery::new(text_field, "jap[ao]n")), 2);
+        assert_eq!(count(&RegexQuery::new(text_field, "x+")), 0);
+        assert!(
+            RegexQuery::new(sku_field, "AB-[0-9")
+                .search(&*searcher, &mut CountCollector::default())
+                .is_err()
+        );
+    }
+}
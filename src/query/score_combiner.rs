@@ -78,3 +78,57 @@ impl ScoreCombiner for SumWithCoordsCombiner {
         self.score
     }
 }
+
+impl SumWithCoordsCombiner {
+    /// Returns the number of scorers which matched.
+    pub fn num_fields(&self) -> usize {
+        self.num_fields
+    }
+}
+
+/// Keeps the maximum of the scores of different scorers, and adds the
+/// other scores multiplied by a tie breaker.
+///
+/// With a tie breaker of `0`, only the best score counts. With a tie breaker
+/// of `1`, the scores are simply summed.
+#[derive(Default, Clone, Copy)]
+pub struct DisjunctionMaxCombiner {
+    tie_breaker: Score,
+    max: Option<Score>,
+    sum: Score,
+}
+
+impl DisjunctionMaxCombiner {
+    /// Creates a new `DisjunctionMaxCombiner`, given its tie breaker.
+    pub fn with_tie_breaker(tie_breaker: Score) -> DisjunctionMaxCombiner {
+        DisjunctionMaxCombiner {
+            tie_breaker,
+            max: None,
+            sum: 0f32,
+        }
+    }
+}
+
+impl ScoreCombiner for DisjunctionMaxCombiner {
+    fn update<TScorer: Scorer>(&mut self, scorer: &mut TScorer) {
+        let score = scorer.score();
+        self.sum += score;
+        self.max = Some(match self.max {
+            Some(max) if max >= score => max,
+            _ => score,
+        });
+    }
+
+    fn clear(&mut self) {
+        // The tie breaker is kept.
+        self.max = None;
+        self.sum = 0f32;
+    }
+
+    fn score(&self) -> Score {
+        match self.max {
+            Some(max) => max + self.tie_breaker * (self.sum - max),
+            None => 0f32,
+        }
+    }
+}
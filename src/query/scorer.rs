@@ -134,14 +134,15 @@ impl<TDocSet: DocSet> DocSet for ConstScorer<TDocSet> {
 
 impl<TDocSet: DocSet + 'static> Scorer for ConstScorer<TDocSet> {
     fn score(&mut self) -> Score {
-        1f32
+        self.score
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::EmptyScorer;
+    use super::{ConstScorer, EmptyScorer, Scorer};
     use DocSet;
+    use query::VecDocSet;
 
     #[test]
     fn test_empty_scorer() {
@@ -154,4 +155,15 @@ mod tests {
     fn test_empty_scorer_panic_on_doc_call() {
         EmptyScorer.doc();
     }
+
+    #[test]
+    fn test_const_scorer() {
+        let mut const_scorer = ConstScorer::new(VecDocSet::from(vec![1u32, 3u32]));
+        assert!(const_scorer.advance());
+        assert_eq!(const_scorer.score(), 1f32);
+        const_scorer.set_score(2.5f32);
+        assert!(const_scorer.advance());
+        assert_eq!(const_scorer.doc(), 3u32);
+        assert_eq!(const_scorer.score(), 2.5f32);
+    }
 }
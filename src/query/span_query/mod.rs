@@ -0,0 +1,437 @@
+mod span_term_query;
+mod span_near_query;
+mod span_or_query;
+mod span_not_query;
+mod span_first_query;
+mod span_scorer;
+
+pub use self::span_term_query::SpanTermQuery;
+pub use self::span_near_query::SpanNearQuery;
+pub use self::span_or_query::SpanOrQuery;
+pub use self::span_not_query::SpanNotQuery;
+pub use self::span_first_query::SpanFirstQuery;
+pub use self::span_scorer::SpanScorer;
+
+use DocId;
+use Result;
+use core::{SegmentReader, Searcher};
+use docset::DocSet;
+use query::{Query, Scorer, Weight};
+use query::bm25::BM25Weight;
+use schema::{Field, Term};
+
+/// Range of positions matched by a span query within a document.
+///
+/// The range is half-open: `start` is the position of the first
+/// matched token, and `end` the position following the last matched token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Span {
+    /// Position of the first token of the span.
+    pub start: u32,
+    /// Position following the last token of the span.
+    pub end: u32,
+}
+
+/// `DocSet` of the documents matching a span query within a specific segment,
+/// giving access to the spans matched in the current document.
+pub trait Spans: DocSet {
+    /// Returns the spans matched in the current document,
+    /// sorted by start position, then by end position.
+    ///
+    /// Once the `Spans` has been advanced to a document,
+    /// this list is never empty.
+    fn spans(&self) -> &[Span];
+}
+
+impl<TSpans: Spans + ?Sized> Spans for Box<TSpans> {
+    fn spans(&self) -> &[Span] {
+        (**self).spans()
+    }
+}
+
+/// Specialization of a `SpanQuery`, making it possible
+/// to enumerate its spans in a given segment.
+pub trait SpanWeight {
+    /// Returns the `Spans` of the query in the given segment.
+    fn spans(&self, reader: &SegmentReader) -> Result<Box<Spans>>;
+}
+
+/// Query matching documents on the positions of their terms,
+/// and reporting the ranges of positions — the spans — it matched.
+///
+/// All of the terms of a span query must belong to the same field,
+/// and positions need to be indexed for this field.
+///
+/// `SpanTermQuery` is the building block of the family: its spans are the
+/// positions of a term. The other span queries combine the spans of their
+/// clauses:
+///
+/// - `SpanNearQuery` matches clauses appearing close to each other,
+/// optionally in order.
+/// - `SpanOrQuery` matches any of its clauses.
+/// - `SpanNotQuery` matches the spans of a clause which do not overlap
+/// with the spans of another clause.
+/// - `SpanFirstQuery` matches the spans of a clause ending close enough to
+/// the beginning of the field.
+///
+/// A span query is a regular `Query`, and can be used to search and score
+/// documents like any other query. The spans of the matched documents can
+/// also be enumerated segment by segment, to highlight the matches:
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate tantivy;
+/// # use tantivy::Index;
+/// # use tantivy::schema::{SchemaBuilder, Term, TEXT};
+/// # use tantivy::query::{Span, SpanNearQuery, SpanQuery, SpanTermQuery, Spans};
+/// # use tantivy::{DocSet, Result};
+/// #
+/// # fn run() -> Result<()> {
+/// #     let mut schema_builder = SchemaBuilder::new();
+/// #     let text_field = schema_builder.add_text_field("text", TEXT);
+/// #     let index = Index::create_in_ram(schema_builder.build());
+/// #     {
+/// #         let mut index_writer = index.writer_with_num_threads(1, 6_000_000)?;
+/// #         index_writer.add_document(doc!(text_field => "a quick brown fox"));
+/// #         index_writer.commit()?;
+/// #     }
+/// #     index.load_searchers()?;
+/// let searcher = index.searcher();
+/// let quick_term = Term::from_field_text(text_field, "quick");
+/// let fox_term = Term::from_field_text(text_field, "fox");
+/// let quick: Box<SpanQuery> = Box::new(SpanTermQuery::new(quick_term));
+/// let fox: Box<SpanQuery> = Box::new(SpanTermQuery::new(fox_term));
+/// let query = SpanNearQuery::new(vec![quick, fox], 1, true);
+/// let span_weight = query.span_weight();
+/// for segment_reader in searcher.segment_readers() {
+///     let mut spans = span_weight.spans(segment_reader)?;
+///     while spans.advance() {
+///         // `quick brown fox` spans the positions 1 to 3.
+///         assert_eq!(spans.spans(), &[Span { start: 1, end: 4 }]);
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #   run().unwrap()
+/// # }
+/// ```
+pub trait SpanQuery: Query {
+    /// Returns the field on which the spans are matched.
+    fn field(&self) -> Field;
+
+    /// Appends the positions of the query to `positions`, each of them
+    /// holding the alternative terms that may appear at this position.
+    ///
+    /// These positions are used to compute the BM25 weight of the query:
+    /// each of them contributes the highest idf of its terms.
+    fn collect_positions(&self, positions: &mut Vec<Vec<Term>>);
+
+    /// Returns the number of positions of the narrowest span
+    /// the query can match.
+    ///
+    /// The spans that are wider than this are scored as sloppy matches.
+    fn min_width(&self) -> u32;
+
+    /// Creates the `SpanWeight` of the query.
+    fn span_weight(&self) -> Box<SpanWeight>;
+}
+
+/// Builds the `Weight` of a span query.
+///
+/// All span queries score their documents the same way,
+/// see [`SpanScorer`](./struct.SpanScorer.html).
+pub(crate) fn span_query_weight(
+    query: &SpanQuery,
+    searcher: &Searcher,
+    scoring_enabled: bool,
+) -> Result<Box<Weight>> {
+    let similarity_weight = if scoring_enabled {
+        let mut positions = Vec::new();
+        query.collect_positions(&mut positions);
+        for terms in &mut positions {
+            terms.sort();
+            terms.dedup();
+        }
+        let positions: Vec<&[Term]> = positions.iter().map(|terms| &terms[..]).collect();
+        BM25Weight::for_positions(searcher, &positions)
+    } else {
+        BM25Weight::null()
+    };
+    Ok(box SpanScoringWeight {
+        field: query.field(),
+        span_weight: query.span_weight(),
+        similarity_weight,
+        min_width: query.min_width(),
+    })
+}
+
+struct SpanScoringWeight {
+    field: Field,
+    span_weight: Box<SpanWeight>,
+    similarity_weight: BM25Weight,
+    min_width: u32,
+}
+
+impl Weight for SpanScoringWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let spans = self.span_weight.spans(reader)?;
+        let fieldnorm_reader = reader.get_fieldnorms_reader(self.field);
+        Ok(box SpanScorer::new(
+            spans,
+            fieldnorm_reader,
+            self.similarity_weight.clone(),
+            self.min_width,
+        ))
+    }
+}
+
+/// `Spans` in which no document matches.
+pub(crate) struct EmptySpans;
+
+impl DocSet for EmptySpans {
+    fn advance(&mut self) -> bool {
+        false
+    }
+
+    fn doc(&self) -> DocId {
+        panic!(
+            "You may not call .doc() on spans \
+             where the last call to advance() did not return true."
+        );
+    }
+
+    fn size_hint(&self) -> u32 {
+        0
+    }
+}
+
+impl Spans for EmptySpans {
+    fn spans(&self) -> &[Span] {
+        &[]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use core::Index;
+    use schema::{Field, IndexRecordOption, SchemaBuilder, Term, TEXT};
+    use collector::tests::search_docs_and_scores;
+    use query::{MultiPhraseQuery, PhraseQuery, TermQuery};
+    use tests::assert_nearly_equals;
+
+    fn create_index(texts: &[&'static str]) -> (Index, Field) {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for &text in texts {
+                index_writer.add_document(doc!(text_field => text));
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        (index, text_field)
+    }
+
+    fn term(field: Field, text: &str) -> Box<SpanQuery> {
+        box SpanTermQuery::new(Term::from_field_text(field, text))
+    }
+
+    /// Returns the documents matched by the query, with their spans.
+    fn matches(index: &Index, query: &SpanQuery) -> Vec<(DocId, Vec<(u32, u32)>)> {
+        let searcher = index.searcher();
+        let span_weight = query.span_weight();
+        let mut matches = Vec::new();
+        for segment_reader in searcher.segment_readers() {
+            let mut spans = span_weight.spans(segment_reader).unwrap();
+            while spans.advance() {
+                let doc_spans = spans
+                    .spans()
+                    .iter()
+                    .map(|span| (span.start, span.end))
+                    .collect();
+                matches.push((spans.doc(), doc_spans));
+            }
+        }
+        matches
+    }
+
+    #[test]
+    fn test_span_term_query() {
+        let (index, text_field) = create_index(&["a b c a", "b c", "c a"]);
+        assert_eq!(
+            matches(&index, &*term(text_field, "a")),
+            vec![(0, vec![(0, 1), (3, 4)]), (2, vec![(1, 2)])]
+        );
+        assert!(matches(&index, &*term(text_field, "d")).is_empty());
+        let searcher = index.searcher();
+        let (docs, scores) = search_docs_and_scores(&*searcher, &*term(text_field, "a"));
+        assert_eq!(docs, vec![0, 2]);
+        assert!(scores.iter().all(|&score| score > 0f32));
+    }
+
+    #[test]
+    fn test_span_near_query() {
+        let (index, text_field) = create_index(&["a b c d", "c b a", "a x x x x c", "b c"]);
+        let near = |slop: u32, in_order: bool| {
+            SpanNearQuery::new(
+                vec![term(text_field, "a"), term(text_field, "c")],
+                slop,
+                in_order,
+            )
+        };
+        assert_eq!(matches(&index, &near(0, true)), vec![]);
+        assert_eq!(matches(&index, &near(1, true)), vec![(0, vec![(0, 3)])]);
+        assert_eq!(
+            matches(&index, &near(1, false)),
+            vec![(0, vec![(0, 3)]), (1, vec![(0, 3)])]
+        );
+        assert_eq!(
+            matches(&index, &near(4, true)),
+            vec![(0, vec![(0, 3)]), (2, vec![(0, 6)])]
+        );
+        let b_c: Box<SpanQuery> = box SpanNearQuery::new(
+            vec![term(text_field, "b"), term(text_field, "c")],
+            0,
+            true,
+        );
+        let nested = SpanNearQuery::new(vec![b_c, term(text_field, "d")], 0, true);
+        assert_eq!(matches(&index, &nested), vec![(0, vec![(1, 4)])]);
+    }
+
+    #[test]
+    fn test_span_near_query_first_clause_or() {
+        let (index, text_field) = create_index(&["a b c"]);
+        // The alternatives of the first clause have different widths.
+        let a_b_c: Box<SpanQuery> = box SpanNearQuery::new(
+            vec![
+                term(text_field, "a"),
+                term(text_field, "b"),
+                term(text_field, "c"),
+            ],
+            0,
+            true,
+        );
+        let first: Box<SpanQuery> = box SpanOrQuery::new(vec![a_b_c, term(text_field, "b")]);
+        assert_eq!(matches(&index, &*first), vec![(0, vec![(0, 3), (1, 2)])]);
+        let query = SpanNearQuery::new(vec![first, term(text_field, "c")], 0, true);
+        assert_eq!(matches(&index, &query), vec![(0, vec![(1, 3)])]);
+    }
+
+    #[test]
+    fn test_span_or_query() {
+        let (index, text_field) = create_index(&["a b", "b c", "c d"]);
+        let query = SpanOrQuery::new(vec![term(text_field, "a"), term(text_field, "b")]);
+        assert_eq!(
+            matches(&index, &query),
+            vec![(0, vec![(0, 1), (1, 2)]), (1, vec![(0, 1)])]
+        );
+    }
+
+    #[test]
+    fn test_span_not_query() {
+        let (index, text_field) = create_index(&["a b", "b a", "a c b", "c"]);
+        let query = SpanNotQuery::new(term(text_field, "a"), term(text_field, "b"));
+        assert_eq!(
+            matches(&index, &query),
+            vec![(0, vec![(0, 1)]), (1, vec![(1, 2)]), (2, vec![(0, 1)])]
+        );
+        let not_b = |pre: u32, post: u32| {
+            SpanNotQuery::new(term(text_field, "a"), term(text_field, "b")).with_margins(pre, post)
+        };
+        // `pre` excludes the `a` following a `b`.
+        assert_eq!(
+            matches(&index, &not_b(1, 0)),
+            vec![(0, vec![(0, 1)]), (2, vec![(0, 1)])]
+        );
+        // `post` excludes the `a` preceding a `b`.
+        assert_eq!(
+            matches(&index, &not_b(0, 1)),
+            vec![(1, vec![(1, 2)]), (2, vec![(0, 1)])]
+        );
+        assert_eq!(matches(&index, &not_b(0, 2)), vec![(1, vec![(1, 2)])]);
+        assert_eq!(matches(&index, &not_b(1, 1)), vec![(2, vec![(0, 1)])]);
+    }
+
+    #[test]
+    fn test_span_first_query() {
+        let (index, text_field) = create_index(&["a b c", "b c a", "c c c a a"]);
+        let query = SpanFirstQuery::new(term(text_field, "a"), 3);
+        assert_eq!(
+            matches(&index, &query),
+            vec![(0, vec![(0, 1)]), (1, vec![(2, 3)])]
+        );
+        let query = SpanFirstQuery::new(term(text_field, "a"), 4);
+        assert_eq!(
+            matches(&index, &query),
+            vec![(0, vec![(0, 1)]), (1, vec![(2, 3)]), (2, vec![(3, 4)])]
+        );
+    }
+
+    #[test]
+    fn test_span_query_scoring() {
+        let (index, text_field) = create_index(&["a b", "a x x b"]);
+        let query = SpanNearQuery::new(vec![term(text_field, "a"), term(text_field, "b")], 2, true);
+        let searcher = index.searcher();
+        let (docs, scores) = search_docs_and_scores(&*searcher, &query);
+        assert_eq!(docs, vec![0, 1]);
+        assert!(scores[0] > scores[1]);
+
+        // Exact matches are scored as a phrase or a term would be.
+        let phrase_query = PhraseQuery::new(vec![
+            Term::from_field_text(text_field, "a"),
+            Term::from_field_text(text_field, "b"),
+        ]);
+        let (_, phrase_scores) = search_docs_and_scores(&*searcher, &phrase_query);
+        assert_nearly_equals(scores[0], phrase_scores[0]);
+
+        let (_, span_term_scores) = search_docs_and_scores(&*searcher, &*term(text_field, "a"));
+        let term_query = TermQuery::new(
+            Term::from_field_text(text_field, "a"),
+            IndexRecordOption::WithFreqs,
+        );
+        let (_, term_scores) = search_docs_and_scores(&*searcher, &term_query);
+        assert_nearly_equals(span_term_scores[0], term_scores[0]);
+    }
+
+    #[test]
+    fn test_span_or_query_scoring() {
+        let (index, text_field) = create_index(&["a b", "c b", "a x b", "c"]);
+        let searcher = index.searcher();
+        let a_or_c = || -> Box<SpanQuery> {
+            box SpanOrQuery::new(vec![term(text_field, "a"), term(text_field, "c")])
+        };
+
+        // The alternatives of a `SpanOrQuery` count as a single position.
+        let query = SpanNearQuery::new(vec![a_or_c(), term(text_field, "b")], 0, true);
+        let (docs, scores) = search_docs_and_scores(&*searcher, &query);
+        assert_eq!(docs, vec![0, 1]);
+        let multi_phrase_query = MultiPhraseQuery::new(vec![
+            vec![
+                Term::from_field_text(text_field, "a"),
+                Term::from_field_text(text_field, "c"),
+            ],
+            vec![Term::from_field_text(text_field, "b")],
+        ]);
+        let (_, multi_phrase_scores) = search_docs_and_scores(&*searcher, &multi_phrase_query);
+        assert_nearly_equals(scores[0], multi_phrase_scores[0]);
+        assert_nearly_equals(scores[1], multi_phrase_scores[1]);
+
+        // `a x b` is a sloppy match, whatever the number of alternatives.
+        let a_or_a: Box<SpanQuery> =
+            box SpanOrQuery::new(vec![term(text_field, "a"), term(text_field, "a")]);
+        let query = SpanNearQuery::new(vec![a_or_a, term(text_field, "b")], 1, true);
+        let (docs, scores) = search_docs_and_scores(&*searcher, &query);
+        assert_eq!(docs, vec![0, 2]);
+        let query = SpanNearQuery::new(vec![term(text_field, "a"), term(text_field, "b")], 1, true);
+        let (_, term_scores) = search_docs_and_scores(&*searcher, &query);
+        assert_nearly_equals(scores[0], term_scores[0]);
+        assert_nearly_equals(scores[1], term_scores[1]);
+        assert!(scores[0] > scores[1]);
+    }
+}
@@ -0,0 +1,114 @@
+use DocId;
+use Result;
+use core::{SegmentReader, Searcher};
+use docset::DocSet;
+use query::{Query, Weight};
+use schema::{Field, Term};
+use super::{span_query_weight, Span, SpanQuery, SpanWeight, Spans};
+
+/// `SpanFirstQuery` matches the spans of a clause ending
+/// before a given position.
+///
+/// For instance, with an `end` of 3, only the spans contained
+/// in the first three positions of the field are kept.
+#[derive(Debug)]
+pub struct SpanFirstQuery {
+    clause: Box<SpanQuery>,
+    end: u32,
+}
+
+impl SpanFirstQuery {
+    /// Creates a new `SpanFirstQuery`, matching the spans of
+    /// `clause` ending at most at the position `end`.
+    pub fn new(clause: Box<SpanQuery>, end: u32) -> SpanFirstQuery {
+        SpanFirstQuery { clause, end }
+    }
+
+    /// Returns the maximum end position of the spans.
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+}
+
+impl Query for SpanFirstQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        span_query_weight(self, searcher, scoring_enabled)
+    }
+}
+
+impl SpanQuery for SpanFirstQuery {
+    fn field(&self) -> Field {
+        self.clause.field()
+    }
+
+    fn collect_positions(&self, positions: &mut Vec<Vec<Term>>) {
+        self.clause.collect_positions(positions);
+    }
+
+    fn min_width(&self) -> u32 {
+        self.clause.min_width()
+    }
+
+    fn span_weight(&self) -> Box<SpanWeight> {
+        box SpanFirstWeight {
+            clause_weight: self.clause.span_weight(),
+            end: self.end,
+        }
+    }
+}
+
+struct SpanFirstWeight {
+    clause_weight: Box<SpanWeight>,
+    end: u32,
+}
+
+impl SpanWeight for SpanFirstWeight {
+    fn spans(&self, reader: &SegmentReader) -> Result<Box<Spans>> {
+        Ok(box FirstSpans {
+            child: self.clause_weight.spans(reader)?,
+            end: self.end,
+            spans: Vec::new(),
+        })
+    }
+}
+
+/// Spans of a `SpanFirstQuery`.
+struct FirstSpans {
+    child: Box<Spans>,
+    end: u32,
+    spans: Vec<Span>,
+}
+
+impl DocSet for FirstSpans {
+    fn advance(&mut self) -> bool {
+        while self.child.advance() {
+            let end = self.end;
+            self.spans.clear();
+            self.spans.extend(
+                self.child
+                    .spans()
+                    .iter()
+                    .filter(|span| span.end <= end)
+                    .cloned(),
+            );
+            if !self.spans.is_empty() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn doc(&self) -> DocId {
+        self.child.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.child.size_hint()
+    }
+}
+
+impl Spans for FirstSpans {
+    fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+}
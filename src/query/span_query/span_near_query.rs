@@ -0,0 +1,342 @@
+use DocId;
+use Result;
+use core::{SegmentReader, Searcher};
+use docset::{DocSet, SkipResult};
+use query::{Query, Weight};
+use schema::{Field, Term};
+use std::cmp::max;
+use std::u32;
+use super::{span_query_weight, Span, SpanQuery, SpanWeight, Spans};
+
+/// `SpanNearQuery` matches the documents in which the spans of
+/// all of its clauses appear close to each other.
+///
+/// The `slop` is the maximum number of positions allowed
+/// between the spans of the clauses, that are not part of any of them.
+/// With a slop of 0, the spans need to be adjacent.
+///
+/// If `in_order` is true, the spans need to appear in the same
+/// order as the clauses, without overlapping.
+///
+/// A match spans from the start of its first clause to the end
+/// of its last clause.
+#[derive(Debug)]
+pub struct SpanNearQuery {
+    clauses: Vec<Box<SpanQuery>>,
+    slop: u32,
+    in_order: bool,
+}
+
+impl SpanNearQuery {
+    /// Creates a new `SpanNearQuery`.
+    ///
+    /// There must be at least one clause, and all clauses
+    /// must belong to the same field.
+    pub fn new(clauses: Vec<Box<SpanQuery>>, slop: u32, in_order: bool) -> SpanNearQuery {
+        assert!(
+            !clauses.is_empty(),
+            "A span near query is required to have at least one clause."
+        );
+        assert!(
+            clauses[1..]
+                .iter()
+                .all(|clause| clause.field() == clauses[0].field()),
+            "All clauses of a span near query must belong to the same field."
+        );
+        SpanNearQuery {
+            clauses,
+            slop,
+            in_order,
+        }
+    }
+
+    /// Returns the slop of the query.
+    pub fn slop(&self) -> u32 {
+        self.slop
+    }
+
+    /// Returns true if the clauses need to match in order.
+    pub fn in_order(&self) -> bool {
+        self.in_order
+    }
+}
+
+impl Query for SpanNearQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        span_query_weight(self, searcher, scoring_enabled)
+    }
+}
+
+impl SpanQuery for SpanNearQuery {
+    fn field(&self) -> Field {
+        self.clauses[0].field()
+    }
+
+    fn collect_positions(&self, positions: &mut Vec<Vec<Term>>) {
+        for clause in &self.clauses {
+            clause.collect_positions(positions);
+        }
+    }
+
+    fn min_width(&self) -> u32 {
+        self.clauses.iter().map(|clause| clause.min_width()).sum()
+    }
+
+    fn span_weight(&self) -> Box<SpanWeight> {
+        box SpanNearWeight {
+            clause_weights: self.clauses
+                .iter()
+                .map(|clause| clause.span_weight())
+                .collect(),
+            slop: self.slop,
+            in_order: self.in_order,
+        }
+    }
+}
+
+struct SpanNearWeight {
+    clause_weights: Vec<Box<SpanWeight>>,
+    slop: u32,
+    in_order: bool,
+}
+
+impl SpanWeight for SpanNearWeight {
+    fn spans(&self, reader: &SegmentReader) -> Result<Box<Spans>> {
+        let mut children = Vec::with_capacity(self.clause_weights.len());
+        for clause_weight in &self.clause_weights {
+            children.push(clause_weight.spans(reader)?);
+        }
+        Ok(box NearSpans {
+            children,
+            slop: self.slop,
+            in_order: self.in_order,
+            started: false,
+            cursors: Vec::new(),
+            spans: Vec::new(),
+        })
+    }
+}
+
+/// Spans of a `SpanNearQuery`.
+///
+/// The children are intersected, and the spans of the documents
+/// on which they all match are combined.
+struct NearSpans {
+    children: Vec<Box<Spans>>,
+    slop: u32,
+    in_order: bool,
+    started: bool,
+    cursors: Vec<usize>,
+    spans: Vec<Span>,
+}
+
+impl NearSpans {
+    /// Advances the children until they are all positioned
+    /// on the same document.
+    ///
+    /// Returns false if one of the children is exhausted.
+    fn align(&mut self) -> bool {
+        let mut candidate = self.children
+            .iter()
+            .map(|child| child.doc())
+            .max()
+            .expect("A span near query has at least one clause.");
+        loop {
+            let mut aligned = true;
+            for child in &mut self.children {
+                if child.doc() < candidate {
+                    match child.skip_next(candidate) {
+                        SkipResult::Reached => {}
+                        SkipResult::OverStep => {
+                            candidate = child.doc();
+                            aligned = false;
+                        }
+                        SkipResult::End => {
+                            return false;
+                        }
+                    }
+                }
+            }
+            if aligned {
+                return true;
+            }
+        }
+    }
+
+    fn compute_spans(&mut self) {
+        let children_spans: Vec<&[Span]> =
+            self.children.iter().map(|child| child.spans()).collect();
+        if self.in_order {
+            ordered_near_spans(&children_spans, self.slop, &mut self.spans);
+        } else {
+            unordered_near_spans(
+                &children_spans,
+                self.slop,
+                &mut self.cursors,
+                &mut self.spans,
+            );
+        }
+    }
+}
+
+impl DocSet for NearSpans {
+    fn advance(&mut self) -> bool {
+        loop {
+            if self.started {
+                if !self.children[0].advance() {
+                    return false;
+                }
+            } else {
+                self.started = true;
+                for child in &mut self.children {
+                    if !child.advance() {
+                        return false;
+                    }
+                }
+            }
+            if !self.align() {
+                return false;
+            }
+            self.compute_spans();
+            if !self.spans.is_empty() {
+                return true;
+            }
+        }
+    }
+
+    fn doc(&self) -> DocId {
+        self.children[0].doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.children
+            .iter()
+            .map(|child| child.size_hint())
+            .min()
+            .unwrap_or(0u32)
+    }
+}
+
+impl Spans for NearSpans {
+    fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+}
+
+/// Computes the spans of an ordered near query.
+///
+/// For each span of the first child, the earliest possible span of each
+/// of the following children is chained, which minimizes the number of
+/// positions between them.
+fn ordered_near_spans(children_spans: &[&[Span]], slop: u32, output: &mut Vec<Span>) {
+    output.clear();
+    'first_spans: for first_span in children_spans[0] {
+        let mut end = first_span.end;
+        let mut gaps = 0u32;
+        for spans in &children_spans[1..] {
+            match spans.iter().find(|span| span.start >= end) {
+                Some(span) => {
+                    gaps += span.start - end;
+                    end = span.end;
+                }
+                // A later span of the first child may end sooner
+                // (e.g. with a `SpanOrQuery`), and still be chained.
+                None => continue 'first_spans,
+            }
+        }
+        if gaps <= slop {
+            output.push(Span {
+                start: first_span.start,
+                end,
+            });
+        }
+    }
+    output.sort();
+    output.dedup();
+}
+
+/// Computes the spans of an unordered near query.
+///
+/// One span of each child is considered at a time. At each step, the
+/// combination is tested, and the child whose span starts first
+/// moves on to its next span.
+fn unordered_near_spans(
+    children_spans: &[&[Span]],
+    slop: u32,
+    cursors: &mut Vec<usize>,
+    output: &mut Vec<Span>,
+) {
+    output.clear();
+    cursors.clear();
+    cursors.resize(children_spans.len(), 0);
+    loop {
+        let mut first_ord = 0;
+        let mut start = u32::MAX;
+        let mut end = 0u32;
+        let mut total_len = 0u32;
+        for (ord, spans) in children_spans.iter().enumerate() {
+            let span = spans[cursors[ord]];
+            if span.start < start {
+                start = span.start;
+                first_ord = ord;
+            }
+            end = max(end, span.end);
+            total_len += span.end - span.start;
+        }
+        if end - start <= total_len + slop {
+            output.push(Span { start, end });
+        }
+        cursors[first_ord] += 1;
+        if cursors[first_ord] == children_spans[first_ord].len() {
+            break;
+        }
+    }
+    output.sort();
+    output.dedup();
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{ordered_near_spans, unordered_near_spans};
+    use query::Span;
+
+    fn spans(positions: &[(u32, u32)]) -> Vec<Span> {
+        positions
+            .iter()
+            .map(|&(start, end)| Span { start, end })
+            .collect()
+    }
+
+    #[test]
+    fn test_ordered_near_spans() {
+        let a = spans(&[(0, 1), (5, 6)]);
+        let b = spans(&[(2, 3), (6, 7)]);
+        let mut output = Vec::new();
+        ordered_near_spans(&[&a, &b], 0, &mut output);
+        assert_eq!(output, spans(&[(5, 7)]));
+        ordered_near_spans(&[&a, &b], 1, &mut output);
+        assert_eq!(output, spans(&[(0, 3), (5, 7)]));
+        ordered_near_spans(&[&b, &a], 1, &mut output);
+        assert_eq!(output, spans(&[]));
+        ordered_near_spans(&[&b, &a], 2, &mut output);
+        assert_eq!(output, spans(&[(2, 6)]));
+        // The spans of the first child are not sorted by end position.
+        let c = spans(&[(0, 3), (1, 2)]);
+        let d = spans(&[(2, 3)]);
+        ordered_near_spans(&[&c, &d], 0, &mut output);
+        assert_eq!(output, spans(&[(1, 3)]));
+    }
+
+    #[test]
+    fn test_unordered_near_spans() {
+        let a = spans(&[(0, 1), (5, 6)]);
+        let b = spans(&[(2, 3), (4, 5)]);
+        let mut cursors = Vec::new();
+        let mut output = Vec::new();
+        unordered_near_spans(&[&a, &b], 0, &mut cursors, &mut output);
+        assert_eq!(output, spans(&[(4, 6)]));
+        unordered_near_spans(&[&a, &b], 1, &mut cursors, &mut output);
+        assert_eq!(output, spans(&[(0, 3), (4, 6)]));
+    }
+}
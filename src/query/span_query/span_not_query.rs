@@ -0,0 +1,174 @@
+use DocId;
+use Result;
+use core::{SegmentReader, Searcher};
+use docset::{DocSet, SkipResult};
+use query::{Query, Weight};
+use schema::{Field, Term};
+use super::{span_query_weight, Span, SpanQuery, SpanWeight, Spans};
+
+/// `SpanNotQuery` matches the spans of an `include` clause which
+/// do not overlap with any span of an `exclude` clause.
+///
+/// Margins make it possible to also exclude the spans of the `include`
+/// clause that are close to a span of the `exclude` clause, see
+/// [`with_margins`](#method.with_margins).
+#[derive(Debug)]
+pub struct SpanNotQuery {
+    include: Box<SpanQuery>,
+    exclude: Box<SpanQuery>,
+    pre: u32,
+    post: u32,
+}
+
+impl SpanNotQuery {
+    /// Creates a new `SpanNotQuery`.
+    ///
+    /// Both clauses must belong to the same field.
+    pub fn new(include: Box<SpanQuery>, exclude: Box<SpanQuery>) -> SpanNotQuery {
+        assert!(
+            include.field() == exclude.field(),
+            "Both clauses of a span not query must belong to the same field."
+        );
+        SpanNotQuery {
+            include,
+            exclude,
+            pre: 0u32,
+            post: 0u32,
+        }
+    }
+
+    /// Widens the spans of the `include` clause by `pre` positions
+    /// before their start and `post` positions after their end
+    /// when checking whether they overlap an excluded span.
+    ///
+    /// In other words, `pre` also excludes the spans starting less than
+    /// `pre` positions after the end of an excluded span, and `post` the
+    /// spans ending less than `post` positions before the start of one.
+    pub fn with_margins(mut self, pre: u32, post: u32) -> SpanNotQuery {
+        self.pre = pre;
+        self.post = post;
+        self
+    }
+}
+
+impl Query for SpanNotQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        span_query_weight(self, searcher, scoring_enabled)
+    }
+}
+
+impl SpanQuery for SpanNotQuery {
+    fn field(&self) -> Field {
+        self.include.field()
+    }
+
+    fn collect_positions(&self, positions: &mut Vec<Vec<Term>>) {
+        // The excluded terms do not contribute to the score.
+        self.include.collect_positions(positions);
+    }
+
+    fn min_width(&self) -> u32 {
+        self.include.min_width()
+    }
+
+    fn span_weight(&self) -> Box<SpanWeight> {
+        box SpanNotWeight {
+            include_weight: self.include.span_weight(),
+            exclude_weight: self.exclude.span_weight(),
+            pre: self.pre,
+            post: self.post,
+        }
+    }
+}
+
+struct SpanNotWeight {
+    include_weight: Box<SpanWeight>,
+    exclude_weight: Box<SpanWeight>,
+    pre: u32,
+    post: u32,
+}
+
+impl SpanWeight for SpanNotWeight {
+    fn spans(&self, reader: &SegmentReader) -> Result<Box<Spans>> {
+        let mut exclude = self.exclude_weight.spans(reader)?;
+        let exclude_exhausted = !exclude.advance();
+        Ok(box NotSpans {
+            include: self.include_weight.spans(reader)?,
+            exclude,
+            exclude_exhausted,
+            pre: self.pre,
+            post: self.post,
+            spans: Vec::new(),
+        })
+    }
+}
+
+/// Spans of a `SpanNotQuery`.
+struct NotSpans {
+    include: Box<Spans>,
+    exclude: Box<Spans>,
+    exclude_exhausted: bool,
+    pre: u32,
+    post: u32,
+    spans: Vec<Span>,
+}
+
+impl NotSpans {
+    /// Advances the `exclude` spans up to `doc`, and
+    /// returns true if they match `doc`.
+    fn exclude_matches(&mut self, doc: DocId) -> bool {
+        if self.exclude_exhausted {
+            return false;
+        }
+        if self.exclude.doc() >= doc {
+            return self.exclude.doc() == doc;
+        }
+        match self.exclude.skip_next(doc) {
+            SkipResult::Reached => true,
+            SkipResult::OverStep => false,
+            SkipResult::End => {
+                self.exclude_exhausted = true;
+                false
+            }
+        }
+    }
+}
+
+impl DocSet for NotSpans {
+    fn advance(&mut self) -> bool {
+        while self.include.advance() {
+            let doc = self.include.doc();
+            self.spans.clear();
+            if self.exclude_matches(doc) {
+                let (pre, post) = (self.pre, self.post);
+                let exclude_spans = self.exclude.spans();
+                self.spans
+                    .extend(self.include.spans().iter().cloned().filter(|span| {
+                        !exclude_spans.iter().any(|excluded| {
+                            excluded.end + pre > span.start && excluded.start < span.end + post
+                        })
+                    }));
+            } else {
+                self.spans.extend_from_slice(self.include.spans());
+            }
+            if !self.spans.is_empty() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn doc(&self) -> DocId {
+        self.include.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.include.size_hint()
+    }
+}
+
+impl Spans for NotSpans {
+    fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+}
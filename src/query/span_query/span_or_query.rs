@@ -0,0 +1,159 @@
+use DocId;
+use Result;
+use core::{SegmentReader, Searcher};
+use docset::DocSet;
+use query::{Query, Weight};
+use schema::{Field, Term};
+use super::{span_query_weight, Span, SpanQuery, SpanWeight, Spans};
+
+/// `SpanOrQuery` matches the documents matching any of its clauses.
+///
+/// Its spans are the union of the spans of the clauses.
+#[derive(Debug)]
+pub struct SpanOrQuery {
+    clauses: Vec<Box<SpanQuery>>,
+}
+
+impl SpanOrQuery {
+    /// Creates a new `SpanOrQuery`.
+    ///
+    /// There must be at least one clause, and all clauses
+    /// must belong to the same field.
+    pub fn new(clauses: Vec<Box<SpanQuery>>) -> SpanOrQuery {
+        assert!(
+            !clauses.is_empty(),
+            "A span or query is required to have at least one clause."
+        );
+        assert!(
+            clauses[1..]
+                .iter()
+                .all(|clause| clause.field() == clauses[0].field()),
+            "All clauses of a span or query must belong to the same field."
+        );
+        SpanOrQuery { clauses }
+    }
+}
+
+impl Query for SpanOrQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        span_query_weight(self, searcher, scoring_enabled)
+    }
+}
+
+impl SpanQuery for SpanOrQuery {
+    fn field(&self) -> Field {
+        self.clauses[0].field()
+    }
+
+    fn collect_positions(&self, positions: &mut Vec<Vec<Term>>) {
+        // The clauses are alternatives: the terms of their
+        // `n`-th positions are merged into a single position.
+        let mut or_positions: Vec<Vec<Term>> = Vec::new();
+        for clause in &self.clauses {
+            let mut clause_positions = Vec::new();
+            clause.collect_positions(&mut clause_positions);
+            for (ord, terms) in clause_positions.into_iter().enumerate() {
+                if ord < or_positions.len() {
+                    or_positions[ord].extend(terms);
+                } else {
+                    or_positions.push(terms);
+                }
+            }
+        }
+        positions.extend(or_positions);
+    }
+
+    fn min_width(&self) -> u32 {
+        self.clauses
+            .iter()
+            .map(|clause| clause.min_width())
+            .min()
+            .expect("A span or query has at least one clause.")
+    }
+
+    fn span_weight(&self) -> Box<SpanWeight> {
+        box SpanOrWeight {
+            clause_weights: self.clauses
+                .iter()
+                .map(|clause| clause.span_weight())
+                .collect(),
+        }
+    }
+}
+
+struct SpanOrWeight {
+    clause_weights: Vec<Box<SpanWeight>>,
+}
+
+impl SpanWeight for SpanOrWeight {
+    fn spans(&self, reader: &SegmentReader) -> Result<Box<Spans>> {
+        let mut children = Vec::with_capacity(self.clause_weights.len());
+        for clause_weight in &self.clause_weights {
+            let mut child = clause_weight.spans(reader)?;
+            if child.advance() {
+                children.push(child);
+            }
+        }
+        Ok(box OrSpans {
+            children,
+            doc: 0u32,
+            started: false,
+            spans: Vec::new(),
+        })
+    }
+}
+
+/// Spans of a `SpanOrQuery`.
+struct OrSpans {
+    /// Children that are not exhausted yet.
+    children: Vec<Box<Spans>>,
+    doc: DocId,
+    started: bool,
+    spans: Vec<Span>,
+}
+
+impl DocSet for OrSpans {
+    fn advance(&mut self) -> bool {
+        if self.started {
+            let doc = self.doc;
+            let mut i = 0;
+            while i < self.children.len() {
+                if self.children[i].doc() == doc && !self.children[i].advance() {
+                    self.children.swap_remove(i);
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        self.started = true;
+        match self.children.iter().map(|child| child.doc()).min() {
+            Some(doc) => {
+                self.doc = doc;
+                self.spans.clear();
+                for child in &self.children {
+                    if child.doc() == doc {
+                        self.spans.extend_from_slice(child.spans());
+                    }
+                }
+                self.spans.sort();
+                self.spans.dedup();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.children.iter().map(|child| child.size_hint()).sum()
+    }
+}
+
+impl Spans for OrSpans {
+    fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+}
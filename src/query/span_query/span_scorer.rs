@@ -0,0 +1,75 @@
+use DocId;
+use Score;
+use docset::{DocSet, SkipResult};
+use fieldnorm::FieldNormReader;
+use query::Scorer;
+use query::bm25::BM25Weight;
+use super::Spans;
+
+/// `Scorer` of the span queries.
+///
+/// The documents are scored using BM25, with a term frequency
+/// replaced by the sloppy frequency of the spans, as for a `PhraseQuery`:
+/// each span contributes `1 / (1 + distance)` to the frequency, where
+/// `distance` is the number of positions of the span in excess of the
+/// width of an exact match of the query (see `SpanQuery::min_width`).
+/// Exact matches therefore count as 1, and narrower spans get a higher score.
+pub struct SpanScorer {
+    spans: Box<Spans>,
+    fieldnorm_reader: FieldNormReader,
+    similarity_weight: BM25Weight,
+    min_width: u32,
+}
+
+impl SpanScorer {
+    /// Creates a new `SpanScorer`, for a query whose exact matches
+    /// span `min_width` positions.
+    pub fn new(
+        spans: Box<Spans>,
+        fieldnorm_reader: FieldNormReader,
+        similarity_weight: BM25Weight,
+        min_width: u32,
+    ) -> SpanScorer {
+        SpanScorer {
+            spans,
+            fieldnorm_reader,
+            similarity_weight,
+            min_width,
+        }
+    }
+}
+
+impl DocSet for SpanScorer {
+    fn advance(&mut self) -> bool {
+        self.spans.advance()
+    }
+
+    fn skip_next(&mut self, target: DocId) -> SkipResult {
+        self.spans.skip_next(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.spans.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.spans.size_hint()
+    }
+}
+
+impl Scorer for SpanScorer {
+    fn score(&mut self) -> Score {
+        let fieldnorm_id = self.fieldnorm_reader.fieldnorm_id(self.doc());
+        let min_width = self.min_width;
+        let sloppy_freq: f32 = self.spans
+            .spans()
+            .iter()
+            .map(|span| {
+                let distance = (span.end - span.start).saturating_sub(min_width);
+                1f32 / (1 + distance) as f32
+            })
+            .sum();
+        self.similarity_weight
+            .score_with_freq(fieldnorm_id, sloppy_freq)
+    }
+}
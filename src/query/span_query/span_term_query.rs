@@ -0,0 +1,145 @@
+use DocId;
+use Result;
+use core::{SegmentReader, Searcher};
+use docset::{DocSet, SkipResult};
+use postings::{Postings, SegmentPostings};
+use query::{Query, Weight};
+use schema::{Field, IndexRecordOption, Term};
+use super::{span_query_weight, EmptySpans, Span, SpanQuery, SpanWeight, Spans};
+
+/// `SpanTermQuery` matches the documents containing a given term.
+///
+/// Its spans are the positions of the term, each of
+/// them spanning a single position.
+#[derive(Debug)]
+pub struct SpanTermQuery {
+    term: Term,
+}
+
+impl SpanTermQuery {
+    /// Creates a new `SpanTermQuery` for the given term.
+    pub fn new(term: Term) -> SpanTermQuery {
+        SpanTermQuery { term }
+    }
+
+    /// Returns the term of the query.
+    pub fn term(&self) -> &Term {
+        &self.term
+    }
+}
+
+impl Query for SpanTermQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> Result<Box<Weight>> {
+        span_query_weight(self, searcher, scoring_enabled)
+    }
+}
+
+impl SpanQuery for SpanTermQuery {
+    fn field(&self) -> Field {
+        self.term.field()
+    }
+
+    fn collect_positions(&self, positions: &mut Vec<Vec<Term>>) {
+        positions.push(vec![self.term.clone()]);
+    }
+
+    fn min_width(&self) -> u32 {
+        1
+    }
+
+    fn span_weight(&self) -> Box<SpanWeight> {
+        box SpanTermWeight {
+            term: self.term.clone(),
+        }
+    }
+}
+
+struct SpanTermWeight {
+    term: Term,
+}
+
+impl SpanWeight for SpanTermWeight {
+    fn spans(&self, reader: &SegmentReader) -> Result<Box<Spans>> {
+        let postings_opt = reader
+            .inverted_index(self.term.field())
+            .read_postings(&self.term, IndexRecordOption::WithFreqsAndPositions);
+        if let Some(postings) = postings_opt {
+            Ok(box TermSpans::new(postings))
+        } else {
+            Ok(box EmptySpans)
+        }
+    }
+}
+
+/// Spans of a term, read from its postings.
+struct TermSpans {
+    postings: SegmentPostings,
+    positions: Vec<u32>,
+    spans: Vec<Span>,
+}
+
+impl TermSpans {
+    fn new(postings: SegmentPostings) -> TermSpans {
+        TermSpans {
+            postings,
+            positions: Vec::new(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Reads the spans of the current document.
+    fn load_spans(&mut self) {
+        self.postings.positions(&mut self.positions);
+        self.spans.clear();
+        self.spans.extend(self.positions.iter().map(|&position| Span {
+            start: position,
+            end: position + 1,
+        }));
+    }
+}
+
+impl DocSet for TermSpans {
+    fn advance(&mut self) -> bool {
+        while self.postings.advance() {
+            self.load_spans();
+            // Documents without positions (e.g. if positions were not
+            // indexed for the field) do not have any span.
+            if !self.spans.is_empty() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn skip_next(&mut self, target: DocId) -> SkipResult {
+        match self.postings.skip_next(target) {
+            SkipResult::End => SkipResult::End,
+            skip_result => {
+                self.load_spans();
+                if self.spans.is_empty() {
+                    if self.advance() {
+                        SkipResult::OverStep
+                    } else {
+                        SkipResult::End
+                    }
+                } else {
+                    skip_result
+                }
+            }
+        }
+    }
+
+    fn doc(&self) -> DocId {
+        self.postings.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.postings.size_hint()
+    }
+}
+
+impl Spans for TermSpans {
+    fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+}
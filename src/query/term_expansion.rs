@@ -0,0 +1,64 @@
+use fst::Automaton;
+use schema::{Field, Term};
+use termdict::{TermDictionaryImpl, TermStreamer, TermStreamerBuilder, TermStreamerBuilderImpl};
+use std::collections::BTreeSet;
+use error::ErrorKind;
+use Result;
+use Searcher;
+
+/// Default maximum number of terms a prefix or a wildcard
+/// query may expand into.
+pub(crate) const DEFAULT_MAX_EXPANSIONS: usize = 1_024;
+
+/// Returns the sorted list of the terms of a field that are
+/// matched by an automaton, in any of the segments of the searcher.
+///
+/// The automaton is intersected with the `fst` of the term
+/// dictionary of each segment.
+pub(crate) fn expand_terms<A: Automaton>(
+    searcher: &Searcher,
+    field: Field,
+    automaton: &A,
+) -> Vec<Term> {
+    let mut terms_bytes: BTreeSet<Vec<u8>> = BTreeSet::new();
+    for segment_reader in searcher.segment_readers() {
+        let inverted_index = segment_reader.inverted_index(field);
+        let mut term_stream = inverted_index.terms().search(automaton).into_stream();
+        while term_stream.advance() {
+            terms_bytes.insert(term_stream.key().to_vec());
+        }
+    }
+    terms_bytes
+        .into_iter()
+        .map(|term_bytes| Term::from_field_bytes(field, &term_bytes))
+        .collect()
+}
+
+/// Checks that the terms streamed by `stream_builder` from the term dictionaries
+/// of the segments of the searcher do not add up to more than `max_expansions`
+/// distinct terms.
+///
+/// Returns a `TooManyTerms` error otherwise.
+pub(crate) fn check_max_expansions<A, F>(
+    searcher: &Searcher,
+    field: Field,
+    max_expansions: usize,
+    stream_builder: F,
+) -> Result<()>
+where
+    A: Automaton,
+    F: for<'a> Fn(&'a TermDictionaryImpl) -> TermStreamerBuilderImpl<'a, A>,
+{
+    let mut terms_bytes: BTreeSet<Vec<u8>> = BTreeSet::new();
+    for segment_reader in searcher.segment_readers() {
+        let inverted_index = segment_reader.inverted_index(field);
+        let mut term_stream = stream_builder(inverted_index.terms()).into_stream();
+        while term_stream.advance() {
+            terms_bytes.insert(term_stream.key().to_vec());
+            if terms_bytes.len() > max_expansions {
+                bail!(ErrorKind::TooManyTerms(max_expansions));
+            }
+        }
+    }
+    Ok(())
+}
@@ -16,6 +16,7 @@ mod tests {
     use schema::{TEXT, STRING, SchemaBuilder, IndexRecordOption};
     use collector::TopCollector;
     use query::{TermQuery, QueryParser, Query, Scorer};
+    use DocAddress;
 
 
     #[test]
@@ -106,4 +107,44 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn test_term_query_explain() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field => "a b b"));
+            index_writer.add_document(doc!(text_field => "a"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let term_query = TermQuery::new(
+            Term::from_field_text(text_field, "b"),
+            IndexRecordOption::WithFreqs,
+        );
+        let mut collector = TopCollector::with_limit(2);
+        searcher.search(&term_query, &mut collector).unwrap();
+        let (score, doc_address) = collector.score_docs()[0];
+        assert_eq!(doc_address, DocAddress(0, 0));
+
+        let explanation = term_query.explain(&*searcher, &doc_address).unwrap();
+        assert_nearly_equals(score, explanation.value());
+        assert!(explanation.description().starts_with("TermQuery"));
+        let bm25_explanation = &explanation.details()[0];
+        assert_nearly_equals(score, bm25_explanation.value());
+        let factors = bm25_explanation.details();
+        assert_eq!(factors.len(), 3);
+        assert_nearly_equals(
+            score,
+            factors[0].value() * factors[1].value() * factors[2].value(),
+        );
+        assert_nearly_equals(factors[1].details()[0].value(), 1f32);
+        assert_nearly_equals(factors[1].details()[1].value(), 2f32);
+        assert_nearly_equals(factors[2].details()[0].value(), 2f32);
+        assert!(explanation.to_pretty_json().contains("\"description\""));
+
+        assert!(term_query.explain(&*searcher, &DocAddress(0, 1)).is_err());
+    }
 }
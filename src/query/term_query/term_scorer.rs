@@ -7,6 +7,7 @@ use postings::Postings;
 use fieldnorm::FieldNormReader;
 use query::bm25::BM25Weight;
 use postings::SegmentPostings;
+use query::Explanation;
 
 pub struct TermScorer {
     postings: SegmentPostings,
@@ -25,6 +26,13 @@ impl TermScorer {
             similarity_weight,
         }
     }
+
+    /// Explains the score of the current document.
+    pub fn explain(&self) -> Explanation {
+        let fieldnorm_id = self.fieldnorm_reader.fieldnorm_id(self.doc());
+        self.similarity_weight
+            .explain(fieldnorm_id, self.postings.term_freq())
+    }
 }
 
 impl DocSet for TermScorer {
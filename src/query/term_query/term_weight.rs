@@ -1,8 +1,10 @@
 use Term;
+use DocId;
 use query::Weight;
 use core::SegmentReader;
-use query::Scorer;
-use docset::DocSet;
+use query::{Explanation, Scorer};
+use query::explanation::does_not_match;
+use docset::{DocSet, SkipResult};
 use postings::SegmentPostings;
 use schema::IndexRecordOption;
 use super::term_scorer::TermScorer;
@@ -18,22 +20,7 @@ pub struct TermWeight {
 impl Weight for TermWeight {
 
     fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
-        let field = self.term.field();
-        let inverted_index = reader.inverted_index(field);
-        let fieldnorm_reader = reader.get_fieldnorms_reader(field);
-        let similarity_weight = self.similarity_weight.clone();
-            let postings_opt: Option<SegmentPostings> =
-                inverted_index.read_postings(&self.term, self.index_record_option);
-            if let Some(segment_postings) = postings_opt {
-                Ok(box TermScorer::new(segment_postings,
-                                    fieldnorm_reader,
-                                    similarity_weight))
-            } else {
-                Ok(box TermScorer::new(
-                    SegmentPostings::empty(),
-                    fieldnorm_reader,
-                    similarity_weight))
-            }
+        Ok(box self.specialized_scorer(reader))
     }
 
     fn count(&self, reader: &SegmentReader) -> Result<u32> {
@@ -48,6 +35,20 @@ impl Weight for TermWeight {
             Ok(self.scorer(reader)?.count())
         }
     }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> Result<Explanation> {
+        let mut scorer = self.specialized_scorer(reader);
+        if scorer.skip_next(doc) != SkipResult::Reached {
+            return Err(does_not_match(doc));
+        }
+        let term_explain = scorer.explain();
+        let mut explanation = Explanation::new(
+            format!("TermQuery({:?}), score of:", self.term),
+            term_explain.value(),
+        );
+        explanation.add_detail(term_explain);
+        Ok(explanation)
+    }
 }
 
 
@@ -62,5 +63,23 @@ impl TermWeight {
             similarity_weight,
         }
     }
+
+    fn specialized_scorer(&self, reader: &SegmentReader) -> TermScorer {
+        let field = self.term.field();
+        let inverted_index = reader.inverted_index(field);
+        let fieldnorm_reader = reader.get_fieldnorms_reader(field);
+        let similarity_weight = self.similarity_weight.clone();
+        let postings_opt: Option<SegmentPostings> =
+            inverted_index.read_postings(&self.term, self.index_record_option);
+        if let Some(segment_postings) = postings_opt {
+            TermScorer::new(segment_postings, fieldnorm_reader, similarity_weight)
+        } else {
+            TermScorer::new(
+                SegmentPostings::empty(),
+                fieldnorm_reader,
+                similarity_weight,
+            )
+        }
+    }
 }
 
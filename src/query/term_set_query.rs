@@ -0,0 +1,190 @@
+use schema::{Field, IndexRecordOption, Term};
+use query::{BitSetDocSet, ConstScorer, Query, Scorer, Weight};
+use termdict::{TermDictionary, TermStreamer, TermStreamerBuilder};
+use core::{SegmentReader, Searcher};
+use common::BitSet;
+use Result;
+
+/// `TermSetQuery` matches all of the documents containing
+/// at least one of the terms of a set.
+///
+/// It is a much cheaper alternative to a `BooleanQuery` made of
+/// a large number of `TermQuery` clauses, e.g. to restrict the results
+/// to a list of allowed ids. The documents are not scored:
+/// matched documents all get a constant `Score` of one.
+///
+/// # Implementation
+///
+/// The terms are sorted, and looked up in a single pass over the
+/// term dictionary of each segment. The documents containing them
+/// are appended into a `BitSet`.
+#[derive(Debug)]
+pub struct TermSetQuery {
+    field: Field,
+    terms: Vec<Term>,
+}
+
+impl TermSetQuery {
+    /// Creates a new `TermSetQuery`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the terms does not belong to `field`.
+    pub fn new(field: Field, mut terms: Vec<Term>) -> TermSetQuery {
+        for term in &terms {
+            assert_eq!(term.field(), field, "All terms must belong to the same field.");
+        }
+        terms.sort();
+        terms.dedup();
+        TermSetQuery { field, terms }
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Returns the sorted and deduplicated terms of the set.
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+}
+
+impl Query for TermSetQuery {
+    fn weight(&self, _searcher: &Searcher, _scoring_enabled: bool) -> Result<Box<Weight>> {
+        Ok(box TermSetWeight {
+            field: self.field,
+            terms: self.terms.clone(),
+        })
+    }
+}
+
+struct TermSetWeight {
+    field: Field,
+    terms: Vec<Term>,
+}
+
+impl Weight for TermSetWeight {
+    fn scorer(&self, reader: &SegmentReader) -> Result<Box<Scorer>> {
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+
+        if let (Some(first_term), Some(last_term)) = (self.terms.first(), self.terms.last()) {
+            let inverted_index = reader.inverted_index(self.field);
+            let mut term_stream = inverted_index
+                .terms()
+                .range()
+                .ge(first_term.value_bytes())
+                .le(last_term.value_bytes())
+                .into_stream();
+            let mut term_values = self.terms
+                .iter()
+                .map(|term| term.value_bytes())
+                .peekable();
+            while term_stream.advance() {
+                let key = term_stream.key();
+                // Skips the terms of the set that are absent from the segment.
+                while term_values
+                    .peek()
+                    .map(|term_value| *term_value < key)
+                    .unwrap_or(false)
+                {
+                    term_values.next();
+                }
+                match term_values.peek() {
+                    Some(term_value) if *term_value == key => {}
+                    Some(_) => continue,
+                    None => break,
+                }
+                let term_info = term_stream.value();
+                let mut block_segment_postings = inverted_index
+                    .read_block_postings_from_terminfo(term_info, IndexRecordOption::Basic);
+                while block_segment_postings.advance() {
+                    for &doc in block_segment_postings.docs() {
+                        doc_bitset.insert(doc);
+                    }
+                }
+            }
+        }
+        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(box ConstScorer::new(doc_bitset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use Term;
+    use schema::{SchemaBuilder, STRING, TEXT};
+    use query::{BooleanQuery, Occur, Query, TermQuery};
+    use schema::IndexRecordOption;
+    use collector::tests::TestCollector;
+    use super::TermSetQuery;
+
+    #[test]
+    fn test_term_set_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for i in 0..100 {
+                let text = if i % 2 == 0 { "even" } else { "odd" };
+                index_writer.add_document(doc!(
+                    id_field => format!("id{:03}", i),
+                    text_field => text
+                ));
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let make_term = |id: &str| Term::from_field_text(id_field, id);
+        let term_set_query = TermSetQuery::new(
+            id_field,
+            vec![
+                make_term("id042"),
+                make_term("id007"),
+                make_term("id100"),
+                make_term("id042"),
+                make_term("id000"),
+                make_term("id099"),
+                make_term("a"),
+            ],
+        );
+        assert_eq!(term_set_query.terms().len(), 6);
+        {
+            let mut test_collector = TestCollector::default();
+            term_set_query
+                .search(&*searcher, &mut test_collector)
+                .unwrap();
+            assert_eq!(test_collector.docs(), vec![0, 7, 42, 99]);
+        }
+        {
+            let even_query: Box<Query> = box TermQuery::new(
+                Term::from_field_text(text_field, "even"),
+                IndexRecordOption::Basic,
+            );
+            let term_set_query: Box<Query> = box term_set_query;
+            let boolean_query = BooleanQuery::from(vec![
+                (Occur::Must, even_query),
+                (Occur::Filter, term_set_query),
+            ]);
+            let mut test_collector = TestCollector::default();
+            boolean_query.search(&*searcher, &mut test_collector).unwrap();
+            assert_eq!(test_collector.docs(), vec![0, 42]);
+        }
+        assert_eq!(TermSetQuery::new(id_field, vec![]).count(&*searcher).unwrap(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_term_set_query_other_field() {
+        let mut schema_builder = SchemaBuilder::default();
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        TermSetQuery::new(id_field, vec![Term::from_field_text(text_field, "a")]);
+    }
+}
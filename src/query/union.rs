@@ -17,7 +17,8 @@ pub struct Union<TScorer, TScoreCombiner = DoNothingCombiner> {
     cursor: usize,
     offset: DocId,
     doc: DocId,
-    score: Score,
+    /// Score combiner of the current document.
+    doc_score_combiner: TScoreCombiner,
 }
 
 impl<TScorer, TScoreCombiner> From<Vec<TScorer>> for Union<TScorer, TScoreCombiner>
@@ -26,6 +27,24 @@ where
     TScorer: Scorer,
 {
     fn from(docsets: Vec<TScorer>) -> Union<TScorer, TScoreCombiner> {
+        Union::with_score_combiner(docsets, TScoreCombiner::default())
+    }
+}
+
+impl<TScorer, TScoreCombiner> Union<TScorer, TScoreCombiner>
+where
+    TScoreCombiner: ScoreCombiner,
+    TScorer: Scorer,
+{
+    /// Creates a `Union`, in which the scores of each document
+    /// are combined by a copy of `score_combiner`.
+    ///
+    /// This makes it possible to use a `ScoreCombiner` that
+    /// needs to be configured.
+    pub fn with_score_combiner(
+        docsets: Vec<TScorer>,
+        score_combiner: TScoreCombiner,
+    ) -> Union<TScorer, TScoreCombiner> {
         let non_empty_docsets: Vec<TScorer> = docsets
             .into_iter()
             .flat_map(
@@ -41,11 +60,11 @@ where
         Union {
             docsets: non_empty_docsets,
             bitsets: Box::new([TinySet::empty(); HORIZON_NUM_TINYBITSETS]),
-            scores: Box::new([TScoreCombiner::default(); HORIZON as usize]),
+            scores: Box::new([score_combiner; HORIZON as usize]),
             cursor: HORIZON_NUM_TINYBITSETS,
             offset: 0,
             doc: 0,
-            score: 0f32,
+            doc_score_combiner: score_combiner,
         }
     }
 }
@@ -76,6 +95,12 @@ fn refill<TScorer: Scorer, TScoreCombiner: ScoreCombiner>(
 }
 
 impl<TScorer: Scorer, TScoreCombiner: ScoreCombiner> Union<TScorer, TScoreCombiner> {
+    /// Returns the score combiner of the current document,
+    /// which has been updated with all of the scorers positioned on it.
+    pub(crate) fn doc_score_combiner(&self) -> &TScoreCombiner {
+        &self.doc_score_combiner
+    }
+
     fn refill(&mut self) -> bool {
         if let Some(min_doc) = self.docsets.iter_mut().map(|docset| docset.doc()).min() {
             self.offset = min_doc;
@@ -98,7 +123,7 @@ impl<TScorer: Scorer, TScoreCombiner: ScoreCombiner> Union<TScorer, TScoreCombin
                 let delta = val + (self.cursor as u32) * 64;
                 self.doc = self.offset + delta;
                 let score_combiner = &mut self.scores[delta as usize];
-                self.score = score_combiner.score();
+                self.doc_score_combiner = *score_combiner;
                 score_combiner.clear();
                 return true;
             } else {
@@ -236,7 +261,7 @@ where
     TScorer: Scorer,
 {
     fn score(&mut self) -> Score {
-        self.score
+        self.doc_score_combiner.score()
     }
 }
 
@@ -1,6 +1,10 @@
 use super::Scorer;
 use Result;
+use DocId;
 use core::SegmentReader;
+use docset::{DocSet, SkipResult};
+use query::Explanation;
+use query::explanation::does_not_match;
 
 /// A Weight is the specialization of a Query
 /// for a given set of segments.
@@ -15,4 +19,16 @@ pub trait Weight {
     fn count(&self, reader: &SegmentReader) -> Result<u32> {
         Ok(self.scorer(reader)?.count())
     }
+
+    /// Returns an [`Explanation`](./struct.Explanation.html) of the score
+    /// of the document `doc` within the given `SegmentReader`.
+    ///
+    /// Returns an error if the document does not match.
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> Result<Explanation> {
+        let mut scorer = self.scorer(reader)?;
+        if scorer.skip_next(doc) != SkipResult::Reached {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("score", scorer.score()))
+    }
 }
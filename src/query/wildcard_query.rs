@@ -0,0 +1,218 @@
+use schema::Field;
+use query::{Query, Weight};
+use query::automaton_weight::AutomatonWeight;
+use query::term_expansion::{check_max_expansions, DEFAULT_MAX_EXPANSIONS};
+use core::Searcher;
+use error::ErrorKind;
+use fst_regex::Regex;
+use Result;
+
+/// Characters that have a special meaning in a regular expression.
+const REGEX_META_CHARACTERS: &str = "\\.+*?()|[]{}^$";
+
+/// Translates a wildcard pattern into the equivalent regular expression.
+///
+/// `*` becomes `.*`, `?` becomes `.`, and all of the other
+/// characters are matched literally.
+fn wildcard_to_regex(pattern: &str) -> String {
+    let mut regex = String::with_capacity(pattern.len() * 2);
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => {
+                if REGEX_META_CHARACTERS.contains(c) {
+                    regex.push('\\');
+                }
+                regex.push(c);
+            }
+        }
+    }
+    regex
+}
+
+/// `WildcardQuery` matches all of the documents containing
+/// a term matching a wildcard pattern.
+///
+/// In the pattern, `*` matches any sequence of characters (including
+/// the empty sequence) and `?` matches exactly one character.
+/// The pattern must match the whole term.
+///
+/// The number of distinct terms matching the pattern is capped
+/// (by default to 1024). Running the query returns a `TooManyTerms`
+/// error if the cap is exceeded.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// # Example
+///
+/// ```rust
+///
+/// # #[macro_use]
+/// # extern crate tantivy;
+/// # use tantivy::Index;
+/// # use tantivy::schema::{SchemaBuilder, TEXT};
+/// # use tantivy::collector::CountCollector;
+/// # use tantivy::query::{Query, WildcardQuery};
+/// # use tantivy::Result;
+/// #
+/// # fn run() -> Result<()> {
+/// #     let mut schema_builder = SchemaBuilder::new();
+/// #     let title_field = schema_builder.add_text_field("title", TEXT);
+/// #     let schema = schema_builder.build();
+/// #
+/// #     let index = Index::create_in_ram(schema);
+/// #     {
+/// #         let mut index_writer = index.writer_with_num_threads(1, 6_000_000).unwrap();
+/// #         index_writer.add_document(doc!(title_field => "The Name of the Wind"));
+/// #         index_writer.add_document(doc!(title_field => "The Diary of Muadib"));
+/// #         index_writer.add_document(doc!(title_field => "A Dairy Cow"));
+/// #         index_writer.commit().unwrap();
+/// #     }
+/// #   index.load_searchers()?;
+/// let searcher = index.searcher();
+///
+/// let query = WildcardQuery::new(title_field, "d??ry");
+///
+/// let mut count_collector = CountCollector::default();
+/// query.search(&*searcher, &mut count_collector)?;
+///
+/// #     assert_eq!(count_collector.count(), 2);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #   run().unwrap()
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WildcardQuery {
+    field: Field,
+    pattern: String,
+    max_expansions: usize,
+}
+
+impl WildcardQuery {
+    /// Creates a new `WildcardQuery`, matching the terms
+    /// of `field` that match `pattern`.
+    pub fn new(field: Field, pattern: &str) -> WildcardQuery {
+        WildcardQuery {
+            field,
+            pattern: pattern.to_string(),
+            max_expansions: DEFAULT_MAX_EXPANSIONS,
+        }
+    }
+
+    /// Sets the maximum number of distinct terms the pattern
+    /// may expand into.
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> WildcardQuery {
+        self.max_expansions = max_expansions;
+        self
+    }
+
+    /// Returns the field of the query.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Returns the wildcard pattern of the query.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl Query for WildcardQuery {
+    fn weight(&self, searcher: &Searcher, _scoring_enabled: bool) -> Result<Box<Weight>> {
+        let automaton = Regex::new(&wildcard_to_regex(&self.pattern)).map_err(|err| {
+            ErrorKind::InvalidArgument(format!(
+                "Invalid wildcard pattern {:?}: {}",
+                self.pattern, err
+            ))
+        })?;
+        check_max_expansions(searcher, self.field, self.max_expansions, |term_dict| {
+            term_dict.search(&automaton)
+        })?;
+        Ok(box AutomatonWeight::new(self.field, automaton))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use schema::{SchemaBuilder, STRING, TEXT};
+    use collector::CountCollector;
+    use query::{Query, QueryParser};
+    use error::{Error, ErrorKind};
+    use super::{wildcard_to_regex, WildcardQuery};
+
+    #[test]
+    fn test_wildcard_to_regex() {
+        assert_eq!(wildcard_to_regex("f?o*"), "f.o.*");
+        assert_eq!(wildcard_to_regex("a.b-c*"), "a\\.b-c.*");
+        assert_eq!(wildcard_to_regex("(a|b)"), "\\(a\\|b\\)");
+    }
+
+    #[test]
+    fn test_wildcard_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let sku_field = schema_builder.add_text_field("sku", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(title_field => "japan", sku_field => "AB-12"));
+            index_writer.add_document(doc!(title_field => "japon", sku_field => "AB.12"));
+            index_writer.commit().unwrap();
+            index_writer.add_document(doc!(title_field => "japan jazz"));
+            index_writer.add_document(doc!(title_field => "korea"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |query: &Query| {
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        assert_eq!(count(&WildcardQuery::new(title_field, "jap?n")), 3);
+        assert_eq!(count(&WildcardQuery::new(title_field, "ja*")), 3);
+        assert_eq!(count(&WildcardQuery::new(title_field, "*a")), 1);
+        assert_eq!(count(&WildcardQuery::new(title_field, "*")), 4);
+        assert_eq!(count(&WildcardQuery::new(title_field, "ja?")), 0);
+        assert_eq!(count(&WildcardQuery::new(sku_field, "AB.*")), 1);
+        assert_eq!(count(&WildcardQuery::new(sku_field, "AB?12")), 2);
+        let result = WildcardQuery::new(title_field, "*a*")
+            .with_max_expansions(2)
+            .search(&*searcher, &mut CountCollector::default());
+        assert_matches!(result, Err(Error(ErrorKind::TooManyTerms(2), _)));
+    }
+
+    #[test]
+    fn test_wildcard_query_parser() {
+        let mut schema_builder = SchemaBuilder::default();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(title_field => "japan"));
+            index_writer.add_document(doc!(title_field => "café"));
+            index_writer.add_document(doc!(title_field => "cafe japon"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title_field]);
+        let count = |query_str: &str| {
+            let query = query_parser.parse_query(query_str).unwrap();
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        assert_eq!(count("jap?n"), 2);
+        assert_eq!(count("title:*pan"), 1);
+        assert_eq!(count("caf?"), 2);
+        assert_eq!(count("caf??"), 0);
+        assert_eq!(count("c*f*"), 2);
+    }
+}
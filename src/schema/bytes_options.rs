@@ -0,0 +1,68 @@
+use std::ops::BitOr;
+
+/// Define how a bytes field should be handled by tantivy.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BytesOptions {
+    indexed: bool,
+    fast: bool,
+    stored: bool,
+}
+
+impl BytesOptions {
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the value is indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Returns true iff the value is a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
+    /// Set the bytes options as stored.
+    ///
+    /// Only the fields that are set as *stored* are
+    /// persisted into the Tantivy's store.
+    pub fn set_stored(mut self) -> BytesOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Set the bytes options as indexed.
+    ///
+    /// The whole value is indexed as a single term,
+    /// so that documents can be searched for by their exact value.
+    pub fn set_indexed(mut self) -> BytesOptions {
+        self.indexed = true;
+        self
+    }
+
+    /// Set the bytes options as a fast field.
+    ///
+    /// The value of each document is then available through
+    /// a variable-length column.
+    /// If more than one value is associated to a document, only the
+    /// first one is kept in the fast field.
+    pub fn set_fast(mut self) -> BytesOptions {
+        self.fast = true;
+        self
+    }
+}
+
+impl BitOr for BytesOptions {
+    type Output = BytesOptions;
+
+    fn bitor(self, other: BytesOptions) -> BytesOptions {
+        BytesOptions {
+            indexed: self.indexed | other.indexed,
+            fast: self.fast | other.fast,
+            stored: self.stored | other.stored,
+        }
+    }
+}
+
@@ -3,6 +3,9 @@ use itertools::Itertools;
 use common::VInt;
 use std::io::{self, Read, Write};
 use common::BinarySerializable;
+use DateTime;
+use std::net::IpAddr;
+use serde_json::{Map as JsonObject, Value as JsonValue};
 
 /// Tantivy's Document is the object that can
 /// be indexed and then searched for.
@@ -87,6 +90,43 @@ impl Document {
         self.add(FieldValue::new(field, Value::I64(value)));
     }
 
+    /// Add a f64 field
+    pub fn add_f64(&mut self, field: Field, value: f64) {
+        self.add(FieldValue::new(field, Value::F64(value)));
+    }
+
+    /// Add a bool field
+    pub fn add_bool(&mut self, field: Field, value: bool) {
+        self.add(FieldValue::new(field, Value::Bool(value)));
+    }
+
+    /// Add a date field
+    pub fn add_date(&mut self, field: Field, value: &DateTime) {
+        self.add(FieldValue::new(field, Value::Date(*value)));
+    }
+
+    /// Add a bytes field
+    pub fn add_bytes(&mut self, field: Field, value: Vec<u8>) {
+        self.add(FieldValue::new(field, Value::Bytes(value)));
+    }
+
+    /// Add an IP address field
+    ///
+    /// IPv4 addresses are mapped to IPv6.
+    pub fn add_ip(&mut self, field: Field, value: IpAddr) {
+        self.add(FieldValue::new(field, Value::from(value)));
+    }
+
+    /// Add a geo point field
+    pub fn add_geo_point(&mut self, field: Field, value: GeoPoint) {
+        self.add(FieldValue::new(field, Value::GeoPoint(value)));
+    }
+
+    /// Add a JSON object field
+    pub fn add_json_object(&mut self, field: Field, value: JsonObject<String, JsonValue>) {
+        self.add(FieldValue::new(field, Value::JsonObject(value)));
+    }
+
     /// Add a field value
     pub fn add(&mut self, field_value: FieldValue) {
         self.field_values.push(field_value);
@@ -153,6 +193,8 @@ impl BinarySerializable for Document {
 mod tests {
 
     use schema::*;
+    use common::BinarySerializable;
+    use chrono::{TimeZone, Utc};
 
     #[test]
     fn test_doc() {
@@ -163,4 +205,16 @@ mod tests {
         assert_eq!(doc.field_values().len(), 1);
     }
 
+    #[test]
+    fn test_doc_serialize_date() {
+        let mut schema_builder = SchemaBuilder::default();
+        let date_field = schema_builder.add_date_field("date", INT_STORED);
+        let mut doc = Document::default();
+        doc.add_date(date_field, &Utc.timestamp(1_500_000_000i64, 123_456_789u32));
+        let mut buffer = Vec::new();
+        doc.serialize(&mut buffer).unwrap();
+        let doc_deser = Document::deserialize(&mut &buffer[..]).unwrap();
+        assert_eq!(doc, doc_deser);
+    }
+
 }
@@ -1,5 +1,9 @@
 use schema::TextOptions;
 use schema::IntOptions;
+use schema::BytesOptions;
+use schema::IpOptions;
+use schema::GeoPointOptions;
+use schema::JsonObjectOptions;
 
 use std::fmt;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -48,6 +52,33 @@ impl FieldEntry {
         }
     }
 
+    /// Creates a new f64 field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_f64(field_name: String, field_type: IntOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::F64(field_type),
+        }
+    }
+
+    /// Creates a new bool field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_bool(field_name: String, field_type: IntOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Bool(field_type),
+        }
+    }
+
+    /// Creates a new date field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_date(field_name: String, field_type: IntOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Date(field_type),
+        }
+    }
+
     /// Creates a field entry for a facet.
     pub fn new_facet(field_name: String) -> FieldEntry {
         FieldEntry {
@@ -56,6 +87,45 @@ impl FieldEntry {
         }
     }
 
+    /// Creates a field entry for a bytes field, given
+    /// a name, and some options.
+    pub fn new_bytes(field_name: String, bytes_options: BytesOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Bytes(bytes_options),
+        }
+    }
+
+    /// Creates a field entry for an IP address field, given
+    /// a name, and some options.
+    pub fn new_ip(field_name: String, ip_options: IpOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Ip(ip_options),
+        }
+    }
+
+    /// Creates a field entry for a geo point field, given
+    /// a name, and some options.
+    pub fn new_geo_point(field_name: String, geo_point_options: GeoPointOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::GeoPoint(geo_point_options),
+        }
+    }
+
+    /// Creates a field entry for a JSON object field, given
+    /// a name, and some options.
+    pub fn new_json_object(
+        field_name: String,
+        json_object_options: JsonObjectOptions,
+    ) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Json(json_object_options),
+        }
+    }
+
     /// Returns the name of the field
     pub fn name(&self) -> &str {
         &self.name
@@ -70,15 +140,29 @@ impl FieldEntry {
     pub fn is_indexed(&self) -> bool {
         match self.field_type {
             FieldType::Str(ref options) => options.get_indexing_options().is_some(),
-            FieldType::U64(ref options) | FieldType::I64(ref options) => options.is_indexed(),
+            FieldType::U64(ref options)
+            | FieldType::I64(ref options)
+            | FieldType::F64(ref options)
+            | FieldType::Bool(ref options)
+            | FieldType::Date(ref options) => options.is_indexed(),
             FieldType::HierarchicalFacet => true,
+            FieldType::Bytes(ref options) => options.is_indexed(),
+            FieldType::Ip(ref options) => options.is_indexed(),
+            FieldType::GeoPoint(ref options) => options.is_indexed(),
+            FieldType::Json(ref options) => options.is_indexed(),
         }
     }
 
-    /// Returns true iff the field is a int (signed or unsigned) fast field
+    /// Returns true iff the field is a fast field backed by a `u64` column
+    /// (u64, i64, f64, bool, date or geo point)
     pub fn is_int_fast(&self) -> bool {
         match self.field_type {
-            FieldType::U64(ref options) | FieldType::I64(ref options) => options.is_fast(),
+            FieldType::U64(ref options)
+            | FieldType::I64(ref options)
+            | FieldType::F64(ref options)
+            | FieldType::Bool(ref options)
+            | FieldType::Date(ref options) => options.is_fast(),
+            FieldType::GeoPoint(ref options) => options.is_fast(),
             _ => false,
         }
     }
@@ -86,8 +170,16 @@ impl FieldEntry {
     /// Returns true iff the field is stored
     pub fn is_stored(&self) -> bool {
         match self.field_type {
-            FieldType::U64(ref options) | FieldType::I64(ref options) => options.is_stored(),
+            FieldType::U64(ref options)
+            | FieldType::I64(ref options)
+            | FieldType::F64(ref options)
+            | FieldType::Bool(ref options)
+            | FieldType::Date(ref options) => options.is_stored(),
             FieldType::Str(ref options) => options.is_stored(),
+            FieldType::Bytes(ref options) => options.is_stored(),
+            FieldType::Ip(ref options) => options.is_stored(),
+            FieldType::GeoPoint(ref options) => options.is_stored(),
+            FieldType::Json(ref options) => options.is_stored(),
             FieldType::HierarchicalFacet => true,
             // TODO make stored hierachical facet optional
         }
@@ -115,9 +207,37 @@ impl Serialize for FieldEntry {
                 s.serialize_field("type", "i64")?;
                 s.serialize_field("options", options)?;
             }
+            FieldType::F64(ref options) => {
+                s.serialize_field("type", "f64")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::Bool(ref options) => {
+                s.serialize_field("type", "bool")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::Date(ref options) => {
+                s.serialize_field("type", "date")?;
+                s.serialize_field("options", options)?;
+            }
             FieldType::HierarchicalFacet => {
                 s.serialize_field("type", "hierarchical_facet")?;
             }
+            FieldType::Bytes(ref options) => {
+                s.serialize_field("type", "bytes")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::Ip(ref options) => {
+                s.serialize_field("type", "ip")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::GeoPoint(ref options) => {
+                s.serialize_field("type", "geo_point")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::Json(ref options) => {
+                s.serialize_field("type", "json")?;
+                s.serialize_field("options", options)?;
+            }
         }
 
         s.end()
@@ -182,6 +302,15 @@ impl<'de> Deserialize<'de> for FieldEntry {
                                 "text" => field_type = Some(FieldType::Str(map.next_value()?)),
                                 "u64" => field_type = Some(FieldType::U64(map.next_value()?)),
                                 "i64" => field_type = Some(FieldType::I64(map.next_value()?)),
+                                "f64" => field_type = Some(FieldType::F64(map.next_value()?)),
+                                "bool" => field_type = Some(FieldType::Bool(map.next_value()?)),
+                                "date" => field_type = Some(FieldType::Date(map.next_value()?)),
+                                "bytes" => field_type = Some(FieldType::Bytes(map.next_value()?)),
+                                "ip" => field_type = Some(FieldType::Ip(map.next_value()?)),
+                                "geo_point" => {
+                                    field_type = Some(FieldType::GeoPoint(map.next_value()?))
+                                }
+                                "json" => field_type = Some(FieldType::Json(map.next_value()?)),
                                 _ => {
                                     let msg = format!("Unrecognised type {}", ty);
                                     return Err(de::Error::custom(msg));
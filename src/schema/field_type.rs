@@ -1,9 +1,16 @@
-use schema::{IntOptions, TextOptions};
+use schema::{BytesOptions, GeoPointOptions, IntOptions, IpOptions, JsonObjectOptions,
+             TextOptions};
 
 use serde_json::Value as JsonValue;
 use schema::Value;
 use schema::IndexRecordOption;
 use schema::Facet;
+use schema::GeoPoint;
+use serde_json;
+use chrono::{DateTime as ChronoDateTime, Utc};
+use base64;
+use common;
+use std::net::IpAddr;
 
 /// Possible error that may occur while parsing a field value
 /// At this point the JSON is known to be valid.
@@ -27,8 +34,30 @@ pub enum FieldType {
     U64(IntOptions),
     /// Signed 64-bits integers 64 field type configuration
     I64(IntOptions),
+    /// 64-bits float field type configuration
+    F64(IntOptions),
+    /// Boolean field type configuration
+    Bool(IntOptions),
+    /// Date field type configuration.
+    ///
+    /// Dates are handled internally as `i64` timestamps (in seconds).
+    Date(IntOptions),
     /// Hierachical Facet
     HierarchicalFacet,
+    /// Raw bytes field type configuration
+    Bytes(BytesOptions),
+    /// IP address field type configuration.
+    ///
+    /// IPv4 and IPv6 addresses are both handled internally
+    /// as 128-bit IPv6 addresses.
+    Ip(IpOptions),
+    /// Geo point field type configuration.
+    GeoPoint(GeoPointOptions),
+    /// JSON object field type configuration.
+    ///
+    /// The leaves of the object are indexed under their dotted path
+    /// (e.g. `dims.width` in `{"dims": {"width": 3}}`).
+    Json(JsonObjectOptions),
 }
 
 impl FieldType {
@@ -36,10 +65,16 @@ impl FieldType {
     pub fn is_indexed(&self) -> bool {
         match *self {
             FieldType::Str(ref text_options) => text_options.get_indexing_options().is_some(),
-            FieldType::U64(ref int_options) | FieldType::I64(ref int_options) => {
-                int_options.is_indexed()
-            }
+            FieldType::U64(ref int_options)
+            | FieldType::I64(ref int_options)
+            | FieldType::F64(ref int_options)
+            | FieldType::Bool(ref int_options)
+            | FieldType::Date(ref int_options) => int_options.is_indexed(),
             FieldType::HierarchicalFacet => true,
+            FieldType::Bytes(ref bytes_options) => bytes_options.is_indexed(),
+            FieldType::Ip(ref ip_options) => ip_options.is_indexed(),
+            FieldType::GeoPoint(ref geo_point_options) => geo_point_options.is_indexed(),
+            FieldType::Json(ref json_object_options) => json_object_options.is_indexed(),
         }
     }
 
@@ -52,7 +87,14 @@ impl FieldType {
             FieldType::Str(ref text_options) => text_options
                 .get_indexing_options()
                 .map(|indexing_options| indexing_options.index_option()),
-            FieldType::U64(ref int_options) | FieldType::I64(ref int_options) => {
+            FieldType::Json(ref json_object_options) => json_object_options
+                .get_indexing_options()
+                .map(|indexing_options| indexing_options.index_option()),
+            FieldType::U64(ref int_options)
+            | FieldType::I64(ref int_options)
+            | FieldType::F64(ref int_options)
+            | FieldType::Bool(ref int_options)
+            | FieldType::Date(ref int_options) => {
                 if int_options.is_indexed() {
                     Some(IndexRecordOption::Basic)
                 } else {
@@ -60,6 +102,27 @@ impl FieldType {
                 }
             }
             FieldType::HierarchicalFacet => Some(IndexRecordOption::Basic),
+            FieldType::Bytes(ref bytes_options) => {
+                if bytes_options.is_indexed() {
+                    Some(IndexRecordOption::Basic)
+                } else {
+                    None
+                }
+            }
+            FieldType::Ip(ref ip_options) => {
+                if ip_options.is_indexed() {
+                    Some(IndexRecordOption::Basic)
+                } else {
+                    None
+                }
+            }
+            FieldType::GeoPoint(ref geo_point_options) => {
+                if geo_point_options.is_indexed() {
+                    Some(IndexRecordOption::Basic)
+                } else {
+                    None
+                }
+            }
         }
     }
 
@@ -72,10 +135,51 @@ impl FieldType {
         match *json {
             JsonValue::String(ref field_text) => match *self {
                 FieldType::Str(_) => Ok(Value::Str(field_text.clone())),
-                FieldType::U64(_) | FieldType::I64(_) => Err(ValueParsingError::TypeError(
-                    format!("Expected an integer, got {:?}", json),
-                )),
+                FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) => Err(
+                    ValueParsingError::TypeError(format!("Expected a number, got {:?}", json)),
+                ),
+                FieldType::Bool(_) => Err(ValueParsingError::TypeError(format!(
+                    "Expected a boolean, got {:?}",
+                    json
+                ))),
+                FieldType::Date(_) => {
+                    let date_time = ChronoDateTime::parse_from_rfc3339(field_text).map_err(|err| {
+                        let msg = format!(
+                            "Failed to parse {:?} as a RFC 3339 date: {}",
+                            field_text, err
+                        );
+                        ValueParsingError::TypeError(msg)
+                    })?;
+                    Ok(Value::Date(date_time.with_timezone(&Utc)))
+                }
                 FieldType::HierarchicalFacet => Ok(Value::Facet(Facet::from(field_text))),
+                FieldType::Bytes(_) => base64::decode(field_text)
+                    .map(Value::Bytes)
+                    .map_err(|err| {
+                        let msg = format!(
+                            "Expected base64 encoded bytes, got {:?}: {}",
+                            field_text, err
+                        );
+                        ValueParsingError::TypeError(msg)
+                    }),
+                FieldType::Ip(_) => field_text
+                    .parse::<IpAddr>()
+                    .map(|ip| Value::Ip(common::ip_to_ipv6(ip)))
+                    .map_err(|err| {
+                        let msg = format!(
+                            "Failed to parse {:?} as an IP address: {}",
+                            field_text, err
+                        );
+                        ValueParsingError::TypeError(msg)
+                    }),
+                FieldType::GeoPoint(_) => {
+                    let msg = format!("Expected a geo point object, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+                FieldType::Json(_) => {
+                    let msg = format!("Expected a json object, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
             },
             JsonValue::Number(ref field_val_num) => match *self {
                 FieldType::I64(_) => {
@@ -94,10 +198,60 @@ impl FieldType {
                         Err(ValueParsingError::OverflowError(msg))
                     }
                 }
-                FieldType::Str(_) | FieldType::HierarchicalFacet => {
+                FieldType::F64(_) => {
+                    if let Some(field_val_f64) = field_val_num.as_f64() {
+                        Ok(Value::F64(field_val_f64))
+                    } else {
+                        let msg = format!("Expected a f64, got {:?}", json);
+                        Err(ValueParsingError::OverflowError(msg))
+                    }
+                }
+                FieldType::Bool(_) => {
+                    let msg = format!("Expected a boolean, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+                FieldType::Str(_)
+                | FieldType::Date(_)
+                | FieldType::HierarchicalFacet
+                | FieldType::Bytes(_)
+                | FieldType::Ip(_) => {
                     let msg = format!("Expected a string, got {:?}", json);
                     Err(ValueParsingError::TypeError(msg))
                 }
+                FieldType::GeoPoint(_) => {
+                    let msg = format!("Expected a geo point object, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+                FieldType::Json(_) => {
+                    let msg = format!("Expected a json object, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+            },
+            JsonValue::Object(ref json_object) => match *self {
+                FieldType::GeoPoint(_) => {
+                    let geo_point: GeoPoint = serde_json::from_value(json.clone()).map_err(|err| {
+                        let msg = format!("Failed to parse {:?} as a geo point: {}", json, err);
+                        ValueParsingError::TypeError(msg)
+                    })?;
+                    if geo_point.is_valid() {
+                        Ok(Value::GeoPoint(geo_point))
+                    } else {
+                        let msg = format!("Geo point {:?} is out of bounds", geo_point);
+                        Err(ValueParsingError::OverflowError(msg))
+                    }
+                }
+                FieldType::Json(_) => Ok(Value::JsonObject(json_object.clone())),
+                _ => {
+                    let msg = format!("Unexpected object {:?} for field type {:?}", json, self);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+            },
+            JsonValue::Bool(field_val_bool) => match *self {
+                FieldType::Bool(_) => Ok(Value::Bool(field_val_bool)),
+                _ => {
+                    let msg = format!("Unexpected boolean {:?} for field type {:?}", json, self);
+                    Err(ValueParsingError::TypeError(msg))
+                }
             },
             _ => {
                 let msg = format!(
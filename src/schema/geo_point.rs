@@ -0,0 +1,184 @@
+use std::f64::consts::PI;
+use std::u32;
+
+/// Mean radius of the earth, in meters.
+pub const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// Number of distinct values a latitude or a longitude
+/// is quantized to (`2^32`).
+const NUM_QUANTIZED_VALS: f64 = 4_294_967_296f64;
+
+/// A geographical point, expressed as a latitude
+/// and a longitude in degrees.
+///
+/// Internally, geo points are quantized over 32 bits per coordinate,
+/// and encoded as the 64 bits Morton code (also known as Z-order)
+/// interleaving the bits of the two coordinates.
+/// The resolution of this encoding is under a centimeter.
+///
+/// Points that are close to each other share a long prefix of
+/// their Morton code, which makes it possible to search for
+/// a geographical area by scanning a handful of term ranges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+    /// Latitude, in degrees, within `[-90, 90]`.
+    pub lat: f64,
+    /// Longitude, in degrees, within `[-180, 180]`.
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Creates a new geo point.
+    pub fn new(lat: f64, lon: f64) -> GeoPoint {
+        GeoPoint { lat, lon }
+    }
+
+    /// Returns true iff the latitude and the longitude
+    /// are within their valid ranges.
+    pub fn is_valid(&self) -> bool {
+        self.lat >= -90f64 && self.lat <= 90f64 && self.lon >= -180f64 && self.lon <= 180f64
+    }
+
+    /// Returns the great-circle distance between two points, in meters.
+    ///
+    /// The distance is computed using the haversine formula.
+    pub fn distance(&self, other: &GeoPoint) -> f64 {
+        let lat1 = self.lat.to_radians();
+        let lat2 = other.lat.to_radians();
+        let half_dlat = (lat2 - lat1) / 2f64;
+        let half_dlon = (other.lon - self.lon).to_radians() / 2f64;
+        let a = half_dlat.sin().powi(2) + lat1.cos() * lat2.cos() * half_dlon.sin().powi(2);
+        2f64 * EARTH_RADIUS_METERS * a.sqrt().min(1f64).asin()
+    }
+
+    /// Returns the Morton code of the point.
+    pub fn to_morton(&self) -> u64 {
+        morton(quantize_lat(self.lat), quantize_lon(self.lon))
+    }
+
+    /// Decodes a point from its Morton code.
+    ///
+    /// Because of the quantization, the point returned is
+    /// the center of the cell containing the original point.
+    pub fn from_morton(code: u64) -> GeoPoint {
+        let (lat_q, lon_q) = unmorton(code);
+        GeoPoint {
+            lat: dequantize(lat_q, 180f64) - 90f64,
+            lon: dequantize(lon_q, 360f64) - 180f64,
+        }
+    }
+}
+
+fn quantize(val: f64, amplitude: f64) -> u32 {
+    let quantized = (val / amplitude * NUM_QUANTIZED_VALS).floor();
+    if quantized <= 0f64 {
+        0u32
+    } else if quantized >= u32::MAX as f64 {
+        u32::MAX
+    } else {
+        quantized as u32
+    }
+}
+
+fn dequantize(quantized: u32, amplitude: f64) -> f64 {
+    (quantized as f64 + 0.5f64) / NUM_QUANTIZED_VALS * amplitude
+}
+
+/// Maps a latitude to `[0, 2^32)`, preserving its order.
+pub(crate) fn quantize_lat(lat: f64) -> u32 {
+    quantize(lat + 90f64, 180f64)
+}
+
+/// Maps a longitude to `[0, 2^32)`, preserving its order.
+pub(crate) fn quantize_lon(lon: f64) -> u32 {
+    quantize(lon + 180f64, 360f64)
+}
+
+/// Spreads the bits of a `u32` over the even bits of a `u64`.
+fn spread(val: u32) -> u64 {
+    let mut x = u64::from(val);
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & 0x5555_5555_5555_5555
+}
+
+/// Reverse of `spread`.
+fn compact(val: u64) -> u32 {
+    let mut x = val & 0x5555_5555_5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x >> 4)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x >> 8)) & 0x0000_FFFF_0000_FFFF;
+    ((x | (x >> 16)) & 0xFFFF_FFFF) as u32
+}
+
+/// Interleaves the bits of a quantized latitude and longitude.
+///
+/// The latitude bits are the odd bits, so that for each
+/// level of the underlying quadtree, cells are ordered
+/// by latitude first.
+pub(crate) fn morton(lat_q: u32, lon_q: u32) -> u64 {
+    (spread(lat_q) << 1) | spread(lon_q)
+}
+
+/// Reverse of `morton`. Returns the quantized latitude and longitude.
+pub(crate) fn unmorton(code: u64) -> (u32, u32) {
+    (compact(code >> 1), compact(code))
+}
+
+/// Converts a distance in meters into an angle in degrees.
+pub(crate) fn meters_to_degrees(distance: f64) -> f64 {
+    (distance / EARTH_RADIUS_METERS) * 180f64 / PI
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_morton() {
+        assert_eq!(morton(0, 0), 0u64);
+        assert_eq!(morton(u32::MAX, u32::MAX), u64::max_value());
+        assert_eq!(morton(1, 0), 2u64);
+        assert_eq!(morton(0, 1), 1u64);
+        assert_eq!(morton(0b11, 0b10), 0b1110u64);
+        for &(lat_q, lon_q) in &[(0u32, 0u32), (12_345, 987_654_321), (u32::MAX, 7)] {
+            assert_eq!(unmorton(morton(lat_q, lon_q)), (lat_q, lon_q));
+        }
+    }
+
+    #[test]
+    fn test_geo_point_morton() {
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        let decoded = GeoPoint::from_morton(paris.to_morton());
+        assert!((decoded.lat - paris.lat).abs() < 1e-7);
+        assert!((decoded.lon - paris.lon).abs() < 1e-7);
+        assert_eq!(decoded.to_morton(), paris.to_morton());
+        let south_west = GeoPoint::new(-90f64, -180f64);
+        assert_eq!(south_west.to_morton(), 0u64);
+        let north_east = GeoPoint::new(90f64, 180f64);
+        assert_eq!(north_east.to_morton(), u64::max_value());
+    }
+
+    #[test]
+    fn test_geo_point_distance() {
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        let london = GeoPoint::new(51.5074, -0.1278);
+        let distance = paris.distance(&london);
+        assert!(distance > 343_000f64 && distance < 345_000f64);
+        assert_eq!(paris.distance(&paris), 0f64);
+        let east = GeoPoint::new(0f64, 179.5f64);
+        let west = GeoPoint::new(0f64, -179.5f64);
+        assert!(east.distance(&west) < 112_000f64);
+    }
+
+    #[test]
+    fn test_geo_point_is_valid() {
+        assert!(GeoPoint::new(90f64, -180f64).is_valid());
+        assert!(!GeoPoint::new(90.5f64, 0f64).is_valid());
+        assert!(!GeoPoint::new(0f64, 181f64).is_valid());
+    }
+}
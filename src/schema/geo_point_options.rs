@@ -0,0 +1,68 @@
+use std::ops::BitOr;
+
+/// Define how a geo point field should be handled by tantivy.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeoPointOptions {
+    indexed: bool,
+    fast: bool,
+    stored: bool,
+}
+
+impl GeoPointOptions {
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the value is indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Returns true iff the value is a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
+    /// Set the geo point options as stored.
+    ///
+    /// Only the fields that are set as *stored* are
+    /// persisted into the Tantivy's store.
+    pub fn set_stored(mut self) -> GeoPointOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Set the geo point options as indexed.
+    ///
+    /// The point is indexed as a single term, encoding its Morton code,
+    /// so that documents can be searched for by bounding box or by distance.
+    pub fn set_indexed(mut self) -> GeoPointOptions {
+        self.indexed = true;
+        self
+    }
+
+    /// Set the geo point options as a fast field.
+    ///
+    /// The Morton code of the point of each document is then
+    /// available through a bitpacked `u64` column, which is
+    /// what distance sorting relies on.
+    /// If more than one value is associated to a document, only the
+    /// first one is kept in the fast field.
+    pub fn set_fast(mut self) -> GeoPointOptions {
+        self.fast = true;
+        self
+    }
+}
+
+impl BitOr for GeoPointOptions {
+    type Output = GeoPointOptions;
+
+    fn bitor(self, other: GeoPointOptions) -> GeoPointOptions {
+        GeoPointOptions {
+            indexed: self.indexed | other.indexed,
+            fast: self.fast | other.fast,
+            stored: self.stored | other.stored,
+        }
+    }
+}
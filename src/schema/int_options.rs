@@ -55,12 +55,14 @@ impl IntOptions {
         self
     }
 
-    /// Set the u64 options as a single-valued fast field.
+    /// Set the u64 options as a fast field.
     ///
     /// Fast fields are designed for random access.
     /// Access time are similar to a random lookup in an array.
-    /// If more than one value is associated to a fast field, only the last one is
-    /// kept.
+    /// If more than one value is associated to a single-valued fast field,
+    /// only the first one is kept.
+    /// Multi-valued fast fields keep all of the values, and are read with
+    /// `SegmentReader::multi_fast_field_reader`.
     pub fn set_fast(mut self, cardinality: Cardinality) -> IntOptions {
         self.fast = Some(cardinality);
         self
@@ -94,6 +96,15 @@ pub const FAST: IntOptions = IntOptions {
     fast: Some(Cardinality::SingleValue),
 };
 
+/// Shortcut for a u64 multi-valued fast field.
+///
+/// Such a shortcut can be composed as follows `STORED | MULTI_FAST | INT_INDEXED`
+pub const MULTI_FAST: IntOptions = IntOptions {
+    indexed: false,
+    stored: false,
+    fast: Some(Cardinality::MultiValues),
+};
+
 /// Shortcut for a u64 indexed field.
 ///
 /// Such a shortcut can be composed as follows `STORED | FAST | INT_INDEXED`
@@ -0,0 +1,50 @@
+use std::ops::BitOr;
+
+/// Define how an IP address field should be handled by tantivy.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpOptions {
+    indexed: bool,
+    stored: bool,
+}
+
+impl IpOptions {
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the value is indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Set the ip options as stored.
+    ///
+    /// Only the fields that are set as *stored* are
+    /// persisted into the Tantivy's store.
+    pub fn set_stored(mut self) -> IpOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Set the ip options as indexed.
+    ///
+    /// Addresses are indexed as 128-bit terms,
+    /// so that they can be searched for by their exact value,
+    /// by range, or by CIDR block.
+    pub fn set_indexed(mut self) -> IpOptions {
+        self.indexed = true;
+        self
+    }
+}
+
+impl BitOr for IpOptions {
+    type Output = IpOptions;
+
+    fn bitor(self, other: IpOptions) -> IpOptions {
+        IpOptions {
+            indexed: self.indexed | other.indexed,
+            stored: self.stored | other.stored,
+        }
+    }
+}
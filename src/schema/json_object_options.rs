@@ -0,0 +1,57 @@
+use std::ops::BitOr;
+use schema::TextFieldIndexing;
+
+/// Define how a JSON object field should be handled by tantivy.
+///
+/// The indexing options apply to all of the text leaves of the object.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonObjectOptions {
+    indexing: Option<TextFieldIndexing>,
+    stored: bool,
+}
+
+impl JsonObjectOptions {
+    /// Returns the indexing options.
+    pub fn get_indexing_options(&self) -> Option<&TextFieldIndexing> {
+        self.indexing.as_ref()
+    }
+
+    /// Returns true iff the object is to be stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the object is indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexing.is_some()
+    }
+
+    /// Sets the field as stored.
+    ///
+    /// The object is returned with the same content, but the keys of
+    /// its objects come back sorted rather than in their original order.
+    pub fn set_stored(mut self) -> JsonObjectOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Sets the field as indexed, with the specific indexing options.
+    ///
+    /// The tokenizer and the record option are used for the text leaves.
+    /// Numbers and booleans are indexed as a single term.
+    pub fn set_indexing_options(mut self, indexing: TextFieldIndexing) -> JsonObjectOptions {
+        self.indexing = Some(indexing);
+        self
+    }
+}
+
+impl BitOr for JsonObjectOptions {
+    type Output = JsonObjectOptions;
+
+    fn bitor(self, other: JsonObjectOptions) -> JsonObjectOptions {
+        JsonObjectOptions {
+            indexing: self.indexing.or(other.indexing),
+            stored: self.stored | other.stored,
+        }
+    }
+}
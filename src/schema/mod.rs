@@ -111,6 +111,11 @@ mod field_value;
 
 mod text_options;
 mod int_options;
+mod bytes_options;
+mod ip_options;
+mod geo_point;
+mod geo_point_options;
+mod json_object_options;
 mod field;
 mod value;
 mod named_field_document;
@@ -127,6 +132,7 @@ pub use self::facet::FACET_SEP_BYTE;
 pub use self::document::Document;
 pub use self::field::Field;
 pub use self::term::Term;
+pub(crate) use self::term::JSON_TEXT_CODE;
 
 pub use self::field_type::FieldType;
 pub use self::field_entry::FieldEntry;
@@ -142,9 +148,19 @@ pub use self::text_options::STORED;
 pub use self::int_options::IntOptions;
 pub use self::int_options::FAST;
 pub use self::int_options::INT_INDEXED;
+pub use self::int_options::MULTI_FAST;
 pub use self::int_options::INT_STORED;
 pub use self::int_options::Cardinality;
 
+pub use self::bytes_options::BytesOptions;
+pub use self::ip_options::IpOptions;
+
+pub use self::geo_point::{GeoPoint, EARTH_RADIUS_METERS};
+pub(crate) use self::geo_point::{meters_to_degrees, morton, quantize_lat, quantize_lon, unmorton};
+pub use self::geo_point_options::GeoPointOptions;
+
+pub use self::json_object_options::JsonObjectOptions;
+
 use regex::Regex;
 
 /// Validator for a potential `field_name`.
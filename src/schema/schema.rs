@@ -73,6 +73,144 @@ impl SchemaBuilder {
         self.add_field(field_entry)
     }
 
+    /// Adds a new f64 field.
+    /// Returns the associated field handle
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_f64_field(&mut self, field_name_str: &str, field_options: IntOptions) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_f64(field_name, field_options);
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new bool field.
+    /// Returns the associated field handle
+    ///
+    /// As a fast field, a bool only requires one bit per document.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_bool_field(&mut self, field_name_str: &str, field_options: IntOptions) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_bool(field_name, field_options);
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new date field.
+    /// Returns the associated field handle
+    ///
+    /// Dates are parsed from RFC 3339 strings in JSON documents,
+    /// and are indexed as `i64` timestamps, so that they can
+    /// be searched with range queries.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_date_field(&mut self, field_name_str: &str, field_options: IntOptions) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_date(field_name, field_options);
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new bytes field.
+    /// Returns the associated field handle
+    ///
+    /// Bytes values are encoded in base64 in JSON documents.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_bytes_field(&mut self, field_name_str: &str, field_options: BytesOptions) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_bytes(field_name, field_options);
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new IP address field.
+    /// Returns the associated field handle
+    ///
+    /// IPv4 and IPv6 addresses are accepted, and
+    /// are expressed as strings in JSON documents.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_ip_field(&mut self, field_name_str: &str, field_options: IpOptions) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_ip(field_name, field_options);
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new geo point field.
+    /// Returns the associated field handle
+    ///
+    /// Geo points are expressed as `{"lat": 48.85, "lon": 2.35}`
+    /// objects in JSON documents.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_geo_point_field(
+        &mut self,
+        field_name_str: &str,
+        field_options: GeoPointOptions,
+    ) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_geo_point(field_name, field_options);
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new JSON object field.
+    /// Returns the associated field handle
+    ///
+    /// The keys of the objects do not need to be declared.
+    /// The leaves are searchable under their dotted path,
+    /// e.g. `attributes.color:red`.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_json_object_field(
+        &mut self,
+        field_name_str: &str,
+        field_options: JsonObjectOptions,
+    ) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_json_object(field_name, field_options);
+        self.add_field(field_entry)
+    }
+
     /// Adds a new text field.
     /// Returns the associated field handle
     ///
@@ -123,6 +261,19 @@ impl Default for SchemaBuilder {
     }
 }
 
+impl From<Schema> for SchemaBuilder {
+    /// Creates a `SchemaBuilder` containing the fields of an existing schema.
+    ///
+    /// This is useful to append new fields to the schema of an
+    /// index. (See `IndexWriter::update_schema`.)
+    fn from(schema: Schema) -> SchemaBuilder {
+        SchemaBuilder {
+            fields: schema.0.fields.clone(),
+            fields_map: schema.0.fields_map.clone(),
+        }
+    }
+}
+
 struct InnerSchema {
     fields: Vec<FieldEntry>,
     fields_map: HashMap<String, Field>, // transient
@@ -307,8 +458,11 @@ mod tests {
 
     use schema::*;
     use serde_json;
+    use serde_json::Value as JsonValue;
     use schema::field_type::ValueParsingError;
     use schema::schema::DocParsingError::NotJSON;
+    use chrono::{TimeZone, Utc};
+    use std::net::Ipv6Addr;
 
     #[test]
     pub fn is_indexed_test() {
@@ -522,4 +676,188 @@ mod tests {
             assert_matches!(json_err, Err(NotJSON(_)));
         }
     }
+
+    #[test]
+    pub fn test_parse_document_date() {
+        let mut schema_builder = SchemaBuilder::default();
+        let date_field = schema_builder.add_date_field("date", INT_STORED);
+        let schema = schema_builder.build();
+        {
+            let doc = schema
+                .parse_document(r#"{"date": "2018-02-05T10:00:00+02:00"}"#)
+                .unwrap();
+            let date = Utc.ymd(2018, 2, 5).and_hms(8, 0, 0);
+            assert_eq!(doc.get_first(date_field).unwrap().date_value(), &date);
+            assert_eq!(schema.to_json(&doc), r#"{"date":["2018-02-05T08:00:00+00:00"]}"#);
+            let doc_serdeser = schema.parse_document(&schema.to_json(&doc)).unwrap();
+            assert_eq!(doc, doc_serdeser);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"date": "05/02/2018"}"#);
+            assert_matches!(
+                json_err,
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_)))
+            );
+        }
+        {
+            let json_err = schema.parse_document(r#"{"date": 1517817600}"#);
+            assert_matches!(
+                json_err,
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_)))
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_bytes() {
+        let mut schema_builder = SchemaBuilder::default();
+        let bytes_field =
+            schema_builder.add_bytes_field("payload", BytesOptions::default().set_stored());
+        let schema = schema_builder.build();
+        {
+            let doc = schema.parse_document(r#"{"payload": "AAEC/w=="}"#).unwrap();
+            assert_eq!(
+                doc.get_first(bytes_field).unwrap().bytes_value(),
+                &[0u8, 1u8, 2u8, 255u8]
+            );
+            assert_eq!(schema.to_json(&doc), r#"{"payload":["AAEC/w=="]}"#);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"payload": "not base64!"}"#);
+            assert_matches!(
+                json_err,
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_)))
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_ip() {
+        let mut schema_builder = SchemaBuilder::default();
+        let ip_field = schema_builder.add_ip_field("src", IpOptions::default().set_stored());
+        let schema = schema_builder.build();
+        {
+            let doc = schema.parse_document(r#"{"src": "10.0.0.1"}"#).unwrap();
+            assert_eq!(
+                doc.get_first(ip_field).unwrap().ip_value(),
+                "::ffff:10.0.0.1".parse::<Ipv6Addr>().unwrap()
+            );
+            assert_eq!(schema.to_json(&doc), r#"{"src":["10.0.0.1"]}"#);
+        }
+        {
+            let doc = schema.parse_document(r#"{"src": "2001:db8::1"}"#).unwrap();
+            assert_eq!(
+                doc.get_first(ip_field).unwrap().ip_value(),
+                "2001:db8::1".parse::<Ipv6Addr>().unwrap()
+            );
+            assert_eq!(schema.to_json(&doc), r#"{"src":["2001:db8::1"]}"#);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"src": "10.0.0.256"}"#);
+            assert_matches!(
+                json_err,
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_)))
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_geo_point() {
+        let mut schema_builder = SchemaBuilder::default();
+        let location_field = schema_builder
+            .add_geo_point_field("location", GeoPointOptions::default().set_stored());
+        let schema = schema_builder.build();
+        {
+            let doc = schema
+                .parse_document(r#"{"location": {"lat": 48.5, "lon": -2.25}}"#)
+                .unwrap();
+            assert_eq!(
+                doc.get_first(location_field).unwrap().geo_point_value(),
+                GeoPoint::new(48.5, -2.25)
+            );
+            assert_eq!(
+                schema.to_json(&doc),
+                r#"{"location":[{"lat":48.5,"lon":-2.25}]}"#
+            );
+        }
+        {
+            let json_err = schema.parse_document(r#"{"location": {"lat": 91, "lon": 0}}"#);
+            assert_matches!(
+                json_err,
+                Err(DocParsingError::ValueError(_, ValueParsingError::OverflowError(_)))
+            );
+        }
+        {
+            let json_err = schema.parse_document(r#"{"location": "48.5,-2.25"}"#);
+            assert_matches!(
+                json_err,
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_)))
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_json_object() {
+        let mut schema_builder = SchemaBuilder::default();
+        let attributes_field = schema_builder.add_json_object_field(
+            "attributes",
+            JsonObjectOptions::default().set_stored(),
+        );
+        let schema = schema_builder.build();
+        {
+            let doc = schema
+                .parse_document(r#"{"attributes": {"color": "red", "dims": {"width": 3}}}"#)
+                .unwrap();
+            let json_object = doc.get_first(attributes_field).unwrap().json_object_value();
+            assert_eq!(json_object["color"], JsonValue::from("red"));
+            assert_eq!(json_object["dims"]["width"], JsonValue::from(3));
+            assert_eq!(
+                schema.to_json(&doc),
+                r#"{"attributes":[{"color":"red","dims":{"width":3}}]}"#
+            );
+            let doc_serdeser = schema.parse_document(&schema.to_json(&doc)).unwrap();
+            assert_eq!(doc, doc_serdeser);
+        }
+        {
+            // The keys do not keep their original order.
+            let doc = schema
+                .parse_document(r#"{"attributes": {"size": "L", "color": "red"}}"#)
+                .unwrap();
+            assert_eq!(
+                schema.to_json(&doc),
+                r#"{"attributes":[{"color":"red","size":"L"}]}"#
+            );
+        }
+        {
+            let json_err = schema.parse_document(r#"{"attributes": "red"}"#);
+            assert_matches!(
+                json_err,
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_)))
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_f64() {
+        let mut schema_builder = SchemaBuilder::default();
+        let ratio_field = schema_builder.add_f64_field("ratio", INT_STORED);
+        let schema = schema_builder.build();
+        {
+            let doc = schema.parse_document(r#"{"ratio": 0.75}"#).unwrap();
+            assert_eq!(doc.get_first(ratio_field).unwrap().f64_value(), 0.75f64);
+            let doc_serdeser = schema.parse_document(&schema.to_json(&doc)).unwrap();
+            assert_eq!(doc, doc_serdeser);
+        }
+        {
+            let doc = schema.parse_document(r#"{"ratio": -3}"#).unwrap();
+            assert_eq!(doc.get_first(ratio_field).unwrap().f64_value(), -3f64);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"ratio": "0.75"}"#);
+            assert_matches!(
+                json_err,
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_)))
+            );
+        }
+    }
 }
@@ -3,11 +3,25 @@ use std::fmt;
 use common;
 use byteorder::{BigEndian, ByteOrder};
 use super::Field;
+use super::GeoPoint;
 use std::str;
+use DateTime;
+use std::net::Ipv6Addr;
 
 /// Size (in bytes) of the buffer of a int field.
 const INT_TERM_LEN: usize = 4 + 8;
 
+/// Size (in bytes) of the buffer of an ip field.
+const IP_TERM_LEN: usize = 4 + 16;
+
+/// Byte separating the path of a JSON leaf from its value.
+pub(crate) const JSON_END_OF_PATH: u8 = 0u8;
+/// Type codes of the values of the JSON leaves.
+pub(crate) const JSON_TEXT_CODE: u8 = b's';
+pub(crate) const JSON_I64_CODE: u8 = b'i';
+pub(crate) const JSON_F64_CODE: u8 = b'f';
+pub(crate) const JSON_BOOL_CODE: u8 = b'b';
+
 /// Term represents the value that the token can take.
 ///
 /// It actually wraps a `Vec<u8>`.
@@ -29,6 +43,117 @@ impl Term {
         Term::from_field_u64(field, val_u64)
     }
 
+    /// Builds a term given a field, and a f64-value
+    ///
+    /// The float is mapped to a `u64` using `common::f64_to_u64`,
+    /// so that the lexicographical order of the terms matches
+    /// the natural order of the floats.
+    pub fn from_field_f64(field: Field, val: f64) -> Term {
+        let val_u64: u64 = common::f64_to_u64(val);
+        Term::from_field_u64(field, val_u64)
+    }
+
+    /// Builds a term given a field, and a bool value
+    ///
+    /// `false` and `true` are respectively encoded as the `u64` values 0 and 1.
+    pub fn from_field_bool(field: Field, val: bool) -> Term {
+        Term::from_field_u64(field, val as u64)
+    }
+
+    /// Builds a term given a field, and a date value
+    ///
+    /// The date is encoded as its `i64` timestamp (in seconds),
+    /// exactly like an `i64` value.
+    pub fn from_field_date(field: Field, val: &DateTime) -> Term {
+        Term::from_field_i64(field, val.timestamp())
+    }
+
+    /// Builds a term given a field, and a bytes value
+    ///
+    /// The whole value is used as the term value, so that
+    /// bytes fields are indexed as a single term.
+    pub fn from_field_bytes(field: Field, bytes: &[u8]) -> Term {
+        let mut term = Term(Vec::with_capacity(4 + bytes.len()));
+        term.set_field(field);
+        term.set_bytes(bytes);
+        term
+    }
+
+    /// Builds a term given a field, and an IP address
+    ///
+    /// The address is encoded over 16 bytes, as the BigEndian
+    /// representation of its 128-bit value, so that the
+    /// lexicographical order of the terms matches the order of the addresses.
+    /// IPv4 addresses are expected to be mapped to IPv6
+    /// (see `common::ip_to_ipv6`).
+    pub fn from_field_ip(field: Field, ip: Ipv6Addr) -> Term {
+        let mut term = Term(vec![0u8; IP_TERM_LEN]);
+        term.set_field(field);
+        term.set_ip(ip);
+        term
+    }
+
+    /// Builds a term given a field, and a geo point
+    ///
+    /// The point is encoded as its Morton code, exactly like a `u64` value.
+    /// See [`GeoPoint`](./struct.GeoPoint.html).
+    pub fn from_field_geo_point(field: Field, geo_point: &GeoPoint) -> Term {
+        Term::from_field_u64(field, geo_point.to_morton())
+    }
+
+    /// Builds the beginning of the terms of a JSON object field,
+    /// for a given path and type of value.
+    ///
+    /// The value bytes of the term are the dotted path of the leaf,
+    /// followed by `JSON_END_OF_PATH`, the type code of the value,
+    /// and finally the encoded value.
+    pub(crate) fn json_path_prefix(field: Field, path: &str, type_code: u8) -> Term {
+        let mut term = Term(Vec::with_capacity(4 + path.len() + 2 + 8));
+        term.set_field(field);
+        term.append_bytes(path.as_bytes());
+        term.append_bytes(&[JSON_END_OF_PATH, type_code]);
+        term
+    }
+
+    /// Builds a term given a JSON object field, the path of
+    /// a leaf within the object, and a text token.
+    ///
+    /// The path is expressed by joining the keys with a `.`,
+    /// e.g. `dims.width`.
+    pub fn from_field_json_text(field: Field, path: &str, text: &str) -> Term {
+        let mut term = Term::json_path_prefix(field, path, JSON_TEXT_CODE);
+        term.append_bytes(text.as_bytes());
+        term
+    }
+
+    /// Builds a term given a JSON object field, the path of
+    /// a leaf within the object, and an `i64` value.
+    pub fn from_field_json_i64(field: Field, path: &str, val: i64) -> Term {
+        let mut term = Term::json_path_prefix(field, path, JSON_I64_CODE);
+        let mut buffer = [0u8; 8];
+        BigEndian::write_u64(&mut buffer, common::i64_to_u64(val));
+        term.append_bytes(&buffer);
+        term
+    }
+
+    /// Builds a term given a JSON object field, the path of
+    /// a leaf within the object, and a `f64` value.
+    pub fn from_field_json_f64(field: Field, path: &str, val: f64) -> Term {
+        let mut term = Term::json_path_prefix(field, path, JSON_F64_CODE);
+        let mut buffer = [0u8; 8];
+        BigEndian::write_u64(&mut buffer, common::f64_to_u64(val));
+        term.append_bytes(&buffer);
+        term
+    }
+
+    /// Builds a term given a JSON object field, the path of
+    /// a leaf within the object, and a bool value.
+    pub fn from_field_json_bool(field: Field, path: &str, val: bool) -> Term {
+        let mut term = Term::json_path_prefix(field, path, JSON_BOOL_CODE);
+        term.append_bytes(&[val as u8]);
+        term
+    }
+
     /// Builds a term given a field, and a string value
     ///
     /// Assuming the term has a field id of 2, and a text value of "abc",
@@ -91,10 +216,36 @@ impl Term {
         self.set_u64(common::i64_to_u64(val));
     }
 
+    /// Sets a `f64` value in the term.
+    pub fn set_f64(&mut self, val: f64) {
+        self.set_u64(common::f64_to_u64(val));
+    }
+
+    /// Sets an IP address in the term.
+    pub fn set_ip(&mut self, ip: Ipv6Addr) {
+        self.0.resize(IP_TERM_LEN, 0u8);
+        self.0[4..].copy_from_slice(&ip.octets());
+    }
+
     /// Set the texts only, keeping the field untouched.
     pub fn set_text(&mut self, text: &str) {
+        self.set_bytes(text.as_bytes());
+    }
+
+    /// Set the value bytes only, keeping the field untouched.
+    pub fn set_bytes(&mut self, bytes: &[u8]) {
         self.0.resize(4, 0u8);
-        self.0.extend(text.as_bytes());
+        self.0.extend(bytes);
+    }
+
+    /// Appends some bytes to the value of the term.
+    pub(crate) fn append_bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    /// Truncates the term to its first `len` bytes (field included).
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
     }
 }
 
@@ -130,6 +281,26 @@ where
         common::u64_to_i64(BigEndian::read_u64(&self.0.as_ref()[4..]))
     }
 
+    /// Returns the `f64` value stored in a term.
+    ///
+    /// # Panics
+    /// ... or returns an invalid value
+    /// if the term is not a `f64` field.
+    pub fn get_f64(&self) -> f64 {
+        common::u64_to_f64(BigEndian::read_u64(&self.0.as_ref()[4..]))
+    }
+
+    /// Returns the IP address stored in a term.
+    ///
+    /// # Panics
+    /// ... or returns an invalid value
+    /// if the term is not an ip field.
+    pub fn get_ip(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.0.as_ref()[4..IP_TERM_LEN]);
+        Ipv6Addr::from(octets)
+    }
+
     /// Returns the text associated with the term.
     ///
     /// # Panics
@@ -175,6 +346,7 @@ impl fmt::Debug for Term {
 mod tests {
 
     use schema::*;
+    use std::net::Ipv6Addr;
 
     #[test]
     pub fn test_term() {
@@ -203,4 +375,54 @@ mod tests {
             assert_eq!(term.as_slice()[11], (983u64 % 256u64) as u8);
         }
     }
+
+    #[test]
+    pub fn test_term_f64() {
+        let mut schema_builder = SchemaBuilder::default();
+        let score_field = schema_builder.add_f64_field("score", INT_INDEXED);
+        let term = Term::from_field_f64(score_field, -1.5f64);
+        assert_eq!(term.field(), score_field);
+        assert_eq!(term.as_slice().len(), 4 + 8);
+        assert_eq!(term.get_f64(), -1.5f64);
+        assert!(Term::from_field_f64(score_field, -2f64) < term);
+        assert!(term < Term::from_field_f64(score_field, 0f64));
+        assert!(Term::from_field_f64(score_field, 0f64) < Term::from_field_f64(score_field, 0.5f64));
+    }
+
+    #[test]
+    pub fn test_term_ip() {
+        let mut schema_builder = SchemaBuilder::default();
+        let ip_field = schema_builder.add_ip_field("src", IpOptions::default().set_indexed());
+        let ip: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        let term = Term::from_field_ip(ip_field, ip);
+        assert_eq!(term.field(), ip_field);
+        assert_eq!(term.as_slice().len(), 4 + 16);
+        assert_eq!(term.get_ip(), ip);
+        let ipv4_term = Term::from_field_ip(ip_field, "::ffff:10.0.0.1".parse().unwrap());
+        let ipv4_term_next = Term::from_field_ip(ip_field, "::ffff:10.0.1.0".parse().unwrap());
+        assert!(ipv4_term < ipv4_term_next);
+        assert!(ipv4_term_next < term);
+    }
+
+    #[test]
+    pub fn test_term_json() {
+        let mut schema_builder = SchemaBuilder::default();
+        let attributes_field = schema_builder.add_json_object_field(
+            "attributes",
+            JsonObjectOptions::default().set_indexing_options(TextFieldIndexing::default()),
+        );
+        let term = Term::from_field_json_text(attributes_field, "dims.unit", "cm");
+        assert_eq!(term.field(), attributes_field);
+        assert_eq!(term.value_bytes(), b"dims.unit\x00scm");
+        let term = Term::from_field_json_bool(attributes_field, "available", true);
+        assert_eq!(term.value_bytes(), b"available\x00b\x01");
+        let term = Term::from_field_json_i64(attributes_field, "dims.width", 3i64);
+        assert_eq!(term.value_bytes().len(), "dims.width".len() + 2 + 8);
+        assert!(Term::from_field_json_i64(attributes_field, "dims.width", -1i64) < term);
+        assert!(term < Term::from_field_json_i64(attributes_field, "dims.width", 4i64));
+        assert!(
+            Term::from_field_json_f64(attributes_field, "dims.width", 2.5f64)
+                < Term::from_field_json_f64(attributes_field, "dims.width", 3f64)
+        );
+    }
 }
@@ -7,6 +7,13 @@ use schema::IndexRecordOption;
 pub struct TextOptions {
     indexing: Option<TextFieldIndexing>,
     stored: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    fast: bool,
+}
+
+fn is_false(val: &bool) -> bool {
+    !*val
 }
 
 impl TextOptions {
@@ -20,12 +27,32 @@ impl TextOptions {
         self.stored
     }
 
+    /// Returns true iff the text is a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
     /// Sets the field as stored
     pub fn set_stored(mut self) -> TextOptions {
         self.stored = true;
         self
     }
 
+    /// Sets the field as a fast field.
+    ///
+    /// For each document, the ordinals of its terms in the
+    /// term dictionary of the segment are stored in a column, and read
+    /// through a `StrFastFieldReader`.
+    /// This makes it possible to sort or group documents by their value.
+    ///
+    /// Only indexed fields can be fast fields. The column is built
+    /// from the tokens of the field, so this is typically used with
+    /// the `raw` tokenizer (see `STRING`).
+    pub fn set_fast(mut self) -> TextOptions {
+        self.fast = true;
+        self
+    }
+
     /// Sets the field as indexed, with the specific indexing options.
     pub fn set_indexing_options(mut self, indexing: TextFieldIndexing) -> TextOptions {
         self.indexing = Some(indexing);
@@ -38,6 +65,7 @@ impl Default for TextOptions {
         TextOptions {
             indexing: None,
             stored: false,
+            fast: false,
         }
     }
 }
@@ -97,6 +125,7 @@ pub const STRING: TextOptions = TextOptions {
         record: IndexRecordOption::Basic,
     }),
     stored: false,
+    fast: false,
 };
 
 /// The field will be tokenized and indexed
@@ -106,6 +135,7 @@ pub const TEXT: TextOptions = TextOptions {
         record: IndexRecordOption::WithFreqsAndPositions,
     }),
     stored: false,
+    fast: false,
 };
 
 /// A stored fields of a document can be retrieved given its `DocId`.
@@ -115,6 +145,7 @@ pub const TEXT: TextOptions = TextOptions {
 pub const STORED: TextOptions = TextOptions {
     indexing: None,
     stored: true,
+    fast: false,
 };
 
 impl BitOr for TextOptions {
@@ -124,6 +155,7 @@ impl BitOr for TextOptions {
         let mut res = TextOptions::default();
         res.indexing = self.indexing.or(other.indexing);
         res.stored = self.stored | other.stored;
+        res.fast = self.fast | other.fast;
         res
     }
 }
@@ -131,6 +163,7 @@ impl BitOr for TextOptions {
 #[cfg(test)]
 mod tests {
     use schema::*;
+    use serde_json;
 
     #[test]
     fn test_field_options() {
@@ -160,6 +193,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_text_options_fast() {
+        let field_options = STRING.set_fast() | STORED;
+        assert!(field_options.is_fast());
+        assert!(field_options.is_stored());
+        let json = serde_json::to_string(&field_options).unwrap();
+        let deserialized: TextOptions = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, field_options);
+        // `fast` is omitted when false
+        assert!(!serde_json::to_string(&TEXT).unwrap().contains("fast"));
+        let deserialized: TextOptions =
+            serde_json::from_str(r#"{"indexing": null, "stored": true}"#).unwrap();
+        assert!(!deserialized.is_fast());
+    }
+
     #[test]
     fn test_cmp_index_record_option() {
         assert!(IndexRecordOption::WithFreqsAndPositions > IndexRecordOption::WithFreqs);
@@ -1,11 +1,18 @@
 use std::fmt;
+use std::cmp::Ordering;
+use common;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde::de::Visitor;
+use serde::de::{MapAccess, Visitor};
 use schema::Facet;
+use schema::GeoPoint;
+use DateTime;
+use base64;
+use std::net::{IpAddr, Ipv6Addr};
+use serde_json::{self, Map as JsonObject, Value as JsonValue};
 
 /// Value represents the value of a any field.
 /// It is an enum over all over all of the possible field type.
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone)]
 pub enum Value {
     /// The str type is used for any text information.
     Str(String),
@@ -13,10 +20,93 @@ pub enum Value {
     U64(u64),
     /// Signed 64-bits Integer `i64`
     I64(i64),
+    /// 64-bits Float `f64`
+    F64(f64),
+    /// Boolean value
+    Bool(bool),
+    /// Date/time. It is stored as is, but indexed with a second precision.
+    Date(DateTime),
     /// Hierarchical Facet
     Facet(Facet),
+    /// Arbitrary binary data
+    Bytes(Vec<u8>),
+    /// IP address, normalized to IPv6
+    Ip(Ipv6Addr),
+    /// Geographical point
+    GeoPoint(GeoPoint),
+    /// JSON object. The keys of its objects are kept sorted,
+    /// not in their original order.
+    JsonObject(JsonObject<String, JsonValue>),
 }
 
+impl Value {
+    /// Rank of the value type, used to order values
+    /// of different types.
+    fn type_rank(&self) -> u8 {
+        match *self {
+            Value::Str(_) => 0,
+            Value::U64(_) => 1,
+            Value::I64(_) => 2,
+            Value::F64(_) => 3,
+            Value::Bool(_) => 4,
+            Value::Date(_) => 5,
+            Value::Facet(_) => 6,
+            Value::Bytes(_) => 7,
+            Value::Ip(_) => 8,
+            Value::GeoPoint(_) => 9,
+            Value::JsonObject(_) => 10,
+        }
+    }
+}
+
+fn json_object_to_string(json_object: &JsonObject<String, JsonValue>) -> String {
+    serde_json::to_string(json_object).expect("Serializing a JSON object cannot fail")
+}
+
+// `f64` is not `Ord`, so the comparison traits are implemented by hand.
+// Floats are compared using the same total order as the one
+// used in the index (see `common::f64_to_u64`).
+impl Ord for Value {
+    fn cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (&Value::Str(ref left), &Value::Str(ref right)) => left.cmp(right),
+            (&Value::U64(ref left), &Value::U64(ref right)) => left.cmp(right),
+            (&Value::I64(ref left), &Value::I64(ref right)) => left.cmp(right),
+            (&Value::F64(left), &Value::F64(right)) => {
+                common::f64_to_u64(left).cmp(&common::f64_to_u64(right))
+            }
+            (&Value::Bool(ref left), &Value::Bool(ref right)) => left.cmp(right),
+            (&Value::Date(ref left), &Value::Date(ref right)) => left.cmp(right),
+            (&Value::Facet(ref left), &Value::Facet(ref right)) => left.cmp(right),
+            (&Value::Bytes(ref left), &Value::Bytes(ref right)) => left.cmp(right),
+            (&Value::Ip(ref left), &Value::Ip(ref right)) => left.cmp(right),
+            (&Value::GeoPoint(ref left), &Value::GeoPoint(ref right)) => {
+                let left_key = (common::f64_to_u64(left.lat), common::f64_to_u64(left.lon));
+                let right_key = (common::f64_to_u64(right.lat), common::f64_to_u64(right.lon));
+                left_key.cmp(&right_key)
+            }
+            (&Value::JsonObject(ref left), &Value::JsonObject(ref right)) => {
+                json_object_to_string(left).cmp(&json_object_to_string(right))
+            }
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
 impl Serialize for Value {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -26,7 +116,14 @@ impl Serialize for Value {
             Value::Str(ref v) => serializer.serialize_str(v),
             Value::U64(u) => serializer.serialize_u64(u),
             Value::I64(u) => serializer.serialize_i64(u),
+            Value::F64(f) => serializer.serialize_f64(f),
+            Value::Bool(b) => serializer.serialize_bool(b),
+            Value::Date(ref date) => serializer.serialize_str(&date.to_rfc3339()),
             Value::Facet(ref facet) => facet.serialize(serializer),
+            Value::Bytes(ref bytes) => serializer.serialize_str(&base64::encode(bytes)),
+            Value::Ip(ip) => serializer.serialize_str(&common::ipv6_to_ip(ip).to_string()),
+            Value::GeoPoint(ref geo_point) => geo_point.serialize(serializer),
+            Value::JsonObject(ref json_object) => json_object.serialize(serializer),
         }
     }
 }
@@ -53,6 +150,14 @@ impl<'de> Deserialize<'de> for Value {
                 Ok(Value::I64(v))
             }
 
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(Value::F64(v))
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+                Ok(Value::Bool(v))
+            }
+
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
                 Ok(Value::Str(v.to_owned()))
             }
@@ -60,6 +165,17 @@ impl<'de> Deserialize<'de> for Value {
             fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
                 Ok(Value::Str(v))
             }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut json_object = JsonObject::new();
+                while let Some((key, value)) = map.next_entry::<String, JsonValue>()? {
+                    json_object.insert(key, value);
+                }
+                Ok(Value::JsonObject(json_object))
+            }
         }
 
         deserializer.deserialize_any(ValueVisitor)
@@ -99,6 +215,85 @@ impl Value {
             _ => panic!("This is not a text field."),
         }
     }
+
+    /// Returns the f64-value, provided the value is of the `F64` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `F64`
+    pub fn f64_value(&self) -> f64 {
+        match *self {
+            Value::F64(ref value) => *value,
+            _ => panic!("This is not a f64 field."),
+        }
+    }
+
+    /// Returns the bool-value, provided the value is of the `Bool` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `Bool`
+    pub fn bool_value(&self) -> bool {
+        match *self {
+            Value::Bool(value) => value,
+            _ => panic!("This is not a bool field."),
+        }
+    }
+
+    /// Returns the date-value, provided the value is of the `Date` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `Date`
+    pub fn date_value(&self) -> &DateTime {
+        match *self {
+            Value::Date(ref value) => value,
+            _ => panic!("This is not a date field."),
+        }
+    }
+
+    /// Returns the bytes, provided the value is of the `Bytes` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `Bytes`
+    pub fn bytes_value(&self) -> &[u8] {
+        match *self {
+            Value::Bytes(ref bytes) => bytes,
+            _ => panic!("This is not a bytes field."),
+        }
+    }
+
+    /// Returns the IP address, provided the value is of the `Ip` type.
+    ///
+    /// IPv4 addresses are returned as IPv4-mapped IPv6 addresses.
+    ///
+    /// # Panics
+    /// If the value is not of type `Ip`
+    pub fn ip_value(&self) -> Ipv6Addr {
+        match *self {
+            Value::Ip(value) => value,
+            _ => panic!("This is not an ip field."),
+        }
+    }
+
+    /// Returns the geo point, provided the value is of the `GeoPoint` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `GeoPoint`
+    pub fn geo_point_value(&self) -> GeoPoint {
+        match *self {
+            Value::GeoPoint(value) => value,
+            _ => panic!("This is not a geo point field."),
+        }
+    }
+
+    /// Returns the JSON object, provided the value is of the `JsonObject` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `JsonObject`
+    pub fn json_object_value(&self) -> &JsonObject<String, JsonValue> {
+        match *self {
+            Value::JsonObject(ref json_object) => json_object,
+            _ => panic!("This is not a json object field."),
+        }
+    }
 }
 
 impl From<String> for Value {
@@ -119,6 +314,54 @@ impl From<i64> for Value {
     }
 }
 
+impl From<f64> for Value {
+    fn from(v: f64) -> Value {
+        Value::F64(v)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Value {
+        Value::Bool(v)
+    }
+}
+
+impl From<DateTime> for Value {
+    fn from(date_time: DateTime) -> Value {
+        Value::Date(date_time)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(bytes: Vec<u8>) -> Value {
+        Value::Bytes(bytes)
+    }
+}
+
+impl From<IpAddr> for Value {
+    fn from(ip: IpAddr) -> Value {
+        Value::Ip(common::ip_to_ipv6(ip))
+    }
+}
+
+impl From<Ipv6Addr> for Value {
+    fn from(ip: Ipv6Addr) -> Value {
+        Value::Ip(ip)
+    }
+}
+
+impl From<GeoPoint> for Value {
+    fn from(geo_point: GeoPoint) -> Value {
+        Value::GeoPoint(geo_point)
+    }
+}
+
+impl From<JsonObject<String, JsonValue>> for Value {
+    fn from(json_object: JsonObject<String, JsonValue>) -> Value {
+        Value::JsonObject(json_object)
+    }
+}
+
 impl<'a> From<&'a str> for Value {
     fn from(s: &'a str) -> Value {
         Value::Str(s.to_string())
@@ -132,15 +375,25 @@ impl<'a> From<Facet> for Value {
 }
 
 mod binary_serialize {
-    use common::BinarySerializable;
+    use common::{BinarySerializable, VInt};
     use std::io::{self, Read, Write};
     use super::Value;
-    use schema::Facet;
+    use schema::{Facet, GeoPoint};
+    use chrono::{TimeZone, Utc};
+    use std::net::Ipv6Addr;
+    use serde_json;
 
     const TEXT_CODE: u8 = 0;
     const U64_CODE: u8 = 1;
     const I64_CODE: u8 = 2;
     const HIERARCHICAL_FACET_CODE: u8 = 3;
+    const F64_CODE: u8 = 4;
+    const DATE_CODE: u8 = 5;
+    const BYTES_CODE: u8 = 6;
+    const BOOL_CODE: u8 = 7;
+    const IP_CODE: u8 = 8;
+    const GEO_POINT_CODE: u8 = 9;
+    const JSON_OBJECT_CODE: u8 = 10;
 
     impl BinarySerializable for Value {
         fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
@@ -157,10 +410,41 @@ mod binary_serialize {
                     I64_CODE.serialize(writer)?;
                     val.serialize(writer)
                 }
+                Value::F64(ref val) => {
+                    F64_CODE.serialize(writer)?;
+                    val.serialize(writer)
+                }
+                Value::Bool(val) => {
+                    BOOL_CODE.serialize(writer)?;
+                    (val as u8).serialize(writer)
+                }
+                Value::Date(ref date) => {
+                    DATE_CODE.serialize(writer)?;
+                    date.timestamp().serialize(writer)?;
+                    date.timestamp_subsec_nanos().serialize(writer)
+                }
                 Value::Facet(ref facet) => {
                     HIERARCHICAL_FACET_CODE.serialize(writer)?;
                     facet.serialize(writer)
                 }
+                Value::Bytes(ref bytes) => {
+                    BYTES_CODE.serialize(writer)?;
+                    VInt(bytes.len() as u64).serialize(writer)?;
+                    writer.write_all(bytes)
+                }
+                Value::Ip(ref ip) => {
+                    IP_CODE.serialize(writer)?;
+                    writer.write_all(&ip.octets())
+                }
+                Value::GeoPoint(ref geo_point) => {
+                    GEO_POINT_CODE.serialize(writer)?;
+                    geo_point.lat.serialize(writer)?;
+                    geo_point.lon.serialize(writer)
+                }
+                Value::JsonObject(ref json_object) => {
+                    JSON_OBJECT_CODE.serialize(writer)?;
+                    super::json_object_to_string(json_object).serialize(writer)
+                }
             }
         }
         fn deserialize<R: Read>(reader: &mut R) -> io::Result<Self> {
@@ -178,7 +462,42 @@ mod binary_serialize {
                     let value = i64::deserialize(reader)?;
                     Ok(Value::I64(value))
                 }
+                F64_CODE => {
+                    let value = f64::deserialize(reader)?;
+                    Ok(Value::F64(value))
+                }
+                BOOL_CODE => {
+                    let value = u8::deserialize(reader)?;
+                    Ok(Value::Bool(value != 0))
+                }
+                DATE_CODE => {
+                    let timestamp = i64::deserialize(reader)?;
+                    let nanos = u32::deserialize(reader)?;
+                    Ok(Value::Date(Utc.timestamp(timestamp, nanos)))
+                }
                 HIERARCHICAL_FACET_CODE => Ok(Value::Facet(Facet::deserialize(reader)?)),
+                BYTES_CODE => {
+                    let num_bytes = VInt::deserialize(reader)?.val() as usize;
+                    let mut bytes = vec![0u8; num_bytes];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(Value::Bytes(bytes))
+                }
+                IP_CODE => {
+                    let mut octets = [0u8; 16];
+                    reader.read_exact(&mut octets)?;
+                    Ok(Value::Ip(Ipv6Addr::from(octets)))
+                }
+                GEO_POINT_CODE => {
+                    let lat = f64::deserialize(reader)?;
+                    let lon = f64::deserialize(reader)?;
+                    Ok(Value::GeoPoint(GeoPoint::new(lat, lon)))
+                }
+                JSON_OBJECT_CODE => {
+                    let json_text = String::deserialize(reader)?;
+                    serde_json::from_str(&json_text)
+                        .map(Value::JsonObject)
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+                }
                 _ => Err(io::Error::new(
                     io::ErrorKind::InvalidData,
                     format!("No field type is associated with code {:?}", type_code),
@@ -1,17 +1,27 @@
-use fst::{IntoStreamer, Streamer};
+use fst::{Automaton, IntoStreamer, Streamer};
+use fst::automaton::AlwaysMatch;
 use fst::map::{Stream, StreamBuilder};
 use postings::TermInfo;
 use super::TermDictionaryImpl;
 use termdict::{TermDictionary, TermOrdinal, TermStreamer, TermStreamerBuilder};
 
 /// See [`TermStreamerBuilder`](./trait.TermStreamerBuilder.html)
-pub struct TermStreamerBuilderImpl<'a> {
+///
+/// The streamer only emits the terms matched by the automaton `A`.
+/// By default, all of the terms are matched.
+pub struct TermStreamerBuilderImpl<'a, A = AlwaysMatch>
+where
+    A: Automaton,
+{
     fst_map: &'a TermDictionaryImpl,
-    stream_builder: StreamBuilder<'a>,
+    stream_builder: StreamBuilder<'a, A>,
 }
 
-impl<'a> TermStreamerBuilderImpl<'a> {
-    pub(crate) fn new(fst_map: &'a TermDictionaryImpl, stream_builder: StreamBuilder<'a>) -> Self {
+impl<'a, A: Automaton> TermStreamerBuilderImpl<'a, A> {
+    pub(crate) fn new(
+        fst_map: &'a TermDictionaryImpl,
+        stream_builder: StreamBuilder<'a, A>,
+    ) -> Self {
         TermStreamerBuilderImpl {
             fst_map,
             stream_builder,
@@ -19,8 +29,8 @@ impl<'a> TermStreamerBuilderImpl<'a> {
     }
 }
 
-impl<'a> TermStreamerBuilder for TermStreamerBuilderImpl<'a> {
-    type Streamer = TermStreamerImpl<'a>;
+impl<'a, A: Automaton> TermStreamerBuilder for TermStreamerBuilderImpl<'a, A> {
+    type Streamer = TermStreamerImpl<'a, A>;
 
     fn ge<T: AsRef<[u8]>>(mut self, bound: T) -> Self {
         self.stream_builder = self.stream_builder.ge(bound);
@@ -54,15 +64,18 @@ impl<'a> TermStreamerBuilder for TermStreamerBuilderImpl<'a> {
 }
 
 /// See [`TermStreamer`](./trait.TermStreamer.html)
-pub struct TermStreamerImpl<'a> {
+pub struct TermStreamerImpl<'a, A = AlwaysMatch>
+where
+    A: Automaton,
+{
     fst_map: &'a TermDictionaryImpl,
-    stream: Stream<'a>,
+    stream: Stream<'a, A>,
     term_ord: TermOrdinal,
     current_key: Vec<u8>,
     current_value: TermInfo,
 }
 
-impl<'a> TermStreamer for TermStreamerImpl<'a> {
+impl<'a, A: Automaton> TermStreamer for TermStreamerImpl<'a, A> {
     fn advance(&mut self) -> bool {
         if let Some((term, term_ord)) = self.stream.next() {
             self.current_key.clear();
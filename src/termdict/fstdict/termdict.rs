@@ -1,5 +1,6 @@
 use std::io::{self, Write};
 use fst;
+use fst::Automaton;
 use fst::raw::Fst;
 use directory::ReadOnlySource;
 use common::BinarySerializable;
@@ -172,3 +173,14 @@ impl<'a> TermDictionary<'a> for TermDictionaryImpl {
         TermStreamerBuilderImpl::new(self, self.fst_index.range())
     }
 }
+
+impl TermDictionaryImpl {
+    /// Returns a search builder, to stream all of the terms
+    /// matched by the given automaton.
+    ///
+    /// The automaton is intersected with the `fst` of the term dictionary,
+    /// so that only the relevant parts of the dictionary are visited.
+    pub fn search<A: Automaton>(&self, automaton: A) -> TermStreamerBuilderImpl<A> {
+        TermStreamerBuilderImpl::new(self, self.fst_index.search(automaton))
+    }
+}